@@ -29,16 +29,22 @@ struct FieldUniforms {
     pulse_speed: f32,
     glow_intensity: f32,
     line_width: f32,
-    _padding: vec3<f32>,
+    // Non-zero in colorblind modes, patterns tell the highlights apart as well as colour
+    highlight_patterns: u32,
+    // Preview, connected, empty connection point and existing target colours
+    highlight_colors: array<vec4<f32>, 4>,
 };
 
-// Storage buffer for connections
+// Storage buffer for connections, one entry per step along a connection's path
 struct ConnectionData {
     start_pos: vec2<f32>,
     end_pos: vec2<f32>,
     strength: f32,
     distance: f32,
-    _padding: vec2<f32>,
+    width: f32,
+    formed_pulse: f32,
+    path_offset: f32,
+    _padding: f32,
 };
 
 // Storage buffer for preview highlights
@@ -113,6 +119,11 @@ fn fragment(
     // Sample tile index from indices texture
     let tile_data = textureSample(tile_indices, tile_indices_sampler, uv);
     let tile_index = tile_data.r;
+
+    // Holes in the field have no ground at all
+    if (tile_data.a < 0.5) {
+        discard;
+    }
     
     // Get tile texture
     let tile_atlas_uv = get_tile_uv(tile_index, cell_uv);
@@ -157,25 +168,34 @@ fn fragment(
         }
         
         // Calculate mycelium line
-        let line_alpha = sdf_line_segment(uv, connection.start_pos, connection.end_pos, field_uniforms.line_width);
+        let line_alpha = sdf_line_segment(uv, connection.start_pos, connection.end_pos, connection.width);
         
         if (line_alpha > 0.0) {
-            // Energy pulse animation
-            let flow_offset = length(uv - connection.start_pos) / connection.distance;
+            // Energy pulse animation, measured along the whole path
+            let flow_offset = (connection.path_offset + length(uv - connection.start_pos)) / connection.distance;
             let pulse = sin((flow_offset - field_uniforms.time * field_uniforms.pulse_speed) * 6.28318) * 0.5 + 0.5;
             
             // Mycelium color with pulse
             let mycelium_color = mix(field_uniforms.mycelium_color_low, field_uniforms.mycelium_color_high, pulse * connection.strength);
-            let glow = 1.0 + pulse * field_uniforms.glow_intensity;
+            var glow = 1.0 + pulse * field_uniforms.glow_intensity;
+            
+            // Newly formed connection: a bright band sweeps from start to end as it fades
+            if (connection.formed_pulse > 0.0) {
+                let sweep = 1.0 - connection.formed_pulse;
+                let band = smoothstep(0.3, 0.0, abs(flow_offset - sweep));
+                glow = glow + connection.formed_pulse * (0.5 + band) * 2.0;
+            }
             
             // Blend mycelium over base
             base_color = mix(base_color, mycelium_color * glow, line_alpha * connection.strength);
         }
     }
     
+    let patterns = field_uniforms.highlight_patterns != 0u;
+    
     // Apply preview highlights
     if (preview_highlight > 0.0) {
-        let preview_color = vec4<f32>(0.2, 0.8, 1.0, 1.0);
+        let preview_color = field_uniforms.highlight_colors[0];
         base_color = mix(base_color, preview_color, preview_highlight * 0.5);
         
         // Edge highlight
@@ -186,9 +206,14 @@ fn fragment(
     }
     
     if (connected_highlight > 0.0) {
-        let connected_color = vec4<f32>(0.2, 1.0, 0.4, 1.0);
+        let connected_color = field_uniforms.highlight_colors[1];
         base_color = mix(base_color, connected_color, connected_highlight * 0.4);
         
+        // Dots
+        if (patterns && length(fract(cell_uv * 3.0) - 0.5) < 0.18) {
+            base_color = mix(base_color, connected_color, 0.7);
+        }
+        
         // Edge highlight
         let edge_dist = min(min(cell_uv.x, 1.0 - cell_uv.x), min(cell_uv.y, 1.0 - cell_uv.y));
         if (edge_dist < 0.03) {
@@ -197,9 +222,14 @@ fn fragment(
     }
     
     if (empty_connection_highlight > 0.0) {
-        let empty_color = vec4<f32>(1.0, 0.3, 0.2, 1.0);
+        let empty_color = field_uniforms.highlight_colors[2];
         base_color = mix(base_color, empty_color, empty_connection_highlight * 0.4);
         
+        // Diagonal stripes
+        if (patterns && fract((cell_uv.x - cell_uv.y) * 4.0) < 0.3) {
+            base_color = mix(base_color, empty_color, 0.6);
+        }
+        
         // Dashed edge
         let edge_dist = min(min(cell_uv.x, 1.0 - cell_uv.x), min(cell_uv.y, 1.0 - cell_uv.y));
         let dash_pattern = sin((cell_uv.x + cell_uv.y) * 20.0 + field_uniforms.time * 8.0) * 0.5 + 0.5;
@@ -209,11 +239,12 @@ fn fragment(
     }
     
     if (existing_target_highlight > 0.0) {
-        let target_color = vec4<f32>(0.3, 0.6, 1.0, 1.0);
+        let target_color = field_uniforms.highlight_colors[3];
         
-        // Thin edge outline
+        // Thin edge outline, thicker with patterns on so it reads without the colour
         let edge_dist = min(min(cell_uv.x, 1.0 - cell_uv.x), min(cell_uv.y, 1.0 - cell_uv.y));
-        if (edge_dist < 0.02) {
+        let outline_width = select(0.02, 0.07, patterns);
+        if (edge_dist < outline_width) {
             base_color = mix(base_color, target_color, existing_target_highlight * 0.6);
         }
     }
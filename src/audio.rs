@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{audio::SpatialScale, prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Music>();
@@ -36,6 +36,31 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
+/// A sound effect played from a point in the world, heard relative to the [`SpatialListener`].
+pub fn spatial_sound_effect(handle: Handle<AudioSource>, translation: Vec3) -> impl Bundle {
+    pitched_spatial_sound_effect(handle, translation, 1.0)
+}
+
+/// A [`spatial_sound_effect`] played at `speed`, which raises or lowers its pitch along with it.
+pub fn pitched_spatial_sound_effect(
+    handle: Handle<AudioSource>,
+    translation: Vec3,
+    speed: f32,
+) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN
+            .with_spatial(true)
+            .with_spatial_scale(SpatialScale::new(SPATIAL_AUDIO_SCALE))
+            .with_speed(speed),
+        Transform::from_translation(translation),
+        SoundEffect,
+    )
+}
+
+/// Scale applied to world positions for spatial sounds, so panning across the board stays subtle.
+const SPATIAL_AUDIO_SCALE: f32 = 0.15;
+
 /// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
 fn apply_global_volume(
     global_volume: Res<GlobalVolume>,
@@ -1,13 +1,19 @@
 // Debugging the turn manager state
 
-use bevy::{dev_tools::states::log_transitions, prelude::*};
+use bevy::{
+    dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
+};
 use bevy_inspector_egui::{
     bevy_egui::{EguiContextPass, EguiContexts},
     egui,
 };
 
 use crate::{
-    game::game_flow::{CurrentLevel, LevelState, TurnData, TurnPhase},
+    game::{
+        game_flow::{CurrentLevel, LevelState, TurnData, TurnPhase},
+        mushrooms::replay::{ChainReplay, ReplayChainEvent},
+        rng::GameRng,
+    },
     screens::Screen,
 };
 
@@ -16,11 +22,36 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, log_transitions::<LevelState>);
     app.add_systems(Update, log_transitions::<TurnPhase>);
 
+    // Fix the seed so a run's card sequence can be reproduced
+    app.add_systems(
+        Update,
+        toggle_fixed_seed.run_if(input_just_pressed(FIXED_SEED_KEY)),
+    );
+
     // Add turn state debug window
     app.add_systems(
         EguiContextPass,
         turn_state_debug_window.run_if(in_state(Screen::Gameplay)),
     );
+    app.add_systems(
+        EguiContextPass,
+        chain_replay_debug_window.run_if(in_state(LevelState::Playing)),
+    );
+}
+
+/// Not an F key, those can be bound to dialogue choices
+const FIXED_SEED_KEY: KeyCode = KeyCode::Insert;
+/// Seed used when the fixed seed is toggled on
+const DEV_FIXED_SEED: u64 = 1234;
+
+/// Switch between the fixed dev seed and entropy, applied from the next level load
+fn toggle_fixed_seed(mut game_rng: ResMut<GameRng>) {
+    let seed = match game_rng.seed {
+        Some(_) => None,
+        None => Some(DEV_FIXED_SEED),
+    };
+    game_rng.reseed(seed);
+    info!("Game seed set to {:?}", seed);
 }
 
 /// Show turn state information in an egui window
@@ -30,6 +61,7 @@ pub(crate) fn turn_state_debug_window(
     current_phase: Option<Res<State<TurnPhase>>>,
     turn_data: Res<TurnData>,
     current_level: Res<CurrentLevel>,
+    game_rng: Res<GameRng>,
 ) {
     egui::Window::new("Turn State Debug")
         .default_pos([10.0, 200.0])
@@ -53,6 +85,11 @@ pub(crate) fn turn_state_debug_window(
                 "Total Spores: {:.0}",
                 current_level.total_spores_earned
             ));
+            ui.label(format!("Level Seed: {}", current_level.seed));
+            ui.label(match game_rng.seed {
+                Some(seed) => format!("Game Seed: {seed} (Insert to clear)"),
+                None => "Game Seed: random (Insert to fix)".to_string(),
+            });
 
             ui.separator();
 
@@ -72,3 +109,23 @@ pub(crate) fn turn_state_debug_window(
             ));
         });
 }
+
+/// Replay the last finished chain at a chosen speed, F11 uses the same speed
+fn chain_replay_debug_window(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut replay: ResMut<ChainReplay>,
+) {
+    egui::Window::new("Chain Replay")
+        .default_pos([10.0, 520.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.add(egui::Slider::new(&mut replay.speed, 0.25..=4.0).text("Speed"));
+            if replay.is_playing() {
+                ui.label("Replaying...");
+            } else if ui.button("Replay last chain (F11)").clicked() {
+                commands.trigger(ReplayChainEvent {
+                    speed: replay.speed,
+                });
+            }
+        });
+}
@@ -9,9 +9,10 @@ use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 use crate::screens::Screen;
 
 mod game_flow;
+mod mushroom_stats;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(game_flow::plugin);
+    app.add_plugins((game_flow::plugin, mushroom_stats::plugin));
 
     // Log `Screen` state transitions.
     app.add_systems(Update, log_transitions::<Screen>);
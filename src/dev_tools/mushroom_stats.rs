@@ -0,0 +1,144 @@
+//! Debug overlay with each mushroom's activation state
+//!
+//! Press F10 to toggle a billboard over every placed mushroom showing its uses this
+//! turn, the energy of its last activation and the cooldown left.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy_rich_text3d::{Text3d, TextAtlas};
+
+use crate::game::{
+    game_flow::LevelState,
+    mushrooms::{Mushroom, MushroomActivationState},
+    visual_effects::FaceCamera,
+};
+
+const TOGGLE_KEY: KeyCode = KeyCode::F10;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MushroomStatsOverlay>();
+    app.add_systems(
+        Update,
+        (
+            toggle_mushroom_stats.run_if(input_just_pressed(TOGGLE_KEY)),
+            update_mushroom_stats,
+        )
+            .chain()
+            .run_if(in_state(LevelState::Playing)),
+    );
+}
+
+/// Whether the stats overlay is shown
+#[derive(Resource, Default, Debug)]
+struct MushroomStatsOverlay {
+    enabled: bool,
+}
+
+/// Billboard with the stats of the mushroom it's a child of
+#[derive(Component)]
+struct MushroomStatsLabel;
+
+fn toggle_mushroom_stats(
+    mut commands: Commands,
+    mut overlay: ResMut<MushroomStatsOverlay>,
+    labels: Query<Entity, With<MushroomStatsLabel>>,
+) {
+    overlay.enabled = !overlay.enabled;
+    info!(
+        "Mushroom stats overlay {}",
+        if overlay.enabled { "on" } else { "off" }
+    );
+
+    if !overlay.enabled {
+        for entity in &labels {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn stats_text(state: &MushroomActivationState) -> String {
+    let cooldown = state
+        .cooldown_timer
+        .as_ref()
+        .map_or(0.0, Timer::remaining_secs);
+    format!(
+        "uses {}\nenergy {:.2}\ncd {:.2}s",
+        state.activations_this_turn, state.last_activation_energy, cooldown
+    )
+}
+
+/// Refresh every label and give new mushrooms one
+fn update_mushroom_stats(
+    mut commands: Commands,
+    overlay: Res<MushroomStatsOverlay>,
+    mushrooms: Query<(Entity, &MushroomActivationState, Option<&Children>), With<Mushroom>>,
+    labels: Query<(), With<MushroomStatsLabel>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut material: Local<Option<Handle<StandardMaterial>>>,
+) {
+    if !overlay.enabled {
+        return;
+    }
+
+    let material = material
+        .get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color_texture: Some(TextAtlas::DEFAULT_IMAGE.clone()),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                base_color: Color::srgb(0.6, 1.0, 0.9),
+                ..Default::default()
+            })
+        })
+        .clone();
+
+    for (mushroom, state, children) in &mushrooms {
+        let text = Text3d::new(stats_text(state));
+        let label = children
+            .into_iter()
+            .flatten()
+            .copied()
+            .find(|child| labels.contains(*child));
+
+        match label {
+            Some(label) => {
+                commands.entity(label).insert(text);
+            }
+            None => {
+                commands.spawn((
+                    Name::new("Mushroom Stats Label"),
+                    text,
+                    Mesh3d::default(),
+                    Transform::from_xyz(0.0, 1.0, 0.0).with_scale(Vec3::splat(0.012)),
+                    MeshMaterial3d(material.clone()),
+                    MushroomStatsLabel,
+                    FaceCamera,
+                    ChildOf(mushroom),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn turning_the_overlay_off_despawns_every_label() {
+        let mut world = World::new();
+        world.insert_resource(MushroomStatsOverlay { enabled: true });
+        let mushroom = world.spawn(MushroomActivationState::default()).id();
+        let label = world.spawn((MushroomStatsLabel, ChildOf(mushroom))).id();
+
+        world.run_system_once(toggle_mushroom_stats).unwrap();
+
+        assert!(!world.resource::<MushroomStatsOverlay>().enabled);
+        assert!(world.get_entity(label).is_err());
+        assert!(world.get_entity(mushroom).is_ok());
+
+        world.run_system_once(toggle_mushroom_stats).unwrap();
+
+        assert!(world.resource::<MushroomStatsOverlay>().enabled);
+    }
+}
@@ -20,12 +20,15 @@ use crate::{
             markers::{Draggable, Dragged},
         },
         level::assets::LevelAssets,
-        mushrooms::{MushroomDefinitions, MushroomType},
+        mushrooms::{
+            MushroomDefinitions, MushroomType, definitions::NEEDS_DOWNSTREAM_HINT,
+            upgrades::MushroomUpgrades,
+        },
     },
     screens::Screen,
 };
 
-use super::constants::CARD_SIZE;
+use super::constants::{CARD_BADGE_SIZE, CARD_MUSHROOM_SCALE, CARD_SIZE};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Card>();
@@ -42,16 +45,15 @@ pub struct CardTemplates {
 
 impl CardTemplates {
     #[tracing::instrument(skip_all)]
-    pub fn draw_random_card(&self) -> &CardTemplate {
+    pub fn draw_random_card(&self, rng: &mut impl Rng) -> &CardTemplate {
         let rarity_choices = [Rarity::Common, Rarity::Uncommon, Rarity::Rare];
         info!("Rarity choices: {:?}", rarity_choices);
         let rarity_weights = [40, 40, 20];
         info!("Rarity weights: {:?}", rarity_weights);
         let dist = WeightedIndex::new(rarity_weights).unwrap();
         info!("Distribution: {:?}", dist);
-        let mut rng = rand::rng();
 
-        let card_rarity = rarity_choices[dist.sample(&mut rng)];
+        let card_rarity = rarity_choices[dist.sample(rng)];
         info!("Chosen rarity: {:?}", card_rarity);
         let card_choices: Vec<&CardTemplate> = self
             .cards
@@ -72,91 +74,139 @@ pub fn create_card_definitions(mut card_templates: ResMut<CardTemplates>) -> Res
             name: "Basic".into(),
             mushroom_type: MushroomType::Basic,
             rarity: Rarity::Uncommon,
+            wildcard: false,
         },
         CardTemplate {
             name: "Pulse".into(),
             mushroom_type: MushroomType::Pulse,
             rarity: Rarity::Common,
+            wildcard: false,
         },
         CardTemplate {
             name: "Sideways".into(),
             mushroom_type: MushroomType::Sideways,
             rarity: Rarity::Common,
+            wildcard: false,
         },
         CardTemplate {
             name: "Fork".into(),
             mushroom_type: MushroomType::Fork,
             rarity: Rarity::Common,
+            wildcard: false,
         },
         CardTemplate {
             name: "Threeway".into(),
             mushroom_type: MushroomType::Threeway,
             rarity: Rarity::Rare,
+            wildcard: false,
         },
         CardTemplate {
             name: "Diagonal".into(),
             mushroom_type: MushroomType::Diagonal,
             rarity: Rarity::Common,
+            wildcard: false,
         },
         CardTemplate {
             name: "Surround".into(),
             mushroom_type: MushroomType::Surround,
             rarity: Rarity::Rare,
+            wildcard: false,
         },
         CardTemplate {
             name: "Skipper".into(),
             mushroom_type: MushroomType::Skipper,
             rarity: Rarity::Uncommon,
+            wildcard: false,
         },
         CardTemplate {
             name: "Deleter".into(),
             mushroom_type: MushroomType::Deleter,
             rarity: Rarity::Uncommon,
+            wildcard: false,
         },
         CardTemplate {
             name: "Bomb".into(),
             mushroom_type: MushroomType::Bomb,
             rarity: Rarity::Rare,
+            wildcard: false,
         },
         CardTemplate {
             name: "Amplifier".into(),
             mushroom_type: MushroomType::Amplifier,
             rarity: Rarity::Uncommon,
+            wildcard: false,
         },
         CardTemplate {
             name: "Twoway_amplifier".into(),
             mushroom_type: MushroomType::TwoWayAmplifier,
             rarity: Rarity::Rare,
+            wildcard: false,
         },
         CardTemplate {
             name: "Threeway_Amplifier".into(),
             mushroom_type: MushroomType::ThreeWayAmplifier,
             rarity: Rarity::Rare,
+            wildcard: false,
         },
         CardTemplate {
             name: "Chain".into(),
             mushroom_type: MushroomType::Chain,
             rarity: Rarity::Uncommon,
+            wildcard: false,
         },
         CardTemplate {
             name: "Burst".into(),
             mushroom_type: MushroomType::Burst,
             rarity: Rarity::Rare,
+            wildcard: false,
         },
         CardTemplate {
             name: "Converter".into(),
             mushroom_type: MushroomType::Converter,
             rarity: Rarity::Uncommon,
+            wildcard: false,
         },
         CardTemplate {
             name: "Knight".into(),
             mushroom_type: MushroomType::Knight,
             rarity: Rarity::Rare,
+            wildcard: false,
         },
         CardTemplate {
             name: "Unblocker".into(),
             mushroom_type: MushroomType::Unblocker,
             rarity: Rarity::Rare,
+            wildcard: false,
+        },
+        CardTemplate {
+            name: "Spawner".into(),
+            mushroom_type: MushroomType::Spawner,
+            rarity: Rarity::Rare,
+            wildcard: false,
+        },
+        CardTemplate {
+            name: "Splitter".into(),
+            mushroom_type: MushroomType::Splitter,
+            rarity: Rarity::Rare,
+            wildcard: false,
+        },
+        CardTemplate {
+            name: "Hub".into(),
+            mushroom_type: MushroomType::Hub,
+            rarity: Rarity::Rare,
+            wildcard: false,
+        },
+        CardTemplate {
+            name: "Capacitor".into(),
+            mushroom_type: MushroomType::Capacitor,
+            rarity: Rarity::Uncommon,
+            wildcard: false,
+        },
+        CardTemplate {
+            name: "Wildcard".into(),
+            mushroom_type: MushroomType::Basic,
+            rarity: Rarity::Rare,
+            wildcard: true,
         },
     ];
     info!("Done creating templates");
@@ -169,6 +219,8 @@ pub struct CardTemplate {
     pub name: String,
     pub mushroom_type: MushroomType,
     pub rarity: Rarity,
+    /// Wildcards let the player choose any unlocked mushroom when played
+    pub wildcard: bool,
 }
 
 #[derive(Component, Clone, Debug, Reflect)]
@@ -179,6 +231,8 @@ pub struct Card {
     pub mushroom_type: MushroomType,
     pub rarity: Rarity,
     pub origin: Transform,
+    /// When set, `mushroom_type` is ignored and the player picks one on play
+    pub wildcard: bool,
 }
 
 impl From<&CardTemplate> for Card {
@@ -188,6 +242,7 @@ impl From<&CardTemplate> for Card {
             mushroom_type: value.mushroom_type,
             rarity: value.rarity,
             origin: Transform::from_translation(Vec3::ZERO),
+            wildcard: value.wildcard,
         }
     }
 }
@@ -199,11 +254,13 @@ impl Default for Card {
             mushroom_type: MushroomType::Basic,
             rarity: Rarity::Common,
             origin: Transform::from_translation(Vec3::ZERO),
+            wildcard: false,
         }
     }
 }
 
-#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Reflect)]
+/// Ordered from most to least common
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Reflect)]
 pub enum Rarity {
     #[default]
     Common,
@@ -211,6 +268,10 @@ pub enum Rarity {
     Rare,
 }
 
+/// Marker for the mushroom sprite shown on a card's face
+#[derive(Component)]
+pub struct CardMushroomSprite;
+
 #[derive(Bundle)]
 pub struct CardBundle {
     pub name: Name,
@@ -244,6 +305,7 @@ pub fn spawn_card(
     card_component: Card,
     hand_entity: Entity,
     mushroom_definitions: &Res<MushroomDefinitions>,
+    upgrades: &MushroomUpgrades,
     level_assets: &Res<LevelAssets>,
     atlas_layout_handle: &Handle<TextureAtlasLayout>,
     asset_server: &Res<AssetServer>,
@@ -293,7 +355,11 @@ pub fn spawn_card(
             commands.spawn((
                 CARD_LAYER,
                 Anchor::TopCenter,
-                Text2d::new(mushroom_definition.name.clone()),
+                Text2d::new(if card_component.wildcard {
+                    card_component.name.clone()
+                } else {
+                    mushroom_definition.name.clone()
+                }),
                 TextColor(tailwind::STONE_200.into()),
                 TextLayout::new(JustifyText::Center, LineBreak::NoWrap),
                 TextFont {
@@ -305,19 +371,55 @@ pub fn spawn_card(
                 Transform::from_xyz(0.0, (CARD_SIZE.y / 2.0) - 10.0, 1.0),
             ));
 
+            // Production Badge, sitting over the top left corner
+            if !card_component.wildcard {
+                commands
+                    .spawn((
+                        CARD_LAYER,
+                        Sprite::from_color(tailwind::AMBER_500, CARD_BADGE_SIZE),
+                        Transform::from_xyz(-CARD_SIZE.x / 2.0 + 4.0, CARD_SIZE.y / 2.0 - 4.0, 2.0),
+                    ))
+                    .with_children(|badge| {
+                        badge.spawn((
+                            CARD_LAYER,
+                            Text2d::new(format_production(mushroom_definition.base_production)),
+                            TextColor(tailwind::STONE_900.into()),
+                            TextLayout::new(JustifyText::Center, LineBreak::NoWrap),
+                            TextFont {
+                                font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                                font_size: 18.0,
+                                font_smoothing: FontSmoothing::AntiAliased,
+                                ..default()
+                            },
+                            Transform::from_xyz(0.0, 0.0, 1.0),
+                        ));
+                    });
+            }
+
             // Mushroom Sprite
             commands.spawn((
                 CARD_LAYER,
                 mushroom_sprite,
-                Transform::from_xyz(0.0, 0.0, 1.0).with_scale(Vec3::splat(3.0)),
+                CardMushroomSprite,
+                Transform::from_xyz(0.0, 0.0, 1.0).with_scale(Vec3::splat(CARD_MUSHROOM_SCALE)),
                 StateScoped(Screen::Gameplay),
             ));
 
             // Activation Limit
-            let card_text = format!(
-                "Triggers: {}\n{}",
-                mushroom_definition.max_uses_per_turn, mushroom_definition.description,
-            );
+            let max_uses = upgrades.max_uses(card_component.mushroom_type, mushroom_definition);
+            let card_text = if card_component.wildcard {
+                "Becomes any unlocked mushroom.".to_string()
+            } else if mushroom_definition.needs_downstream() {
+                format!(
+                    "Triggers: {}\n{}\n{}",
+                    max_uses, mushroom_definition.description, NEEDS_DOWNSTREAM_HINT,
+                )
+            } else {
+                format!(
+                    "Triggers: {}\n{}",
+                    max_uses, mushroom_definition.description,
+                )
+            };
             commands.spawn((
                 CARD_LAYER,
                 Anchor::TopCenter,
@@ -340,3 +442,39 @@ pub fn spawn_card(
 
     Ok(card_entity)
 }
+
+/// Production for the card badge, whole numbers without a decimal point
+fn format_production(production: f64) -> String {
+    if production.fract() == 0.0 {
+        format!("{production:.0}")
+    } else {
+        format!("{production:.1}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::rng::GameRng;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn same_seed_draws_the_same_cards() {
+        let mut world = World::new();
+        world.init_resource::<CardTemplates>();
+        world
+            .run_system_once(create_card_definitions)
+            .unwrap()
+            .unwrap();
+        let templates = world.resource::<CardTemplates>();
+
+        let draw_ten = |seed| {
+            let mut rng = GameRng::new(Some(seed));
+            (0..10)
+                .map(|_| templates.draw_random_card(&mut rng).name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(draw_ten(42), draw_ten(42));
+    }
+}
@@ -10,3 +10,9 @@ pub const CARD_LAYER: RenderLayers = RenderLayers::layer(2);
 pub const CARD_SIZE: Vec2 = Vec2::new(140.0, 190.0);
 pub const CARD_IN_PLAY_POSITION: Vec3 = Vec3::new(400.0, 360.0, 0.0);
 pub const HAND_SIZE_LIMIT: usize = 7;
+pub const STARTING_DECK_SIZE: usize = 30;
+pub const MAX_DECK_SIZE: usize = 60;
+pub const CARD_MUSHROOM_SCALE: f32 = 3.0;
+pub const CARD_BADGE_SIZE: Vec2 = Vec2::new(34.0, 26.0);
+pub const PREVIEW_PULSE_DURATION: f32 = 0.4;
+pub const PREVIEW_PULSE_SCALE: f32 = 1.1;
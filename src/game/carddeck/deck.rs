@@ -2,22 +2,80 @@
 //!
 //! A deck is an ordered collection of cards that can be drawn from, added to, shuffled, etc.
 //! stored as a resource in the world when active.
+//!
+//! Played cards go to the [`DiscardPile`], which is shuffled back in once the deck runs out.
 
 use bevy::prelude::*;
-use rand::{rng, seq::SliceRandom};
+use rand::{Rng, seq::SliceRandom};
 use std::collections::VecDeque;
 
-use crate::game::carddeck::card::Card;
+use crate::game::{
+    carddeck::{
+        card::{Card, CardTemplates},
+        constants::{MAX_DECK_SIZE, STARTING_DECK_SIZE},
+        presets::{ActiveDeckPreset, DeckPresets},
+    },
+    game_flow::{CurrentLevel, LevelState},
+    level::definitions::LevelDefinitions,
+    mushrooms::{MushroomDefinitions, MushroomType},
+    resources::GameState,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    // app.register_type::<Deck>();
+    app.register_type::<Deck>();
+    app.register_type::<DiscardPile>();
+
+    app.init_resource::<Deck>();
+    app.init_resource::<DiscardPile>();
 
-    // app.init_resource::<Deck>();
+    app.add_systems(OnEnter(LevelState::StartDialogue), create_deck);
 }
 
-#[derive(Resource, Default, Debug, Reflect)]
+#[derive(Resource, Debug, Reflect)]
 pub struct Deck {
     cards: VecDeque<Card>,
+    /// Most cards the deck can hold
+    pub max_size: usize,
+    /// Times the discard pile has been shuffled back in this level
+    reshuffles: u32,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self {
+            cards: VecDeque::new(),
+            max_size: MAX_DECK_SIZE,
+            reshuffles: 0,
+        }
+    }
+}
+
+/// Cards played this level, waiting to be shuffled back into the deck
+#[derive(Resource, Debug, Default, Reflect)]
+pub struct DiscardPile {
+    cards: Vec<Card>,
+}
+
+impl DiscardPile {
+    pub fn add(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    pub fn get_card_count(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cards.clear();
+    }
 }
 
 impl Deck {
@@ -27,10 +85,8 @@ impl Deck {
     /// must first be used, with the return shuffled.
     ///
     /// See [`VecDeque::make_contiguous`] for details.
-    #[allow(dead_code)]
-    pub fn shuffle(&mut self) -> Result {
-        let mut rng = rng();
-        self.cards.make_contiguous().shuffle(&mut rng);
+    pub fn shuffle(&mut self, rng: &mut impl Rng) -> Result {
+        self.cards.make_contiguous().shuffle(rng);
 
         Ok(())
     }
@@ -41,7 +97,6 @@ impl Deck {
     /// return an [`Option<T>`] if there are no cards in the deck.
     /// It is left to the caller of this method to handle this situation
     /// as they see fit.
-    #[allow(dead_code)]
     #[tracing::instrument(name = "from deck", skip_all)]
     pub fn draw(&mut self) -> Option<Card> {
         self.cards.pop_front()
@@ -62,28 +117,88 @@ impl Deck {
     }
 
     /// Add a card to the bottom of the deck
-    #[allow(dead_code)]
+    ///
+    /// Fails without adding the card if the deck already holds [`Deck::max_size`] cards.
     #[tracing::instrument(name = "Adding card to deck", skip_all)]
     pub fn add_to_bottom(&mut self, card: Card) -> Result {
+        if self.is_full() {
+            return Err(format!("Deck is full ({} cards)", self.max_size).into());
+        }
         self.cards.push_back(card);
 
         Ok(())
     }
 
     /// Get count of remaining cards
-    #[allow(dead_code)]
     pub fn get_card_count(&self) -> usize {
         self.cards.len()
     }
 
+    /// Whether the deck has reached its size limit
+    pub fn is_full(&self) -> bool {
+        self.cards.len() >= self.max_size
+    }
+
+    /// Cards in draw order, top of the deck first
+    pub fn iter(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter()
+    }
+
     /// Empty this deck
     #[tracing::instrument(skip_all)]
     pub fn empty_deck(&mut self) -> Result {
         self.cards.drain(..);
+        self.reshuffles = 0;
 
         Ok(())
     }
 
+    /// Shuffle the discard pile back in, returning how many cards came back
+    ///
+    /// Only as many cards as the deck has room for come back, the rest stay in the
+    /// discard pile. Each reshuffle in a level uses its own stream of the level's seed, so
+    /// a retry with the same plays deals the same cards.
+    #[tracing::instrument(skip_all)]
+    pub fn reshuffle_discard(
+        &mut self,
+        discard: &mut DiscardPile,
+        current_level: &CurrentLevel,
+    ) -> Result<usize> {
+        let room = self.max_size.saturating_sub(self.cards.len());
+        let count = discard.cards.len().min(room);
+        if count < discard.cards.len() {
+            warn!(
+                "Deck only has room for {} of {} discarded cards",
+                count,
+                discard.cards.len()
+            );
+        }
+        self.cards.extend(discard.cards.drain(..count));
+
+        let stream = RESHUFFLE_RNG_STREAM ^ (u64::from(self.reshuffles) << 32);
+        self.shuffle(&mut current_level.rng(stream))?;
+        self.reshuffles += 1;
+
+        Ok(count)
+    }
+
+    /// Draw the top card, shuffling the discard pile back in first if the deck is empty
+    ///
+    /// Also returns how many cards were shuffled back in, 0 if there was no reshuffle.
+    pub fn draw_or_reshuffle(
+        &mut self,
+        discard: &mut DiscardPile,
+        current_level: &CurrentLevel,
+    ) -> Result<(Option<Card>, usize)> {
+        let reshuffled = if self.cards.is_empty() && !discard.is_empty() {
+            self.reshuffle_discard(discard, current_level)?
+        } else {
+            0
+        };
+
+        Ok((self.draw(), reshuffled))
+    }
+
     // /// Refresh this deck from the original deck
     // #[tracing::instrument(skip_all)]
     // pub fn refresh_deck(&mut self, template_deck: Res<Deck>) -> Result {
@@ -94,6 +209,105 @@ impl Deck {
     // }
 }
 
+/// Stream of the level's random numbers used for building the deck
+const DECK_RNG_STREAM: u64 = 1;
+/// Stream of the level's random numbers used for reshuffling the discard pile
+const RESHUFFLE_RNG_STREAM: u64 = 3;
+
+/// Build a fresh, shuffled deck for the level from the card templates
+///
+/// Random cards and the shuffle come from the level's seed, so retrying with the
+/// same draws deals the same deck.
+///
+/// Uses the level's `starting_deck` if it has one, then the [`ActiveDeckPreset`] if
+/// one is chosen, falling back to random cards when neither has anything usable on
+/// this level.
+#[tracing::instrument(name = "Create deck", skip_all)]
+pub fn create_deck(
+    mut deck: ResMut<Deck>,
+    mut discard: ResMut<DiscardPile>,
+    card_templates: Res<CardTemplates>,
+    presets: Res<DeckPresets>,
+    active_preset: Res<ActiveDeckPreset>,
+    definitions: Res<MushroomDefinitions>,
+    game_state: Res<GameState>,
+    current_level: Res<CurrentLevel>,
+    level_definitions: Res<LevelDefinitions>,
+) -> Result {
+    deck.empty_deck()?;
+    discard.clear();
+    let mut rng = current_level.rng(DECK_RNG_STREAM);
+
+    if card_templates.cards.is_empty() {
+        warn!("No card templates available, deck will be empty");
+        return Ok(());
+    }
+
+    // The level's own deck isn't checked against unlocks, the level decides its tools
+    let level_counts = level_definitions
+        .get_level(current_level.level_index)
+        .map(|level| level.starting_deck.clone())
+        .unwrap_or_default();
+    stock_deck(&mut deck, &card_templates, &definitions, &level_counts)?;
+
+    if deck.get_card_count() == 0 {
+        let preset_counts = active_preset
+            .0
+            .as_deref()
+            .and_then(|name| presets.get(name))
+            .map(|preset| preset.validated(&definitions, &game_state, &current_level))
+            .unwrap_or_default();
+        stock_deck(&mut deck, &card_templates, &definitions, &preset_counts)?;
+    }
+
+    if deck.get_card_count() == 0 {
+        for _ in 0..STARTING_DECK_SIZE {
+            deck.add_to_bottom(Card::from(card_templates.draw_random_card(&mut rng)))?;
+        }
+    }
+    deck.shuffle(&mut rng)?;
+
+    info!("Created deck with {} cards", deck.get_card_count());
+
+    Ok(())
+}
+
+/// Add `count` cards of each type to the deck, skipping types without a definition or
+/// card template
+fn stock_deck(
+    deck: &mut Deck,
+    card_templates: &CardTemplates,
+    definitions: &MushroomDefinitions,
+    counts: &[(MushroomType, u32)],
+) -> Result {
+    for (mushroom_type, count) in counts.iter().copied() {
+        if definitions.get(mushroom_type).is_none() {
+            warn!(
+                "Unknown mushroom type {:?} in deck, skipping it",
+                mushroom_type
+            );
+            continue;
+        }
+        let Some(template) = card_templates
+            .cards
+            .iter()
+            .find(|template| template.mushroom_type == mushroom_type && !template.wildcard)
+        else {
+            warn!("No card template for {:?}, skipping it", mushroom_type);
+            continue;
+        };
+        for _ in 0..count {
+            if deck.is_full() {
+                warn!("Deck list is larger than the deck, truncating it");
+                return Ok(());
+            }
+            deck.add_to_bottom(Card::from(template))?;
+        }
+    }
+
+    Ok(())
+}
+
 // /// Empty the deck
 // #[tracing::instrument(skip_all)]
 // pub fn empty_active_deck(mut deck: ResMut<Deck>) -> Result {
@@ -101,3 +315,107 @@ impl Deck {
 
 //     Ok(())
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::carddeck::card::create_card_definitions;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn adding_to_a_full_deck_is_rejected() {
+        let mut deck = Deck {
+            max_size: 2,
+            ..default()
+        };
+        deck.add_to_bottom(Card::default()).unwrap();
+        deck.add_to_bottom(Card::default()).unwrap();
+        assert!(deck.is_full());
+
+        assert!(deck.add_to_bottom(Card::default()).is_err());
+        assert_eq!(deck.get_card_count(), 2);
+    }
+
+    #[test]
+    fn stocked_deck_holds_every_listed_card() {
+        let mut world = World::new();
+        world.init_resource::<CardTemplates>();
+        world
+            .run_system_once(create_card_definitions)
+            .unwrap()
+            .unwrap();
+        let counts = [
+            (MushroomType::Pulse, 3),
+            (MushroomType::Basic, 2),
+            // Listed twice, both entries are added
+            (MushroomType::Pulse, 1),
+        ];
+
+        let mut deck = Deck::default();
+        stock_deck(
+            &mut deck,
+            world.resource::<CardTemplates>(),
+            &MushroomDefinitions::standard(),
+            &counts,
+        )
+        .unwrap();
+
+        let listed: u32 = counts.iter().map(|(_, count)| count).sum();
+        assert_eq!(deck.get_card_count(), listed as usize);
+        let pulses = deck
+            .iter()
+            .filter(|card| card.mushroom_type == MushroomType::Pulse)
+            .count();
+        assert_eq!(pulses, 4);
+    }
+
+    #[test]
+    fn drawing_past_the_deck_pulls_reshuffled_cards() {
+        let mut deck = Deck {
+            max_size: 3,
+            ..default()
+        };
+        for _ in 0..3 {
+            deck.add_to_bottom(Card::default()).unwrap();
+        }
+        let mut discard = DiscardPile::default();
+        let current_level = CurrentLevel::default();
+
+        // Play every card as it's drawn, going through the deck three times
+        let mut drawn = 0;
+        let mut reshuffled = 0;
+        for _ in 0..9 {
+            let (card, cards) = deck
+                .draw_or_reshuffle(&mut discard, &current_level)
+                .unwrap();
+            discard.add(card.unwrap());
+            drawn += 1;
+            reshuffled += cards;
+        }
+
+        assert_eq!(drawn, 9);
+        assert_eq!(reshuffled, 6);
+        assert_eq!(deck.reshuffles, 2);
+    }
+
+    #[test]
+    fn reshuffle_leaves_cards_that_dont_fit_in_the_discard_pile() {
+        let mut deck = Deck {
+            max_size: 3,
+            ..default()
+        };
+        deck.add_to_bottom(Card::default()).unwrap();
+        let mut discard = DiscardPile::default();
+        for _ in 0..4 {
+            discard.add(Card::default());
+        }
+
+        let count = deck
+            .reshuffle_discard(&mut discard, &CurrentLevel::default())
+            .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(deck.get_card_count(), 3);
+        assert_eq!(discard.get_card_count(), 2);
+    }
+}
@@ -11,3 +11,10 @@ pub struct DrawEvent(pub u32);
 /// Event to fire when cards have been added to the hand
 #[derive(Event, Debug)]
 pub struct HandChangeEvent;
+
+/// Event to fire when the discard pile is shuffled back into the deck
+#[derive(Event, Debug)]
+pub struct DeckReshuffleEvent {
+    /// Number of cards shuffled back in
+    pub cards: usize,
+}
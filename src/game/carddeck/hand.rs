@@ -2,36 +2,50 @@
 //!
 //! The hand contains the cards that have been drawn and are currently playable by the player.
 //! These are drawn from the deck.
+//!
+//! The hand can be kept sorted by mushroom type or rarity, toggled with S or the sort button.
 
 use bevy::prelude::*;
-use std::collections::VecDeque;
+use std::{cmp::Ordering, collections::VecDeque};
 
 use crate::{
+    PausableSystems,
     game::{
         carddeck::{
-            card::{Card, CardTemplates, spawn_card},
+            card::{Card, spawn_card},
             constants::{CARD_LAYER, CARD_SIZE, CARD_SPACING, HAND_SIZE_LIMIT},
-            events::{DrawEvent, HandChangeEvent},
+            deck::{Deck, DiscardPile},
+            events::{DeckReshuffleEvent, DrawEvent, HandChangeEvent},
             markers::Dragged,
         },
-        game_flow::LevelCompleteAction,
+        game_flow::{CurrentLevel, LevelCompleteAction},
         level::assets::LevelAssets,
-        mushrooms::MushroomDefinitions,
+        mushrooms::{MushroomDefinitions, upgrades::MushroomUpgrades},
         ui::GameplayUI,
     },
     screens::Screen,
 };
 
+const SORT_KEY: KeyCode = KeyCode::KeyS;
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Hand>();
 
     app.init_resource::<Hand>();
+    app.init_resource::<HandSortOrder>();
 
     app.add_systems(OnEnter(Screen::Gameplay), spawn_hand_entity);
     app.add_systems(OnExit(Screen::Gameplay), empty_hand_on_exit);
+    app.add_systems(
+        Update,
+        cycle_sort_with_key
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
 
     app.add_observer(update_card_origins)
         .add_observer(draw_n)
+        .add_observer(cycle_hand_sort)
         .add_observer(empty_hand_on_level_complete);
 }
 
@@ -69,23 +83,58 @@ pub struct Hand {
 
 impl Hand {
     /// Get count of cards in hand
-    #[allow(dead_code)]
     pub fn get_card_count(&self) -> usize {
         self.cards.len()
     }
 
-    /// Despawn a card with the given entity
-    pub fn despawn_card(&mut self, mut commands: Commands, card_entity: Entity) -> Result {
+    /// Cards currently in the hand, in order
+    pub fn iter(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter().map(|(card, _)| card)
+    }
+
+    /// Entity of the card at `index` in the hand, if there is one
+    pub fn card_entity(&self, index: usize) -> Option<Entity> {
+        self.cards.get(index).and_then(|(_, entity)| *entity)
+    }
+
+    /// Despawn a card with the given entity, returning the card if it was in the hand
+    pub fn despawn_card(
+        &mut self,
+        mut commands: Commands,
+        card_entity: Entity,
+    ) -> Result<Option<Card>> {
         for (index, (_, entity)) in self.cards.iter().enumerate() {
             if *entity == Some(card_entity) {
                 commands.entity(card_entity).despawn();
-                self.cards.remove(index);
+                let removed = self.cards.remove(index).map(|(card, _)| card);
                 commands.trigger(HandChangeEvent);
-                return Ok(());
+                return Ok(removed);
             }
         }
 
-        Ok(())
+        Ok(None)
+    }
+
+    /// Sort the hand by `order`, cards that are `pinned` keep their place
+    ///
+    /// Cards with equal keys keep their current order.
+    pub fn sort(&mut self, order: HandSortOrder, pinned: impl Fn(Entity) -> bool) {
+        if order == HandSortOrder::Unsorted {
+            return;
+        }
+
+        let (kept, mut loose): (Vec<_>, Vec<_>) = self
+            .cards
+            .drain(..)
+            .enumerate()
+            .partition(|(_, (_, entity))| entity.is_some_and(&pinned));
+        loose.sort_by(|(_, (a, _)), (_, (b, _))| order.compare(a, b));
+
+        self.cards = loose.into_iter().map(|(_, card)| card).collect();
+        // In index order, so each pinned card lands back in its old slot
+        for (index, card) in kept {
+            self.cards.insert(index, card);
+        }
     }
 
     pub fn empty_hand(&mut self, mut commands: Commands) -> Result {
@@ -115,10 +164,83 @@ impl Default for Hand {
 #[derive(Component)]
 pub struct HandEntity;
 
+/// How the hand is kept arranged, applied again whenever cards are drawn
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HandSortOrder {
+    /// Cards stay in the order they were drawn
+    #[default]
+    Unsorted,
+    Type,
+    Rarity,
+}
+
+impl HandSortOrder {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unsorted => "Unsorted",
+            Self::Type => "Type",
+            Self::Rarity => "Rarity",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Unsorted => Self::Type,
+            Self::Type => Self::Rarity,
+            Self::Rarity => Self::Unsorted,
+        }
+    }
+
+    /// Wildcards go after the other cards of their rarity
+    fn compare(self, a: &Card, b: &Card) -> Ordering {
+        match self {
+            Self::Unsorted => Ordering::Equal,
+            Self::Type => (a.wildcard, a.mushroom_type).cmp(&(b.wildcard, b.mushroom_type)),
+            Self::Rarity => (a.rarity, a.wildcard, a.mushroom_type).cmp(&(
+                b.rarity,
+                b.wildcard,
+                b.mushroom_type,
+            )),
+        }
+    }
+}
+
+/// Switch to the next sort order and rearrange the hand
+#[derive(Event, Debug)]
+pub struct CycleHandSortEvent;
+
+fn cycle_sort_with_key(mut commands: Commands, keyboard: Res<ButtonInput<KeyCode>>) {
+    if keyboard.just_pressed(SORT_KEY) {
+        commands.trigger(CycleHandSortEvent);
+    }
+}
+
+fn cycle_hand_sort(
+    _: Trigger<CycleHandSortEvent>,
+    mut commands: Commands,
+    mut order: ResMut<HandSortOrder>,
+    mut hand: ResMut<Hand>,
+    dragged: Query<&Dragged, With<Card>>,
+) {
+    *order = order.next();
+    info!("Sorting hand by {}", order.label());
+
+    hand.sort(*order, |entity| is_pinned(&dragged, entity));
+    commands.trigger(HandChangeEvent);
+}
+
+/// Cards being dragged or played stay put, so they don't jump while sorting
+fn is_pinned(dragged: &Query<&Dragged, With<Card>>, entity: Entity) -> bool {
+    dragged
+        .get(entity)
+        .is_ok_and(|dragged| *dragged != Dragged::Released)
+}
+
 /// Draw N cards from deck into hand
 ///
 /// Will check that cards will fit and cards remaining in deck, and
-/// adjust amount to draw as needed.
+/// adjust amount to draw as needed. An empty deck is refilled from the
+/// [`DiscardPile`] first.
 ///
 /// When the card is drawn, [`spawn_card`] is triggered to also create
 /// the entity that will represent the card visually.
@@ -128,9 +250,14 @@ pub fn draw_n(
     mut commands: Commands,
     mut hand: ResMut<Hand>,
     hand_entity: Query<Entity, With<HandEntity>>,
-    card_templates: Res<CardTemplates>,
+    mut deck: ResMut<Deck>,
+    mut discard: ResMut<DiscardPile>,
+    current_level: Res<CurrentLevel>,
     mushroom_definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
     level_assets: Res<LevelAssets>,
+    sort_order: Res<HandSortOrder>,
+    dragged: Query<&Dragged, With<Card>>,
     mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     mut local_atlas_layout: Local<Option<Handle<TextureAtlasLayout>>>,
     asset_server: Res<AssetServer>,
@@ -160,14 +287,22 @@ pub fn draw_n(
     }
 
     for _ in 0..cards_to_draw {
-        let drawn_card = card_templates.draw_random_card();
+        let (card, reshuffled) = deck.draw_or_reshuffle(&mut discard, &current_level)?;
+        if reshuffled > 0 {
+            commands.trigger(DeckReshuffleEvent { cards: reshuffled });
+        }
+
+        let Some(card_component) = card else {
+            info!("Deck is empty, no more cards to draw");
+            break;
+        };
 
-        let card_component = Card::from(drawn_card);
         let card_entity = spawn_card(
             commands.reborrow(),
             card_component.clone(),
             hand_entity,
             &mushroom_definitions,
+            &upgrades,
             &level_assets,
             &atlas_layout_handle,
             &asset_server,
@@ -176,6 +311,7 @@ pub fn draw_n(
         hand.cards.push_back((card_component, Some(card_entity)));
     }
 
+    hand.sort(*sort_order, |entity| is_pinned(&dragged, entity));
     commands.trigger(HandChangeEvent);
 
     Ok(())
@@ -190,21 +326,23 @@ fn update_card_origins(
     _: Trigger<HandChangeEvent>,
     mut commands: Commands,
     hand: Res<Hand>,
-    mut cards_query: Query<(&mut Card, &Transform)>,
+    mut cards_query: Query<(&mut Card, &Transform, Option<&Dragged>)>,
 ) -> Result {
-    let number_of_cards: f32 = hand.get_card_count() as f32;
+    let number_of_cards = hand.get_card_count();
     debug!("Number of cards: {}", number_of_cards);
     debug!("Using spacing: {}", CARD_SPACING);
 
-    let first_card_offset: f32 = ((number_of_cards - 1.0) / 2.0) * (CARD_SPACING + CARD_SIZE.x);
-
     for (index, card_tuple) in hand.cards.iter().enumerate() {
         if let (_, Some(entity)) = card_tuple {
-            let (mut card_component, card_transform) = cards_query.get_mut(*entity)?;
+            let (mut card_component, card_transform, dragged) = cards_query.get_mut(*entity)?;
+            // A card being dragged or played keeps its origin until it's released
+            if dragged.is_some_and(|dragged| *dragged != Dragged::Released) {
+                continue;
+            }
             let new_origin = card_component
                 .origin
                 .translation
-                .with_x(-first_card_offset + (index as f32 * (CARD_SPACING + CARD_SIZE.x)))
+                .with_x(card_offset(index, number_of_cards))
                 .with_z((index + 1) as f32);
             debug!("Card at index {} will have offset {}", index, new_origin.x);
 
@@ -220,6 +358,12 @@ fn update_card_origins(
     Ok(())
 }
 
+/// Horizontal offset of the card at `index`, the hand is centred on the middle card
+fn card_offset(index: usize, number_of_cards: usize) -> f32 {
+    let first_card_offset = ((number_of_cards as f32 - 1.0) / 2.0) * (CARD_SPACING + CARD_SIZE.x);
+    -first_card_offset + (index as f32 * (CARD_SPACING + CARD_SIZE.x))
+}
+
 #[tracing::instrument(skip_all)]
 fn empty_hand_on_level_complete(
     _: Trigger<LevelCompleteAction>,
@@ -230,3 +374,57 @@ fn empty_hand_on_level_complete(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{carddeck::card::Rarity, mushrooms::MushroomType};
+
+    fn card(mushroom_type: MushroomType, rarity: Rarity) -> Card {
+        Card {
+            mushroom_type,
+            rarity,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn rarity_sort_puts_common_cards_first() {
+        let mut hand = Hand::default();
+        let cards = [
+            card(MushroomType::Bomb, Rarity::Rare),
+            card(MushroomType::Pulse, Rarity::Common),
+            card(MushroomType::Basic, Rarity::Uncommon),
+            card(MushroomType::Fork, Rarity::Common),
+        ];
+        for (index, card) in cards.into_iter().enumerate() {
+            hand.cards
+                .push_back((card, Some(Entity::from_raw(index as u32))));
+        }
+
+        hand.sort(HandSortOrder::Rarity, |_| false);
+
+        let sorted: Vec<_> = hand
+            .iter()
+            .map(|card| (card.rarity, card.mushroom_type))
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                (Rarity::Common, MushroomType::Pulse),
+                (Rarity::Common, MushroomType::Fork),
+                (Rarity::Uncommon, MushroomType::Basic),
+                (Rarity::Rare, MushroomType::Bomb),
+            ]
+        );
+    }
+
+    #[test]
+    fn card_offsets_increase_across_a_centred_hand() {
+        let offsets: Vec<f32> = (0..5).map(|index| card_offset(index, 5)).collect();
+
+        assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(offsets[2], 0.0);
+        assert_eq!(offsets[0], -offsets[4]);
+    }
+}
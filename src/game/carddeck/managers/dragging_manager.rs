@@ -1,13 +1,17 @@
 use bevy::prelude::*;
 
-use crate::game::{
-    carddeck::{
-        card::Card,
-        managers::{create_tween_move_to_play, create_tween_return_to_origin},
-        markers::{Draggable, Dragged},
+use crate::{
+    game::{
+        carddeck::{
+            card::Card,
+            managers::{create_tween_move_to_play, create_tween_return_to_origin},
+            markers::{Draggable, Dragged},
+            wildcard::OpenWildcardPicker,
+        },
+        game_flow::TurnPhase,
+        mushrooms::SelectedMushroomType,
     },
-    game_flow::TurnPhase,
-    mushrooms::SelectedMushroomType,
+    settings::AnimationStyle,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -94,6 +98,7 @@ pub fn on_card_darg_start(
 #[tracing::instrument(skip_all)]
 pub fn on_card_drag_end(
     trigger: Trigger<Pointer<DragEnd>>,
+    mut commands: Commands,
     mut cards_being_dragged: Query<(&mut Dragged, &Card)>,
     mut selected_type: ResMut<SelectedMushroomType>,
     window: Query<&Window>,
@@ -103,7 +108,13 @@ pub fn on_card_drag_end(
     if let Ok((mut dragged_component, card)) = cards_being_dragged.get_mut(trigger.target) {
         if trigger.pointer_location.position.y < window.height() * 0.8 {
             *dragged_component = Dragged::Played;
-            selected_type.mushroom_type = Some(card.mushroom_type);
+            if card.wildcard {
+                // The mushroom type is chosen from the picker
+                selected_type.mushroom_type = None;
+                commands.trigger(OpenWildcardPicker);
+            } else {
+                selected_type.mushroom_type = Some(card.mushroom_type);
+            }
         } else {
             *dragged_component = Dragged::Released;
         };
@@ -116,6 +127,7 @@ pub fn on_card_drag_end(
 pub fn move_cards_back_to_origin(
     mut commands: Commands,
     mut cards_being_dragged: Query<(Entity, &Transform, &Card, &Dragged), Changed<Dragged>>,
+    animation_style: Res<AnimationStyle>,
 ) -> Result {
     for (card_entity, card_transform, card_component, dragged_component) in
         cards_being_dragged.iter_mut()
@@ -127,10 +139,16 @@ pub fn move_cards_back_to_origin(
                     card_entity,
                     card_component,
                     card_transform,
+                    *animation_style,
                 )?;
             }
             Dragged::Played => {
-                create_tween_move_to_play(commands.reborrow(), card_entity, card_transform)?;
+                create_tween_move_to_play(
+                    commands.reborrow(),
+                    card_entity,
+                    card_transform,
+                    *animation_style,
+                )?;
             }
             _ => continue,
         }
@@ -1,7 +1,16 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
-use bevy_tweening::lens::TransformScaleLens;
+use bevy_tweening::{Animator, RepeatCount, RepeatStrategy, Tween, lens::TransformScaleLens};
 
-use crate::game::carddeck::{card::Card, markers::Hovered};
+use crate::{
+    game::carddeck::{
+        card::{Card, CardMushroomSprite},
+        constants::{CARD_MUSHROOM_SCALE, PREVIEW_PULSE_DURATION, PREVIEW_PULSE_SCALE},
+        markers::{Hovered, PreviewAnimator},
+    },
+    settings::{AnimationStyle, ReducedMotion},
+};
 
 use super::create_card_scale_tween;
 
@@ -46,17 +55,40 @@ fn on_hover_finish(
 #[tracing::instrument(skip_all)]
 fn on_hover_added(
     trigger: Trigger<OnAdd, Hovered>,
-    commands: Commands,
-    cards_query: Query<(Entity, &Card), (With<Hovered>, With<Card>)>,
+    mut commands: Commands,
+    cards_query: Query<(Entity, &Card, &Children), (With<Hovered>, With<Card>)>,
+    mushroom_sprites: Query<Entity, With<CardMushroomSprite>>,
+    animation_style: Res<AnimationStyle>,
+    reduced_motion: Res<ReducedMotion>,
 ) -> Result {
-    let (card_entity, card_component) = cards_query.get(trigger.target())?;
+    let (card_entity, card_component, children) = cards_query.get(trigger.target())?;
+
+    // Pulse the mushroom like it does when it activates on the board
+    if !reduced_motion.0 {
+        for sprite in mushroom_sprites.iter_many(children) {
+            let pulse = Tween::new(
+                EaseFunction::SineInOut,
+                Duration::from_secs_f32(PREVIEW_PULSE_DURATION),
+                TransformScaleLens {
+                    start: Vec3::splat(CARD_MUSHROOM_SCALE),
+                    end: Vec3::splat(CARD_MUSHROOM_SCALE * PREVIEW_PULSE_SCALE),
+                },
+            )
+            .with_repeat_count(RepeatCount::Infinite)
+            .with_repeat_strategy(RepeatStrategy::MirroredRepeat);
+
+            commands
+                .entity(sprite)
+                .with_child((PreviewAnimator, Animator::new(pulse).with_target(sprite)));
+        }
+    }
 
     let scale_lens = TransformScaleLens {
         start: card_component.origin.scale,
         end: card_component.origin.scale * 1.1,
     };
 
-    create_card_scale_tween(commands, card_entity, scale_lens)?;
+    create_card_scale_tween(commands, card_entity, scale_lens, *animation_style)?;
 
     Ok(())
 }
@@ -64,17 +96,31 @@ fn on_hover_added(
 #[tracing::instrument(skip_all)]
 fn on_hover_removed(
     trigger: Trigger<OnRemove, Hovered>,
-    commands: Commands,
-    cards_query: Query<(Entity, &Card), (With<Hovered>, With<Card>)>,
+    mut commands: Commands,
+    cards_query: Query<(Entity, &Card, &Children), (With<Hovered>, With<Card>)>,
+    mut mushroom_sprites: Query<(&mut Transform, Option<&Children>), With<CardMushroomSprite>>,
+    preview_animators: Query<Entity, With<PreviewAnimator>>,
+    animation_style: Res<AnimationStyle>,
 ) -> Result {
-    let (card_entity, card_component) = cards_query.get(trigger.target())?;
+    let (card_entity, card_component, children) = cards_query.get(trigger.target())?;
+
+    // Stop the activation preview and put the mushroom back to its resting size
+    for &child in children {
+        let Ok((mut transform, sprite_children)) = mushroom_sprites.get_mut(child) else {
+            continue;
+        };
+        transform.scale = Vec3::splat(CARD_MUSHROOM_SCALE);
+        for animator in preview_animators.iter_many(sprite_children.into_iter().flatten()) {
+            commands.entity(animator).despawn();
+        }
+    }
 
     let scale_lens = TransformScaleLens {
         start: card_component.origin.scale * 1.1,
         end: card_component.origin.scale,
     };
 
-    create_card_scale_tween(commands, card_entity, scale_lens)?;
+    create_card_scale_tween(commands, card_entity, scale_lens, *animation_style)?;
 
     Ok(())
 }
@@ -10,9 +10,10 @@ use crate::{
     game::carddeck::{
         card::Card,
         constants::{SCALE_TWEEN_DURATION, TRANSLATION_TWEEN_DURATION},
-        markers::Dragged,
+        markers::{Dragged, PreviewAnimator},
     },
     screens::Screen,
+    settings::AnimationStyle,
 };
 
 use super::constants::CARD_IN_PLAY_POSITION;
@@ -40,9 +41,10 @@ pub fn create_card_scale_tween(
     mut commands: Commands,
     card_entity: Entity,
     scale_lens: TransformScaleLens,
+    style: AnimationStyle,
 ) -> Result {
     let scale_tween = Tween::new(
-        EaseFunction::QuadraticInOut,
+        style.ease(),
         Duration::from_secs_f32(SCALE_TWEEN_DURATION),
         scale_lens,
     )
@@ -61,9 +63,10 @@ pub fn create_tween_return_to_origin(
     card_entity: Entity,
     card_component: &Card,
     card_transform: &Transform,
+    style: AnimationStyle,
 ) -> Result {
     let move_tween = Tween::new(
-        EaseFunction::QuadraticInOut,
+        style.ease(),
         Duration::from_secs_f32(TRANSLATION_TWEEN_DURATION),
         TransformPositionLens {
             start: card_transform.translation,
@@ -84,9 +87,10 @@ pub fn create_tween_move_to_play(
     mut commands: Commands,
     card_entity: Entity,
     card_transform: &Transform,
+    style: AnimationStyle,
 ) -> Result {
     let move_tween = Tween::new(
-        EaseFunction::QuadraticInOut,
+        style.ease(),
         Duration::from_secs_f32(TRANSLATION_TWEEN_DURATION),
         TransformPositionLens {
             start: card_transform.translation,
@@ -123,7 +127,7 @@ fn on_finish_transform_tween(
 #[tracing::instrument(skip_all)]
 fn cleanup_finished_animators(
     mut commands: Commands,
-    animator_query: Query<(Entity, &Animator<Transform>)>,
+    animator_query: Query<(Entity, &Animator<Transform>), Without<PreviewAnimator>>,
 ) -> Result {
     for (entity, animator) in animator_query {
         if animator.tweenable().progress() == 1.0 {
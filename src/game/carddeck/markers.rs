@@ -16,3 +16,7 @@ pub enum Dragged {
 /// A marker component to indicate that a card is being hovered
 #[derive(Component)]
 pub struct Hovered;
+
+/// A marker component for the looping activation preview on a hovered card
+#[derive(Component)]
+pub struct PreviewAnimator;
@@ -2,12 +2,23 @@ use bevy::prelude::*;
 
 pub(crate) mod card;
 pub(crate) mod constants;
-// mod deck;
+pub(crate) mod deck;
 pub(crate) mod events;
 pub(crate) mod hand;
 mod managers;
 pub(crate) mod markers;
+pub(crate) mod presets;
+mod ui;
+pub(crate) mod wildcard;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((hand::plugin, card::plugin, managers::plugin));
+    app.add_plugins((
+        hand::plugin,
+        card::plugin,
+        deck::plugin,
+        managers::plugin,
+        wildcard::plugin,
+        presets::plugin,
+        ui::plugin,
+    ));
 }
@@ -0,0 +1,149 @@
+//! # Deck Presets
+//!
+//! Named deck compositions the player can save and pick before a run.
+//! A preset stores how many cards of each mushroom type the deck holds; the chosen
+//! preset is used in place of a random deck the next time a level starts.
+//!
+//! Presets are kept in the save along with the deck in play.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    carddeck::{
+        deck::{Deck, DiscardPile},
+        hand::Hand,
+    },
+    game_flow::CurrentLevel,
+    mushrooms::{MushroomDefinitions, MushroomType},
+    resources::GameState,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DeckPresets>();
+    app.init_resource::<ActiveDeckPreset>();
+}
+
+/// A saved deck composition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeckPreset {
+    pub name: String,
+    /// Number of cards of each type, wildcards are not stored
+    pub counts: Vec<(MushroomType, u32)>,
+}
+
+impl DeckPreset {
+    /// Capture the cards in the deck, hand and discard pile, grouped by type
+    pub fn from_cards(
+        name: impl Into<String>,
+        deck: &Deck,
+        hand: &Hand,
+        discard: &DiscardPile,
+    ) -> Self {
+        let mut counts: Vec<(MushroomType, u32)> = Vec::new();
+        for card in deck
+            .iter()
+            .chain(hand.iter())
+            .chain(discard.iter())
+            .filter(|card| !card.wildcard)
+        {
+            match counts.iter_mut().find(|(ty, _)| *ty == card.mushroom_type) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((card.mushroom_type, 1)),
+            }
+        }
+
+        Self {
+            name: name.into(),
+            counts,
+        }
+    }
+
+    /// Total number of cards in the preset
+    pub fn card_count(&self) -> u32 {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Drop the entries the player can't use on this level, keeping the rest
+    pub fn validated(
+        &self,
+        definitions: &MushroomDefinitions,
+        game_state: &GameState,
+        current_level: &CurrentLevel,
+    ) -> Vec<(MushroomType, u32)> {
+        self.counts
+            .iter()
+            .copied()
+            .filter(|(mushroom_type, count)| {
+                *count > 0
+                    && *mushroom_type != MushroomType::Test
+                    && definitions.is_unlocked(
+                        *mushroom_type,
+                        game_state,
+                        current_level.level_index,
+                    )
+            })
+            .collect()
+    }
+}
+
+/// Every saved preset, in the order they were saved
+#[derive(Resource, Default, Debug)]
+pub struct DeckPresets {
+    pub presets: Vec<DeckPreset>,
+}
+
+impl DeckPresets {
+    /// Save a preset, replacing any existing one with the same name
+    pub fn save(&mut self, preset: DeckPreset) {
+        match self.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&DeckPreset> {
+        self.presets.iter().find(|p| p.name == name)
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.presets.retain(|p| p.name != name);
+    }
+
+    /// First "Preset N" name not already taken
+    pub fn next_name(&self) -> String {
+        (1..)
+            .map(|n| format!("Preset {n}"))
+            .find(|name| self.get(name).is_none())
+            .unwrap()
+    }
+}
+
+/// Preset to build the deck from when the next level starts, or a random deck if `None`
+#[derive(Resource, Default, Debug)]
+pub struct ActiveDeckPreset(pub Option<String>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_round_trip_through_the_save_format() {
+        let mut presets = DeckPresets::default();
+        presets.save(DeckPreset {
+            name: "Pulse heavy".to_string(),
+            counts: vec![(MushroomType::Pulse, 6), (MushroomType::Basic, 2)],
+        });
+        presets.save(DeckPreset {
+            name: presets.next_name(),
+            counts: vec![(MushroomType::Splitter, 1)],
+        });
+
+        let contents = ron::to_string(&presets.presets).unwrap();
+        let restored: Vec<DeckPreset> = ron::from_str(&contents).unwrap();
+
+        assert_eq!(restored, presets.presets);
+        assert_eq!(restored[1].name, "Preset 1");
+        assert_eq!(restored[0].card_count(), 8);
+    }
+}
@@ -0,0 +1,214 @@
+//! # Deck UI
+//!
+//! Shows how many cards are left in the deck out of its limit and how many are in the discard
+//! pile, and flashes a notice when the discard pile is shuffled back in. A button next to the
+//! count switches how the hand is sorted.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    game::{
+        carddeck::{
+            deck::{Deck, DiscardPile},
+            events::DeckReshuffleEvent,
+            hand::{CycleHandSortEvent, HandSortOrder},
+        },
+        ui::GameplayUI,
+    },
+    screens::Screen,
+    theme::{assets::ThemeAssets, palette as ui_palette, widget::slice_1_slicer},
+};
+
+/// How long the reshuffle notice stays on screen
+const RESHUFFLE_NOTICE_DURATION: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (spawn_deck_display, spawn_hand_sort_button),
+    );
+    app.add_systems(
+        Update,
+        (
+            update_deck_display,
+            update_hand_sort_label,
+            fade_reshuffle_notice,
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+
+    app.add_observer(show_reshuffle_notice);
+}
+
+/// Marker for the deck count text
+#[derive(Component)]
+struct DeckCountDisplay;
+
+/// Marker for the hand sort button's text
+#[derive(Component)]
+struct HandSortLabel;
+
+/// A "Shuffling!" notice that fades out over its timer
+#[derive(Component)]
+struct ReshuffleNotice {
+    timer: Timer,
+}
+
+fn spawn_deck_display(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Deck Count"),
+        Text::new("Deck: 0"),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 24.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(ui_palette::LABEL_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(210.0),
+            left: Val::Px(20.0),
+            ..default()
+        },
+        DeckCountDisplay,
+        GameplayUI,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn update_deck_display(
+    deck: Res<Deck>,
+    discard: Res<DiscardPile>,
+    mut display: Query<&mut Text, With<DeckCountDisplay>>,
+) {
+    if !deck.is_changed() && !discard.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = display.single_mut() {
+        text.0 = format!(
+            "Deck: {}/{}  Discard: {}",
+            deck.get_card_count(),
+            deck.max_size,
+            discard.get_card_count()
+        );
+    }
+}
+
+fn spawn_hand_sort_button(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme_assets: Res<ThemeAssets>,
+    order: Res<HandSortOrder>,
+) {
+    commands
+        .spawn((
+            Name::new("Hand Sort Button"),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(204.0),
+                left: Val::Px(330.0),
+                width: Val::Px(150.0),
+                height: Val::Px(36.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Button,
+            ImageNode {
+                image: theme_assets.slice_1.clone(),
+                image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                color: Color::WHITE,
+                ..default()
+            },
+            GameplayUI,
+            StateScoped(Screen::Gameplay),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Button Text"),
+                Text::new(sort_label(*order)),
+                TextFont {
+                    font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                    font_size: 16.0,
+                    font_smoothing: FontSmoothing::AntiAliased,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                HandSortLabel,
+                Pickable::IGNORE,
+            ));
+        })
+        .observe(|_: Trigger<Pointer<Click>>, mut commands: Commands| {
+            commands.trigger(CycleHandSortEvent);
+        });
+}
+
+fn sort_label(order: HandSortOrder) -> String {
+    format!("Sort: {} (S)", order.label())
+}
+
+fn update_hand_sort_label(
+    order: Res<HandSortOrder>,
+    mut labels: Query<&mut Text, With<HandSortLabel>>,
+) {
+    if !order.is_changed() {
+        return;
+    }
+
+    for mut text in &mut labels {
+        text.0 = sort_label(*order);
+    }
+}
+
+fn show_reshuffle_notice(
+    trigger: Trigger<DeckReshuffleEvent>,
+    mut commands: Commands,
+    existing: Query<Entity, With<ReshuffleNotice>>,
+    asset_server: Res<AssetServer>,
+) {
+    info!("Shuffled {} cards back into the deck", trigger.cards);
+
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+
+    commands.spawn((
+        Name::new("Reshuffle Notice"),
+        Text::new("Shuffling!"),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 28.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(ui_palette::HEADER_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(240.0),
+            left: Val::Px(20.0),
+            ..default()
+        },
+        ReshuffleNotice {
+            timer: Timer::from_seconds(RESHUFFLE_NOTICE_DURATION, TimerMode::Once),
+        },
+        GameplayUI,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn fade_reshuffle_notice(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut notices: Query<(Entity, &mut ReshuffleNotice, &mut TextColor)>,
+) {
+    for (entity, mut notice, mut color) in &mut notices {
+        notice.timer.tick(time.delta());
+        color.0.set_alpha(notice.timer.fraction_remaining());
+
+        if notice.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! # Wildcard
+//!
+//! A wildcard card isn't tied to a mushroom. When it's played the player picks which
+//! unlocked mushroom it becomes, then places it like any other card.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    game::{
+        carddeck::{card::Card, markers::Dragged},
+        game_flow::{CurrentLevel, TurnPhase},
+        mushrooms::{MushroomDefinitions, MushroomType, SelectedMushroomType},
+        resources::GameState,
+        ui::GameplayUI,
+    },
+    theme::{assets::ThemeAssets, widget::slice_1_slicer},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<OpenWildcardPicker>();
+    app.add_observer(spawn_wildcard_picker);
+    app.add_systems(Update, close_picker_when_released);
+}
+
+/// Event fired when a wildcard card has been played and needs a mushroom type
+#[derive(Event, Debug)]
+pub struct OpenWildcardPicker;
+
+/// Marker for the wildcard picker panel
+#[derive(Component)]
+struct WildcardPicker;
+
+/// Show a list of every unlocked mushroom for the wildcard to become
+fn spawn_wildcard_picker(
+    _: Trigger<OpenWildcardPicker>,
+    mut commands: Commands,
+    existing: Query<Entity, With<WildcardPicker>>,
+    definitions: Res<MushroomDefinitions>,
+    game_state: Res<GameState>,
+    current_level: Res<CurrentLevel>,
+    theme_assets: Res<ThemeAssets>,
+    asset_server: Res<AssetServer>,
+) {
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+
+    let font = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
+
+    let mut choices: Vec<(MushroomType, String)> = definitions
+        .all_types()
+        .into_iter()
+        .filter(|t| definitions.is_unlocked(*t, &game_state, current_level.level_index))
+        .filter_map(|t| definitions.get(t).map(|d| (t, d.name.clone())))
+        .collect();
+    choices.sort_by(|a, b| a.1.cmp(&b.1));
+
+    commands
+        .spawn((
+            Name::new("Wildcard Picker"),
+            WildcardPicker,
+            GameplayUI,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(100.0),
+                right: Val::Px(10.0),
+                width: Val::Px(300.0),
+                padding: UiRect::all(Val::Px(20.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            StateScoped(TurnPhase::Planting),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Wildcard Header"),
+                Text::new("Choose a mushroom"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 20.0,
+                    font_smoothing: FontSmoothing::AntiAliased,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            for (mushroom_type, name) in choices {
+                parent
+                    .spawn((
+                        Name::new(format!("Wildcard Choice - {name}")),
+                        Button,
+                        Node {
+                            padding: UiRect::all(Val::Px(8.0)),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        ImageNode {
+                            image: theme_assets.slice_1.clone(),
+                            image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ))
+                    .with_child((
+                        Text::new(name),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 16.0,
+                            font_smoothing: FontSmoothing::AntiAliased,
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                    ))
+                    .observe(
+                        move |_: Trigger<Pointer<Click>>,
+                              mut commands: Commands,
+                              mut selected: ResMut<SelectedMushroomType>,
+                              pickers: Query<Entity, With<WildcardPicker>>| {
+                            info!("Wildcard becomes {:?}", mushroom_type);
+                            selected.mushroom_type = Some(mushroom_type);
+                            for entity in &pickers {
+                                commands.entity(entity).despawn();
+                            }
+                        },
+                    );
+            }
+        });
+}
+
+/// Close the picker if the wildcard was put back in the hand before a choice was made
+fn close_picker_when_released(
+    mut commands: Commands,
+    pickers: Query<Entity, With<WildcardPicker>>,
+    cards: Query<(&Card, &Dragged)>,
+) {
+    if pickers.is_empty() {
+        return;
+    }
+
+    let wildcard_in_play = cards
+        .iter()
+        .any(|(card, dragged)| card.wildcard && *dragged == Dragged::Played);
+
+    if !wildcard_in_play {
+        for entity in &pickers {
+            commands.entity(entity).despawn();
+        }
+    }
+}
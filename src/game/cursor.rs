@@ -0,0 +1,87 @@
+//! Swaps the window cursor to hint at what a click will do
+//!
+//! - Planting with a mushroom selected: crosshair for placing it
+//! - Holding right click over a mushroom while planting: "not allowed" for deleting it
+//! - Chain phase: pointer for sparking a chain
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, SystemCursorIcon},
+    winit::cursor::CursorIcon,
+};
+
+use crate::{
+    game::{
+        game_flow::TurnPhase, mushrooms::SelectedMushroomType,
+        play_field::placement_preview::HoveredCell, resources::GameState,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_cursor_icon.run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), reset_cursor_icon);
+}
+
+/// What the cursor is hinting at
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum CursorIntent {
+    #[default]
+    None,
+    Plant,
+    Delete,
+    Spark,
+}
+
+impl CursorIntent {
+    fn icon(self) -> SystemCursorIcon {
+        match self {
+            CursorIntent::None => SystemCursorIcon::Default,
+            CursorIntent::Plant => SystemCursorIcon::Crosshair,
+            CursorIntent::Delete => SystemCursorIcon::NotAllowed,
+            CursorIntent::Spark => SystemCursorIcon::Pointer,
+        }
+    }
+}
+
+fn update_cursor_icon(
+    mut commands: Commands,
+    window: Single<(Entity, Option<&CursorIcon>), With<PrimaryWindow>>,
+    current_phase: Option<Res<State<TurnPhase>>>,
+    selected_type: Res<SelectedMushroomType>,
+    hovered_cell: Res<HoveredCell>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    game_state: Res<GameState>,
+) {
+    let hovering_mushroom = hovered_cell
+        .position
+        .is_some_and(|position| game_state.play_field.get(position).is_some());
+
+    let intent = match current_phase.as_deref().map(State::get) {
+        Some(TurnPhase::Planting)
+            if hovering_mushroom && mouse_buttons.pressed(MouseButton::Right) =>
+        {
+            CursorIntent::Delete
+        }
+        Some(TurnPhase::Planting) if selected_type.mushroom_type.is_some() => CursorIntent::Plant,
+        Some(TurnPhase::Chain) => CursorIntent::Spark,
+        _ => CursorIntent::None,
+    };
+
+    // Compare with the window itself, so a reset on leaving gameplay is picked up
+    let (window, current) = *window;
+    let icon = CursorIcon::from(intent.icon());
+    if current == Some(&icon) {
+        return;
+    }
+    commands.entity(window).insert(icon);
+}
+
+fn reset_cursor_icon(mut commands: Commands, window: Single<Entity, With<PrimaryWindow>>) {
+    commands
+        .entity(*window)
+        .insert(CursorIcon::from(SystemCursorIcon::Default));
+}
@@ -35,6 +35,14 @@ pub struct DialogueAssets {
     #[dependency]
     pub failure_dialogues: Vec<Handle<DialogueAsset>>,
 
+    // Pools picked by how the level went, falling back to the pools above when empty
+    #[dependency]
+    pub crushed_it_dialogues: Vec<Handle<DialogueAsset>>,
+    #[dependency]
+    pub barely_won_dialogues: Vec<Handle<DialogueAsset>>,
+    #[dependency]
+    pub close_loss_dialogues: Vec<Handle<DialogueAsset>>,
+
     // Portraits for characters
     #[dependency]
     pub portraits: PortraitAssets,
@@ -90,6 +98,21 @@ impl FromWorld for DialogueAssets {
                 asset_server.load("dialogues/failure/failure_4.dialogue.3.json"),
             ],
 
+            crushed_it_dialogues: vec![
+                asset_server.load("dialogues/success/crushed_it_1.dialogue.json"),
+                asset_server.load("dialogues/success/crushed_it_2.dialogue.json"),
+            ],
+
+            barely_won_dialogues: vec![
+                asset_server.load("dialogues/success/barely_won_1.dialogue.json"),
+                asset_server.load("dialogues/success/barely_won_2.dialogue.json"),
+            ],
+
+            close_loss_dialogues: vec![
+                asset_server.load("dialogues/failure/close_loss_1.dialogue.json"),
+                asset_server.load("dialogues/failure/close_loss_2.dialogue.json"),
+            ],
+
             portraits: PortraitAssets {
                 wizard: asset_server.load("images/portraits/wizard.png"),
                 witch: asset_server.load("images/portraits/witch.png"),
@@ -1,5 +1,7 @@
 //! Loading and managing dialogue assets
 
+use std::collections::HashSet;
+
 use bevy::prelude::*;
 use bevy::text::FontSmoothing;
 use funkus_dialogue_core::{
@@ -10,23 +12,97 @@ use funkus_dialogue_ui::DialogueDisplay;
 use funkus_dialogue_ui::DialogueText;
 use funkus_dialogue_ui::SpeakerText;
 use rand::prelude::*;
-use rand::rng;
 
 use crate::PausableSystems;
 use crate::game::{
     dialogue::assets::DialogueAssets,
-    game_flow::{CurrentLevel, LevelState},
+    game_flow::{CurrentLevel, LevelState, TurnData},
 };
+use crate::settings::{KeyBindings, key_label};
 use crate::theme::assets::ThemeAssets;
 use crate::theme::widget::slice_2_slicer;
 
 pub mod assets;
 
+/// Stream of the level's random numbers used for picking the end dialogue
+const END_DIALOGUE_RNG_STREAM: u64 = 2;
+
+/// Share of the target to beat it by for the "crushed it" outro
+const CRUSHED_IT_RATIO: f64 = 1.5;
+/// Wins under this share of the target that used every turn count as barely won
+const BARELY_WON_RATIO: f64 = 1.1;
+/// Losses at or above this share of the target count as close
+const CLOSE_LOSS_RATIO: f64 = 0.8;
+
+/// How the level went, for picking an outro that fits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Performance {
+    CrushedIt,
+    Won,
+    BarelyWon,
+    CloseLoss,
+    Lost,
+}
+
+impl Performance {
+    fn of(current_level: &CurrentLevel, turn_data: &TurnData) -> Self {
+        let ratio = if current_level.target_score > 0.0 {
+            current_level.total_spores_earned / current_level.target_score
+        } else {
+            1.0
+        };
+        let turns_used = turn_data.current_turn;
+
+        match current_level.level_completed_successfully {
+            Some(true) => {
+                // Winning in half the turns counts as crushing it too
+                if ratio >= CRUSHED_IT_RATIO || turns_used * 2 <= current_level.max_turns {
+                    Performance::CrushedIt
+                } else if ratio < BARELY_WON_RATIO && turns_used >= current_level.max_turns {
+                    Performance::BarelyWon
+                } else {
+                    Performance::Won
+                }
+            }
+            _ if ratio >= CLOSE_LOSS_RATIO => Performance::CloseLoss,
+            _ => Performance::Lost,
+        }
+    }
+
+    /// The pool for this performance, or the plain success or failure pool if it's empty
+    fn pool(self, dialogue_assets: &DialogueAssets) -> &Vec<Handle<DialogueAsset>> {
+        let (pool, fallback) = match self {
+            Performance::CrushedIt => (
+                &dialogue_assets.crushed_it_dialogues,
+                &dialogue_assets.success_dialogues,
+            ),
+            Performance::BarelyWon => (
+                &dialogue_assets.barely_won_dialogues,
+                &dialogue_assets.success_dialogues,
+            ),
+            Performance::CloseLoss => (
+                &dialogue_assets.close_loss_dialogues,
+                &dialogue_assets.failure_dialogues,
+            ),
+            Performance::Won => (
+                &dialogue_assets.success_dialogues,
+                &dialogue_assets.success_dialogues,
+            ),
+            Performance::Lost => (
+                &dialogue_assets.failure_dialogues,
+                &dialogue_assets.failure_dialogues,
+            ),
+        };
+        if pool.is_empty() { fallback } else { pool }
+    }
+}
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(assets::plugin);
 
     // Add the dialogue delay timer resource
     app.init_resource::<DialogueAdvanceDelay>();
+    app.init_resource::<SeenIntros>();
 
     // Systems for dialogue states
     app.add_systems(OnEnter(LevelState::StartDialogue), enter_start_dialogue);
@@ -96,6 +172,29 @@ impl DialogueAdvanceDelay {
     }
 }
 
+/// Levels whose intro has already played this session
+///
+/// Retries and replays skip an intro that's been seen, unless a replay was asked for.
+#[derive(Resource, Debug, Default)]
+pub struct SeenIntros {
+    seen: HashSet<usize>,
+    /// Play the next intro even if it's been seen
+    replay_next: bool,
+}
+
+impl SeenIntros {
+    /// Play the next level's intro again, even if it's been seen
+    pub fn request_replay(&mut self) {
+        self.replay_next = true;
+    }
+
+    /// Whether the intro for `level_index` should play, marking it as seen
+    fn should_play(&mut self, level_index: usize) -> bool {
+        let replay = std::mem::take(&mut self.replay_next);
+        self.seen.insert(level_index) || replay
+    }
+}
+
 /// Update the dialogue advance timer
 fn update_dialogue_advance_timer(
     time: Res<Time>,
@@ -136,6 +235,7 @@ fn update_dialogue_advance_timer(
 /// Handle keyboard input during dialogue
 fn handle_dialogue_input(
     keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     dialogue_query: Query<(Entity, &DialogueRunner)>,
     mut advance_events: EventWriter<AdvanceDialogue>,
     mut select_events: EventWriter<SelectDialogueChoice>,
@@ -147,8 +247,8 @@ fn handle_dialogue_input(
             continue;
         }
 
-        // Space to advance dialogue
-        if keyboard.just_pressed(KeyCode::Space) {
+        // Advance dialogue
+        if keyboard.just_pressed(bindings.advance) {
             match runner.state {
                 DialogueState::ShowingText => {
                     if delay.can_advance {
@@ -166,20 +266,7 @@ fn handle_dialogue_input(
         if runner.state == DialogueState::WaitingForChoice
             || matches!(runner.state, DialogueState::ChoiceSelected(_))
         {
-            for i in 0..9 {
-                let key = match i {
-                    0 => KeyCode::Digit1,
-                    1 => KeyCode::Digit2,
-                    2 => KeyCode::Digit3,
-                    3 => KeyCode::Digit4,
-                    4 => KeyCode::Digit5,
-                    5 => KeyCode::Digit6,
-                    6 => KeyCode::Digit7,
-                    7 => KeyCode::Digit8,
-                    8 => KeyCode::Digit9,
-                    _ => unreachable!(),
-                };
-
+            for (i, key) in bindings.choice_keys.keys().into_iter().enumerate() {
                 if keyboard.just_pressed(key) {
                     select_events.write(SelectDialogueChoice {
                         entity,
@@ -233,7 +320,9 @@ fn enter_start_dialogue(
     mut start_dialogue_events: EventWriter<StartDialogueEvent>,
     mut level_state: ResMut<NextState<LevelState>>,
     mut delay: ResMut<DialogueAdvanceDelay>,
+    mut seen_intros: ResMut<SeenIntros>,
     asset_server: Res<AssetServer>,
+    bindings: Res<KeyBindings>,
 ) {
     // Reset dialogue delay
     delay.reset(0.5);
@@ -249,6 +338,10 @@ fn enter_start_dialogue(
         _ => None,                                        // Other levels have no intro
     };
 
+    // Skip an intro that's already been seen this session
+    let play_intro = seen_intros.should_play(current_level.level_index);
+    let dialogue_handle = dialogue_handle.filter(|_| play_intro);
+
     if let Some(handle) = dialogue_handle {
         info!(
             "Starting intro dialogue for level {}",
@@ -265,7 +358,12 @@ fn enter_start_dialogue(
             .id();
 
         // Spawn UI
-        spawn_dialogue_ui(&mut commands, LevelState::StartDialogue, &asset_server);
+        spawn_dialogue_ui(
+            &mut commands,
+            LevelState::StartDialogue,
+            &asset_server,
+            bindings.advance,
+        );
 
         // Start dialogue
         start_dialogue_events.write(StartDialogueEvent {
@@ -273,9 +371,9 @@ fn enter_start_dialogue(
             dialogue_handle: handle,
         });
     } else {
-        // No intro dialogue for this level, skip straight to playing
+        // No intro dialogue to play for this level, skip straight to playing
         info!(
-            "No intro dialogue for level {}, starting gameplay",
+            "No intro dialogue to play for level {}, starting gameplay",
             current_level.level_index + 1
         );
         level_state.set(LevelState::Playing);
@@ -303,10 +401,12 @@ fn enter_end_dialogue(
     mut commands: Commands,
     dialogue_assets: Res<DialogueAssets>,
     current_level: Res<CurrentLevel>,
+    turn_data: Res<TurnData>,
     mut start_dialogue_events: EventWriter<StartDialogueEvent>,
     mut delay: ResMut<DialogueAdvanceDelay>,
     level_definitions: Res<crate::game::level::definitions::LevelDefinitions>,
     asset_server: Res<AssetServer>,
+    bindings: Res<KeyBindings>,
 ) {
     info!("Starting level outro dialogue");
 
@@ -323,23 +423,23 @@ fn enter_end_dialogue(
             info!("Using final level success dialogue - game complete!");
             dialogue_assets.final_level_success.clone()
         } else {
-            // For all other cases (including final level failure), use normal random selection
-            let dialogue_pool: &Vec<Handle<DialogueAsset>> =
-                match current_level.level_completed_successfully {
-                    Some(true) => &dialogue_assets.success_dialogues,
-                    Some(false) => &dialogue_assets.failure_dialogues,
-                    None => {
-                        warn!("No completion status set, defaulting to failure dialogue");
-                        &dialogue_assets.failure_dialogues
-                    }
-                };
+            // For all other cases (including final level failure), pick randomly from
+            // the pool matching how the level went
+            if current_level.level_completed_successfully.is_none() {
+                warn!("No completion status set, defaulting to failure dialogue");
+            }
+            let performance = Performance::of(&current_level, &turn_data);
+            info!("Level outro for performance {:?}", performance);
+            let dialogue_pool = performance.pool(&dialogue_assets);
 
             // Pick a random dialogue from the pool
             if dialogue_pool.is_empty() {
                 error!("No dialogues in pool!");
                 return;
             } else {
-                let index = rng().random_range(0..dialogue_pool.len());
+                let index = current_level
+                    .rng(END_DIALOGUE_RNG_STREAM)
+                    .random_range(0..dialogue_pool.len());
                 dialogue_pool[index].clone()
             }
         };
@@ -354,7 +454,12 @@ fn enter_end_dialogue(
         .id();
 
     // Spawn UI
-    spawn_dialogue_ui(&mut commands, LevelState::EndDialogue, &asset_server);
+    spawn_dialogue_ui(
+        &mut commands,
+        LevelState::EndDialogue,
+        &asset_server,
+        bindings.advance,
+    );
 
     // Start dialogue
     start_dialogue_events.write(StartDialogueEvent {
@@ -399,7 +504,12 @@ fn handle_end_dialogue_end(
 }
 
 /// Spawn dialogue UI
-fn spawn_dialogue_ui(commands: &mut Commands, state: LevelState, asset_server: &Res<AssetServer>) {
+fn spawn_dialogue_ui(
+    commands: &mut Commands,
+    state: LevelState,
+    asset_server: &Res<AssetServer>,
+    advance_key: KeyCode,
+) {
     // First spawn the default UI
     let ui_entity = funkus_dialogue_ui::spawn_dialogue_ui(commands);
 
@@ -431,7 +541,10 @@ fn spawn_dialogue_ui(commands: &mut Commands, state: LevelState, asset_server: &
                 right: Val::Px(10.0),
                 ..default()
             },
-            Text::new("Click or press Space to continue..."),
+            Text::new(format!(
+                "Click or press {} to continue...",
+                key_label(advance_key)
+            )),
             TextFont {
                 font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
                 font_size: 14.0,
@@ -549,3 +662,22 @@ fn style_dialogue_ui(
         text_font.font_smoothing = FontSmoothing::AntiAliased;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_entry_skips_the_intro_unless_replayed() {
+        let mut seen_intros = SeenIntros::default();
+        assert!(seen_intros.should_play(0));
+        assert!(!seen_intros.should_play(0));
+        // Other levels keep their own intro
+        assert!(seen_intros.should_play(1));
+
+        seen_intros.request_replay();
+        assert!(seen_intros.should_play(0));
+        // The replay only covers one entry
+        assert!(!seen_intros.should_play(0));
+    }
+}
@@ -0,0 +1,137 @@
+//! Celebration played the moment a level's spore goal is reached
+//!
+//! Plays a short jingle, flashes the screen and shows a "GOAL REACHED!" popup,
+//! then moves on to the end dialogue once it has finished.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    audio::sound_effect,
+    game::{game_flow::LevelState, level::assets::LevelAssets, ui::GameplayUI},
+    settings::ReducedMotion,
+    theme::palette as ui_palette,
+};
+
+/// How long the celebration holds before the end dialogue
+const CELEBRATION_DURATION: f32 = 1.8;
+/// Peak opacity of the screen flash
+const FLASH_ALPHA: f32 = 0.6;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<GoalReachedEvent>();
+    app.add_observer(start_celebration);
+    app.add_systems(
+        Update,
+        run_celebration.run_if(resource_exists::<GoalCelebration>),
+    );
+    app.add_systems(OnExit(LevelState::Playing), end_celebration);
+}
+
+/// Event fired when the level's total spores first reach the target
+#[derive(Event, Debug)]
+pub struct GoalReachedEvent;
+
+/// Celebration in progress
+#[derive(Resource)]
+pub struct GoalCelebration {
+    timer: Timer,
+}
+
+/// Full-screen flash, also catches clicks while the celebration plays
+#[derive(Component)]
+struct GoalFlash;
+
+/// "GOAL REACHED!" popup text
+#[derive(Component)]
+struct GoalPopup;
+
+fn start_celebration(
+    _: Trigger<GoalReachedEvent>,
+    mut commands: Commands,
+    reduced_motion: Res<ReducedMotion>,
+    asset_server: Res<AssetServer>,
+    level_assets: Option<Res<LevelAssets>>,
+) {
+    info!("Goal reached, celebrating");
+
+    commands.insert_resource(GoalCelebration {
+        timer: Timer::from_seconds(CELEBRATION_DURATION, TimerMode::Once),
+    });
+
+    if let Some(level_assets) = level_assets {
+        commands.spawn(sound_effect(level_assets.sfx_goal_jingle.clone()));
+    }
+
+    let flash_alpha = if reduced_motion.0 { 0.0 } else { FLASH_ALPHA };
+    commands.spawn((
+        Name::new("Goal Flash"),
+        GoalFlash,
+        GameplayUI,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::WHITE.with_alpha(flash_alpha)),
+        GlobalZIndex(5),
+        StateScoped(LevelState::Playing),
+        children![(
+            Name::new("Goal Popup"),
+            GoalPopup,
+            Text::new("GOAL REACHED!"),
+            TextFont {
+                font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                font_size: 72.0,
+                font_smoothing: FontSmoothing::AntiAliased,
+                ..default()
+            },
+            TextColor(ui_palette::HEADER_TEXT),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn run_celebration(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut celebration: ResMut<GoalCelebration>,
+    reduced_motion: Res<ReducedMotion>,
+    mut flashes: Query<&mut BackgroundColor, With<GoalFlash>>,
+    mut popups: Query<&mut Transform, With<GoalPopup>>,
+    mut level_state: ResMut<NextState<LevelState>>,
+) {
+    celebration.timer.tick(time.delta());
+    let elapsed = celebration.timer.elapsed_secs();
+
+    // Flash fades out over the first half
+    let flash = (1.0 - elapsed / (CELEBRATION_DURATION * 0.5)).max(0.0);
+    for mut color in &mut flashes {
+        let alpha = if reduced_motion.0 {
+            0.0
+        } else {
+            FLASH_ALPHA * flash
+        };
+        color.0.set_alpha(alpha);
+    }
+
+    // Popup punches in, then settles
+    if !reduced_motion.0 {
+        let punch = 1.0 + 0.3 * (1.0 - (elapsed / 0.3).min(1.0));
+        for mut transform in &mut popups {
+            transform.scale = Vec3::splat(punch);
+        }
+    }
+
+    if celebration.timer.finished() {
+        level_state.set(LevelState::EndDialogue);
+        commands.remove_resource::<GoalCelebration>();
+    }
+}
+
+/// Stop a celebration if the level is left before it finishes
+fn end_celebration(mut commands: Commands) {
+    commands.remove_resource::<GoalCelebration>();
+}
@@ -1,23 +1,51 @@
 //! Turn-based gameplay state management
 
 use bevy::{prelude::*, text::FontSmoothing};
+use rand::{SeedableRng, rngs::StdRng};
 
 use crate::{
+    PausableSystems,
     game::{
         carddeck::events::DrawEvent,
+        dialogue::SeenIntros,
         level::{
             CurrentGameplayMusic,
-            definitions::{LevelDefinitions, load_level_config},
+            definitions::{BonusGoal, BonusReward, LevelDefinitions, load_level_config},
+        },
+        mushrooms::{
+            ChainManager, Mushroom, MushroomDefinitions, MushroomType, SelectedMushroomType,
+            chain_activation::reset_mushroom_states,
+            upgrades::{MushroomUpgrades, UPGRADE_CHOICES, UpgradeMushroom},
         },
-        mushrooms::{ChainManager, SelectedMushroomType, chain_activation::reset_mushroom_states},
         play_field::placement_preview::PreviewConnections,
-        resources::GameState,
+        resources::{GameState, clamp_spores, format_spores},
+        rng::GameRng,
+        save::{CompletedLevelDeck, ResumeLevel, SaveData, SaveGame, SaveProgress},
     },
-    screens::Screen,
+    screens::{Screen, transition::FadeToScreen},
+    settings::AutoSave,
     theme::{assets::ThemeAssets, widget::slice_1_slicer},
 };
 
+mod goal_celebration;
+mod out_of_cards;
+pub(crate) mod practice;
+mod share;
+mod turn_summary;
+
+pub(crate) use goal_celebration::GoalCelebration;
+use goal_celebration::GoalReachedEvent;
+use practice::PracticeMode;
+
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        goal_celebration::plugin,
+        out_of_cards::plugin,
+        practice::plugin,
+        share::plugin,
+        turn_summary::plugin,
+    ));
+
     // Initialize states
     app.init_state::<LevelState>();
     app.init_state::<LevelLifecycle>();
@@ -33,6 +61,12 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         check_phase_completion.run_if(in_state(Screen::Gameplay)),
     );
+    app.add_systems(
+        Update,
+        watch_chain_progress
+            .run_if(in_state(TurnPhase::Chain))
+            .in_set(PausableSystems),
+    );
 
     // Handle level complete actions
     app.add_observer(handle_level_complete_action);
@@ -74,6 +108,8 @@ pub(super) fn plugin(app: &mut App) {
     // Initialize resources
     app.init_resource::<TurnData>();
     app.init_resource::<CurrentLevel>();
+    app.init_resource::<SporeHistory>();
+    app.add_systems(OnEnter(LevelState::StartDialogue), clear_spore_history);
 }
 
 /// Component for the game complete screen
@@ -103,8 +139,7 @@ fn update_game_complete_timer(
 fn handle_game_complete_click(
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     query: Query<&GameCompleteScreen>,
-    mut next_screen: ResMut<NextState<Screen>>,
-    _commands: Commands,
+    mut commands: Commands,
     mut game_state: ResMut<GameState>,
     mut current_level: ResMut<CurrentLevel>,
     mut turn_data: ResMut<TurnData>,
@@ -132,7 +167,7 @@ fn handle_game_complete_click(
         chain_manager.chains.clear();
         chain_manager.activation_queue.clear();
         chain_manager.current_chain = None;
-        chain_manager.chain_started_this_turn = false;
+        chain_manager.chains_remaining = 0;
         preview_connections.connected_positions.clear();
         preview_connections.empty_connection_points.clear();
         preview_connections.existing_connection_targets.clear();
@@ -140,7 +175,7 @@ fn handle_game_complete_click(
         selected_mushroom.mushroom_type = None;
 
         // Return to title screen
-        next_screen.set(Screen::Title);
+        commands.trigger(FadeToScreen(Screen::Title));
     }
 }
 
@@ -243,14 +278,60 @@ pub struct TurnData {
     pub spores_this_chain: f64,
 }
 
+/// Spores earned each turn of the current level, oldest first
+#[derive(Resource, Default, Debug)]
+pub struct SporeHistory {
+    pub per_turn: Vec<f64>,
+}
+
 /// Current level configuration
 #[derive(Resource, Default, Debug)]
 pub struct CurrentLevel {
     pub level_index: usize,
     pub target_score: f64,
     pub max_turns: u32,
+    /// Chains the player may start each turn
+    pub max_chains_per_turn: u32,
     pub total_spores_earned: f64,
+    /// Fraction of the target that earns an extra turn when the last one falls short
+    pub overtime_threshold: f32,
+    /// Whether the level's overtime turn has been granted, it's only given once
+    pub overtime: bool,
     pub level_completed_successfully: Option<bool>, // None = still playing, Some(true) = won, Some(false) = lost
+    /// Optional goals for this level
+    pub bonus_goals: Vec<BonusGoal>,
+    /// Which bonus goals have been completed, matching `bonus_goals`
+    pub bonus_completed: Vec<bool>,
+    /// Seed for this attempt's card draws and dialogue picks, kept when retrying with the same draws
+    pub seed: u64,
+}
+
+impl CurrentLevel {
+    /// Random numbers for one part of the level, the same every time for a seed
+    ///
+    /// Each user passes its own `stream`, so adding draws in one place doesn't change another.
+    pub fn rng(&self, stream: u64) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    /// Fraction of the spore target earned so far
+    pub fn progress(&self) -> f64 {
+        self.total_spores_earned / self.target_score
+    }
+
+    /// Add the overtime turn if the level has one left and the player is close enough
+    /// Returns whether it was granted
+    pub fn grant_overtime(&mut self) -> bool {
+        if self.overtime
+            || self.overtime_threshold >= 1.0
+            || self.progress() < self.overtime_threshold as f64
+        {
+            return false;
+        }
+        self.overtime = true;
+        self.max_turns += 1;
+        true
+    }
 }
 
 /// Actions available when a level is complete
@@ -258,6 +339,10 @@ pub struct CurrentLevel {
 pub enum LevelCompleteAction {
     NextLevel,
     RetryLevel,
+    /// Retry with the same seed, so the deck and dialogue come out the same
+    RetrySameDraws,
+    /// Retry and play the level's intro again, even if it's been seen
+    ReplayIntro,
     MainMenu,
 }
 
@@ -273,6 +358,11 @@ fn deactivate_level_lifecycle(mut next_state: ResMut<NextState<LevelLifecycle>>)
     next_state.set(LevelLifecycle::Inactive);
 }
 
+/// Start each level with an empty spore history
+fn clear_spore_history(mut history: ResMut<SporeHistory>) {
+    history.per_turn.clear();
+}
+
 /// Load a specific level by index
 fn load_level(
     level_index: usize,
@@ -280,6 +370,7 @@ fn load_level(
     current_level: &mut CurrentLevel,
     turn_data: &mut TurnData,
     game_state: &mut GameState,
+    game_rng: &mut GameRng,
 ) -> Result<String, String> {
     if let Some(level_def) = load_level_config(level_index, level_definitions, game_state) {
         let level_name = level_def.name.clone();
@@ -288,8 +379,14 @@ fn load_level(
             level_index,
             target_score: level_def.target_score,
             max_turns: level_def.max_turns,
+            max_chains_per_turn: level_def.max_chains_per_turn,
             total_spores_earned: 0.0,
+            overtime_threshold: level_def.overtime_threshold,
+            overtime: false,
             level_completed_successfully: None,
+            bonus_completed: vec![false; level_def.bonus_goals.len()],
+            bonus_goals: level_def.bonus_goals.clone(),
+            seed: game_rng.next_seed(),
         };
 
         *turn_data = TurnData {
@@ -322,15 +419,19 @@ fn enter_first_level(
     mut turn_data: ResMut<TurnData>,
     level_definitions: Res<LevelDefinitions>,
     mut game_state: ResMut<GameState>,
+    mut game_rng: ResMut<GameRng>,
+    mut resume_level: ResMut<ResumeLevel>,
 ) {
-    info!("Starting first level");
+    let level_index = resume_level.take_clamped(&level_definitions);
+    info!("Starting at level {}", level_index + 1);
 
     match load_level(
-        0,
+        level_index,
         &level_definitions,
         &mut current_level,
         &mut turn_data,
         &mut game_state,
+        &mut game_rng,
     ) {
         Ok(_) => {
             level_state.set(LevelState::StartDialogue);
@@ -341,11 +442,17 @@ fn enter_first_level(
     }
 }
 
+/// Number of cards drawn at the start of a turn
+pub fn cards_drawn_on_turn(turn: u32) -> u32 {
+    if turn == 1 { 6 } else { 4 }
+}
+
 /// Draw phase - player draws mushrooms from bag
 fn enter_draw_phase(
     mut commands: Commands,
     mut turn_data: ResMut<TurnData>,
     current_level: Res<CurrentLevel>,
+    mut game_state: ResMut<GameState>,
     mut phase_state: ResMut<NextState<TurnPhase>>,
 ) {
     info!("=== DRAW PHASE ===");
@@ -354,7 +461,8 @@ fn enter_draw_phase(
         turn_data.current_turn, current_level.max_turns
     );
 
-    let draw_amount = if turn_data.current_turn == 1 { 6 } else { 4 };
+    let draw_amount = cards_drawn_on_turn(turn_data.current_turn) + game_state.bonus_cards;
+    game_state.bonus_cards = 0;
     turn_data.mushrooms_drawn_this_turn = draw_amount;
 
     info!("Drawing {} mushrooms from bag", draw_amount);
@@ -382,29 +490,97 @@ fn spawn_level_success_ui(
     commands: Commands,
     asset_server: Res<AssetServer>,
     theme_assets: Res<ThemeAssets>,
+    history: Res<SporeHistory>,
+    current_level: Res<CurrentLevel>,
+    mushrooms: Query<&Mushroom>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
 ) {
     let font_asset = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
-    spawn_level_complete_ui(commands, true, font_asset, theme_assets);
+    let upgrade_choices = upgrade_choices(&mushrooms, &definitions, &upgrades);
+    spawn_level_complete_ui(
+        commands,
+        true,
+        font_asset,
+        theme_assets,
+        &history,
+        &current_level,
+        &upgrade_choices,
+    );
+}
+
+/// The mushroom types placed most on the field, offered as upgrades
+/// Each entry is the type, its name and its current upgrade level
+fn upgrade_choices(
+    mushrooms: &Query<&Mushroom>,
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+) -> Vec<(MushroomType, String, u32)> {
+    let mut counts: Vec<(MushroomType, usize)> = Vec::new();
+    for mushroom in mushrooms {
+        match counts.iter_mut().find(|(ty, _)| *ty == mushroom.0) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((mushroom.0, 1)),
+        }
+    }
+    // Stable sort, so ties keep the order they were first placed in
+    counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    counts
+        .into_iter()
+        .filter(|(mushroom_type, _)| *mushroom_type != MushroomType::Test)
+        .filter_map(|(mushroom_type, _)| {
+            let definition = definitions.get(mushroom_type)?;
+            Some((
+                mushroom_type,
+                definition.name.clone(),
+                upgrades.level(mushroom_type),
+            ))
+        })
+        .take(UPGRADE_CHOICES)
+        .collect()
 }
 
+/// Row of upgrade buttons on the level complete screen, removed once one is picked
+#[derive(Component)]
+struct UpgradeOffer;
+
 /// Spawn failure UI
 fn spawn_level_failed_ui(
     commands: Commands,
     asset_server: Res<AssetServer>,
     theme_assets: Res<ThemeAssets>,
+    history: Res<SporeHistory>,
+    current_level: Res<CurrentLevel>,
 ) {
     let font_asset = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
-    spawn_level_complete_ui(commands, false, font_asset, theme_assets);
+    spawn_level_complete_ui(
+        commands,
+        false,
+        font_asset,
+        theme_assets,
+        &history,
+        &current_level,
+        &[],
+    );
 }
 
 /// Score phase - check win/loss conditions
 fn enter_score_phase(
+    mut commands: Commands,
     chain_manager: Res<ChainManager>,
     mut current_level: ResMut<CurrentLevel>,
     mut level_state: ResMut<NextState<LevelState>>,
-    mut _game_state: ResMut<GameState>,
+    mut game_state: ResMut<GameState>,
     turn_data: Res<TurnData>,
+    practice: Res<PracticeMode>,
+    mut history: ResMut<SporeHistory>,
 ) {
+    // Practice runs don't count towards the level
+    if practice.active {
+        return;
+    }
+
     info!("=== SCORE PHASE ===");
 
     // Calculate total spores from all chains this turn
@@ -414,7 +590,9 @@ fn enter_score_phase(
         .map(|chain| chain.total_spores)
         .sum();
 
-    current_level.total_spores_earned += chain_score;
+    let previous_total = current_level.total_spores_earned;
+    current_level.total_spores_earned = clamp_spores(previous_total + chain_score);
+    history.per_turn.push(chain_score);
 
     info!(
         "Generated {} spores from {} chains this turn",
@@ -426,11 +604,60 @@ fn enter_score_phase(
         current_level.total_spores_earned, current_level.target_score
     );
 
+    let won = current_level.total_spores_earned >= current_level.target_score;
+    let goal_crossed = won && previous_total < current_level.target_score;
+
+    // Check bonus goals
+    let connections = game_state.play_field.connections.len();
+    let CurrentLevel {
+        bonus_goals,
+        bonus_completed,
+        ..
+    } = &mut *current_level;
+    for (goal, completed) in bonus_goals.iter().zip(bonus_completed.iter_mut()) {
+        if *completed
+            || !goal
+                .objective
+                .is_complete(connections, turn_data.current_turn, chain_score, won)
+        {
+            continue;
+        }
+
+        *completed = true;
+        info!(
+            "Bonus goal complete: {} ({})",
+            goal.objective.description(),
+            goal.reward.description()
+        );
+        match goal.reward {
+            // Drawn with the next hand, so a goal met on the winning turn isn't wasted
+            BonusReward::ExtraCard => game_state.bonus_cards += 1,
+            BonusReward::UnlockSpores(spores) => {
+                game_state.total_spores_earned =
+                    clamp_spores(game_state.total_spores_earned + spores)
+            }
+        }
+    }
+
     // Check win condition
-    if current_level.total_spores_earned >= current_level.target_score {
+    if won {
         info!("Level complete - SUCCESS!");
         current_level.level_completed_successfully = Some(true);
-        level_state.set(LevelState::EndDialogue);
+        if goal_crossed {
+            // The celebration moves on to the end dialogue once it's done
+            commands.trigger(GoalReachedEvent);
+        } else {
+            level_state.set(LevelState::EndDialogue);
+        }
+        return;
+    }
+
+    // Close enough on the last turn, play one more
+    if turn_data.current_turn >= current_level.max_turns && current_level.grant_overtime() {
+        info!(
+            "Overtime! {:.0}% of the target reached, one extra turn",
+            current_level.progress() * 100.0
+        );
         return;
     }
 
@@ -453,18 +680,29 @@ fn handle_level_complete_action(
     mut turn_data: ResMut<TurnData>,
     level_definitions: Res<LevelDefinitions>,
     mut game_state: ResMut<GameState>,
-    mut next_screen: ResMut<NextState<Screen>>,
+    mut game_rng: ResMut<GameRng>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    completed_deck: Res<CompletedLevelDeck>,
+    mut seen_intros: ResMut<SeenIntros>,
+    auto_save: Res<AutoSave>,
+    mut commands: Commands,
 ) {
     match trigger.event() {
-        LevelCompleteAction::RetryLevel => {
+        action @ (LevelCompleteAction::RetryLevel | LevelCompleteAction::ReplayIntro) => {
             info!("Retrying level {}", current_level.level_index + 1);
 
+            if matches!(action, LevelCompleteAction::ReplayIntro) {
+                seen_intros.request_replay();
+            }
+
             if load_level(
                 current_level.level_index,
                 &level_definitions,
                 &mut current_level,
                 &mut turn_data,
                 &mut game_state,
+                &mut game_rng,
             )
             .is_ok()
             {
@@ -474,30 +712,64 @@ fn handle_level_complete_action(
             }
         }
 
+        LevelCompleteAction::RetrySameDraws => {
+            info!(
+                "Retrying level {} with the same draws",
+                current_level.level_index + 1
+            );
+
+            let seed = current_level.seed;
+            if load_level(
+                current_level.level_index,
+                &level_definitions,
+                &mut current_level,
+                &mut turn_data,
+                &mut game_state,
+                &mut game_rng,
+            )
+            .is_ok()
+            {
+                current_level.seed = seed;
+                level_state.set(LevelState::StartDialogue);
+            }
+        }
+
         LevelCompleteAction::NextLevel => {
             let next_index = current_level.level_index + 1;
             info!("Progressing to level {}", next_index + 1);
 
+            if auto_save.0 && next_index < level_definitions.levels.len() {
+                commands.trigger(SaveGame(SaveData::capture(
+                    next_index,
+                    &game_state,
+                    &definitions,
+                    &upgrades,
+                    &completed_deck.0,
+                )));
+            }
+
             match load_level(
                 next_index,
                 &level_definitions,
                 &mut current_level,
                 &mut turn_data,
                 &mut game_state,
+                &mut game_rng,
             ) {
                 Ok(_) => {
                     level_state.set(LevelState::StartDialogue);
                 }
                 Err(_) => {
                     info!("No more levels! Game complete!");
-                    next_screen.set(Screen::Title);
+                    commands.trigger(FadeToScreen(Screen::Title));
                 }
             }
         }
 
         LevelCompleteAction::MainMenu => {
             info!("Returning to main menu");
-            next_screen.set(Screen::Title);
+            commands.trigger(SaveProgress::Auto);
+            commands.trigger(FadeToScreen(Screen::Title));
         }
     }
 }
@@ -532,7 +804,7 @@ fn cleanup_gameplay_state(
     chain_manager.chains.clear();
     chain_manager.activation_queue.clear();
     chain_manager.current_chain = None;
-    chain_manager.chain_started_this_turn = false;
+    chain_manager.chains_remaining = 0;
 
     // Clear preview connections
     preview_connections.connected_positions.clear();
@@ -563,7 +835,12 @@ fn check_phase_completion(
             // Handled by player action
         }
         TurnPhase::Chain => {
-            if !chain_manager.has_active_chains() && chain_manager.chain_started_this_turn {
+            // With chains left to start, the player ends the turn themselves
+            let all_started = chain_manager.chains_remaining == 0;
+            if !chain_manager.has_active_chains()
+                && chain_manager.any_chain_started()
+                && all_started
+            {
                 info!("All chains complete, advancing to score phase");
                 next_phase.set(TurnPhase::Score);
             }
@@ -574,14 +851,55 @@ fn check_phase_completion(
     }
 }
 
+/// How long a chain can go without progress before the watchdog complains
+const CHAIN_STALL_WARNING_SECS: f32 = 5.0;
+
+/// Log a warning if the activation queue stops making progress mid-chain
+fn watch_chain_progress(
+    time: Res<Time>,
+    chain_manager: Res<ChainManager>,
+    turn_data: Res<TurnData>,
+    mut last_progress: Local<(usize, u32)>,
+    mut stalled_for: Local<f32>,
+    mut warned: Local<bool>,
+) {
+    let progress = (
+        chain_manager.activation_queue.len(),
+        turn_data.activations_this_chain,
+    );
+
+    if !chain_manager.has_active_chains() || progress != *last_progress {
+        *last_progress = progress;
+        *stalled_for = 0.0;
+        *warned = false;
+        return;
+    }
+
+    *stalled_for += time.delta_secs();
+    if *stalled_for >= CHAIN_STALL_WARNING_SECS && !*warned {
+        warn!(
+            "Chain hasn't progressed for {:.1}s ({} pending activations), use End Turn to recover",
+            *stalled_for,
+            chain_manager.activation_queue.len()
+        );
+        *warned = true;
+    }
+}
+
 fn spawn_level_complete_ui(
     mut commands: Commands,
     success: bool,
     font_asset: Handle<Font>,
     theme_assets: Res<ThemeAssets>,
+    history: &SporeHistory,
+    current_level: &CurrentLevel,
+    upgrade_choices: &[(MushroomType, String, u32)],
 ) {
     use bevy::ui::Val::*;
 
+    // Tallest bar is the best turn, so bars are scaled against it
+    let best_turn = history.per_turn.iter().copied().fold(0.0, f64::max);
+
     commands
         .spawn((
             Name::new("Level Complete UI"),
@@ -623,6 +941,184 @@ fn spawn_level_complete_ui(
                 }),
             ));
 
+            // Bonus goals and whether they were completed
+            for (goal, completed) in current_level
+                .bonus_goals
+                .iter()
+                .zip(&current_level.bonus_completed)
+            {
+                parent.spawn((
+                    Text::new(format!(
+                        "{} {} ({})",
+                        if *completed { "[x]" } else { "[ ]" },
+                        goal.objective.description(),
+                        goal.reward.description()
+                    )),
+                    TextFont {
+                        font: font_asset.clone(),
+                        font_size: 24.0,
+                        font_smoothing: FontSmoothing::AntiAliased,
+                        ..default()
+                    },
+                    TextColor(if *completed {
+                        Color::srgb(0.2, 0.8, 0.2)
+                    } else {
+                        Color::srgba(1.0, 1.0, 1.0, 0.6)
+                    }),
+                ));
+            }
+
+            // Level up one of the mushrooms used this level
+            if !upgrade_choices.is_empty() {
+                parent
+                    .spawn((
+                        Name::new("Upgrade Offer"),
+                        UpgradeOffer,
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Px(10.0),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|offer| {
+                        offer.spawn((
+                            Text::new("Level up a mushroom (+1 use per turn)"),
+                            TextFont {
+                                font: font_asset.clone(),
+                                font_size: 24.0,
+                                font_smoothing: FontSmoothing::AntiAliased,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+
+                        offer
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                column_gap: Px(12.0),
+                                ..default()
+                            })
+                            .with_children(|choices| {
+                                for (mushroom_type, name, level) in upgrade_choices {
+                                    let mushroom_type = *mushroom_type;
+                                    choices
+                                        .spawn((
+                                            Button,
+                                            Node {
+                                                padding: UiRect::all(Px(12.0)),
+                                                ..default()
+                                            },
+                                            ImageNode {
+                                                image: theme_assets.slice_1.clone(),
+                                                image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                                                color: Color::WHITE,
+                                                ..default()
+                                            },
+                                        ))
+                                        .with_child((
+                                            Text::new(format!("{name} (Lv {level})")),
+                                            TextFont {
+                                                font: font_asset.clone(),
+                                                font_size: 24.0,
+                                                font_smoothing: FontSmoothing::AntiAliased,
+                                                ..default()
+                                            },
+                                            Pickable::IGNORE,
+                                        ))
+                                        .observe(
+                                            move |_: Trigger<Pointer<Click>>,
+                                                  mut commands: Commands,
+                                                  offers: Query<Entity, With<UpgradeOffer>>| {
+                                                commands.trigger(UpgradeMushroom(mushroom_type));
+                                                for entity in &offers {
+                                                    commands.entity(entity).despawn();
+                                                }
+                                            },
+                                        );
+                                }
+                            });
+                    });
+            }
+
+            // Spores earned per turn, as a bar chart
+            if !history.per_turn.is_empty() {
+                parent.spawn((
+                    Text::new("Spores per turn"),
+                    TextFont {
+                        font: font_asset.clone(),
+                        font_size: 24.0,
+                        font_smoothing: FontSmoothing::AntiAliased,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+
+                parent
+                    .spawn((
+                        Name::new("Spore History Graph"),
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::FlexEnd,
+                            column_gap: Px(8.0),
+                            height: Px(160.0),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|graph| {
+                        for (turn, spores) in history.per_turn.iter().enumerate() {
+                            let fraction = if best_turn > 0.0 {
+                                (spores / best_turn) as f32
+                            } else {
+                                0.0
+                            };
+
+                            graph
+                                .spawn((
+                                    Name::new(format!("Turn {} Bar", turn + 1)),
+                                    Node {
+                                        flex_direction: FlexDirection::Column,
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::FlexEnd,
+                                        height: Percent(100.0),
+                                        ..default()
+                                    },
+                                ))
+                                .with_children(|column| {
+                                    column.spawn((
+                                        Text::new(format_spores(*spores)),
+                                        TextFont {
+                                            font: font_asset.clone(),
+                                            font_size: 14.0,
+                                            font_smoothing: FontSmoothing::AntiAliased,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                    column.spawn((
+                                        Node {
+                                            width: Px(24.0),
+                                            // Leave room for the value label above the bar
+                                            height: Px(fraction * 120.0),
+                                            ..default()
+                                        },
+                                        BackgroundColor(Color::srgb(0.6, 0.4, 0.8)),
+                                    ));
+                                    column.spawn((
+                                        Text::new((turn + 1).to_string()),
+                                        TextFont {
+                                            font: font_asset.clone(),
+                                            font_size: 14.0,
+                                            font_smoothing: FontSmoothing::AntiAliased,
+                                            ..default()
+                                        },
+                                        TextColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+                                    ));
+                                });
+                        }
+                    });
+            }
+
             // Buttons container
             parent
                 .spawn(Node {
@@ -660,6 +1156,66 @@ fn spawn_level_complete_ui(
                             commands.trigger(LevelCompleteAction::RetryLevel);
                         });
 
+                    // Retry with the same draws (only if failed)
+                    if !success {
+                        buttons
+                            .spawn((
+                                Button,
+                                Node {
+                                    padding: UiRect::all(Px(20.0)),
+                                    ..default()
+                                },
+                                ImageNode {
+                                    image: theme_assets.slice_1.clone(),
+                                    image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                            ))
+                            .with_child((
+                                Text::new("Retry (Same Draws)"),
+                                TextFont {
+                                    font: font_asset.clone(),
+                                    font_size: 48.0,
+                                    font_smoothing: FontSmoothing::AntiAliased,
+                                    ..default()
+                                },
+                                Pickable::IGNORE,
+                            ))
+                            .observe(|_: Trigger<Pointer<Click>>, mut commands: Commands| {
+                                commands.trigger(LevelCompleteAction::RetrySameDraws);
+                            });
+
+                        // Retry with the intro again
+                        buttons
+                            .spawn((
+                                Button,
+                                Node {
+                                    padding: UiRect::all(Px(20.0)),
+                                    ..default()
+                                },
+                                ImageNode {
+                                    image: theme_assets.slice_1.clone(),
+                                    image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                            ))
+                            .with_child((
+                                Text::new("Replay Intro"),
+                                TextFont {
+                                    font: font_asset.clone(),
+                                    font_size: 48.0,
+                                    font_smoothing: FontSmoothing::AntiAliased,
+                                    ..default()
+                                },
+                                Pickable::IGNORE,
+                            ))
+                            .observe(|_: Trigger<Pointer<Click>>, mut commands: Commands| {
+                                commands.trigger(LevelCompleteAction::ReplayIntro);
+                            });
+                    }
+
                     // Next level button (only if success)
                     if success {
                         buttons
@@ -691,6 +1247,13 @@ fn spawn_level_complete_ui(
                             });
                     }
 
+                    // Share the result (only if success)
+                    if success {
+                        buttons
+                            .spawn(share::share_button(font_asset.clone(), &theme_assets))
+                            .observe(share::share_result);
+                    }
+
                     // Main menu button
                     buttons
                         .spawn((
@@ -722,3 +1285,111 @@ fn spawn_level_complete_ui(
                 });
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_turn_at(progress: f64, overtime_threshold: f32) -> CurrentLevel {
+        CurrentLevel {
+            target_score: 100.0,
+            max_turns: 5,
+            total_spores_earned: 100.0 * progress,
+            overtime_threshold,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn close_finish_grants_one_overtime_turn() {
+        let mut level = last_turn_at(0.85, 0.8);
+
+        assert!(level.grant_overtime());
+        assert!(level.overtime);
+        assert_eq!(level.max_turns, 6);
+
+        // Still short after the extra turn, there isn't another one
+        assert!(!level.grant_overtime());
+        assert_eq!(level.max_turns, 6);
+    }
+
+    #[test]
+    fn overtime_needs_the_threshold_reached() {
+        let mut short = last_turn_at(0.75, 0.8);
+        assert!(!short.grant_overtime());
+        assert_eq!(short.max_turns, 5);
+
+        // 1.0 turns overtime off
+        let mut disabled = last_turn_at(0.85, 1.0);
+        assert!(!disabled.grant_overtime());
+        assert!(!disabled.overtime);
+    }
+
+    #[test]
+    fn extra_card_earned_on_the_winning_turn_waits_for_the_next_draw() {
+        use crate::game::{level::definitions::BonusObjective, mushrooms::chain_activation::Chain};
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        let mut chain_manager = ChainManager::default();
+        chain_manager.chains.push(Chain {
+            id: 0,
+            starter: Entity::PLACEHOLDER,
+            activations: vec![],
+            total_spores: 100.0,
+            active: false,
+            endings: vec![],
+        });
+        world.insert_resource(chain_manager);
+        world.insert_resource(CurrentLevel {
+            target_score: 100.0,
+            max_turns: 5,
+            bonus_goals: vec![BonusGoal {
+                objective: BonusObjective::WinWithinTurns(5),
+                reward: BonusReward::ExtraCard,
+            }],
+            bonus_completed: vec![false],
+            ..default()
+        });
+        world.insert_resource(TurnData {
+            current_turn: 1,
+            ..default()
+        });
+        world.init_resource::<NextState<LevelState>>();
+        world.init_resource::<GameState>();
+        world.init_resource::<PracticeMode>();
+        world.init_resource::<SporeHistory>();
+
+        world.run_system_once(enter_score_phase).unwrap();
+
+        assert_eq!(world.resource::<CurrentLevel>().bonus_completed, [true]);
+        assert_eq!(world.resource::<GameState>().bonus_cards, 1);
+    }
+
+    #[test]
+    fn close_finish_on_the_last_turn_plays_on_instead_of_ending() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<ChainManager>();
+        world.insert_resource(last_turn_at(0.85, 0.8));
+        world.insert_resource(TurnData {
+            current_turn: 5,
+            ..default()
+        });
+        world.init_resource::<NextState<LevelState>>();
+        world.init_resource::<GameState>();
+        world.init_resource::<PracticeMode>();
+        world.init_resource::<SporeHistory>();
+
+        world.run_system_once(enter_score_phase).unwrap();
+
+        let level = world.resource::<CurrentLevel>();
+        assert_eq!(level.max_turns, 6);
+        assert_eq!(level.level_completed_successfully, None);
+        assert!(matches!(
+            world.resource::<NextState<LevelState>>(),
+            NextState::Unchanged
+        ));
+    }
+}
@@ -0,0 +1,288 @@
+//! Warning shown when the player has run out of cards and can no longer win
+//!
+//! While planting with an empty hand, deck and discard pile, the board is scored with the dry-run
+//! simulation for every turn left. If even that falls short of the target, a panel
+//! explains why and offers to end the turn or give up the level.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    game::{
+        carddeck::{
+            deck::{Deck, DiscardPile},
+            hand::Hand,
+        },
+        game_flow::{CurrentLevel, LevelState, TurnData, TurnPhase, practice::PracticeMode},
+        mushrooms::{
+            Mushroom, MushroomDefinitions, MushroomDirection,
+            chain_activation::ChainConfig,
+            simulation::{SimulatedBoard, best_chain},
+            upgrades::MushroomUpgrades,
+        },
+        play_field::GridPosition,
+        resources::{GameState, format_spores},
+        ui::GameplayUI,
+    },
+    theme::{assets::ThemeAssets, palette as ui_palette, widget::slice_1_slicer},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        check_out_of_cards.run_if(in_state(TurnPhase::Planting).and(in_state(LevelState::Playing))),
+    );
+}
+
+/// Marker for the warning panel
+#[derive(Component)]
+struct OutOfCardsPanel;
+
+/// Spores the board could still earn if every remaining turn played its best chain
+fn remaining_potential(
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+    config: &ChainConfig,
+    game_state: &GameState,
+    board: &SimulatedBoard,
+    turns_left: u32,
+) -> f64 {
+    let best = best_chain(definitions, upgrades, config, &game_state.play_field, board)
+        .map_or(0.0, |(_, spores)| spores);
+    best * turns_left as f64
+}
+
+/// Whether any card is still left to plant with
+/// The discard pile counts, it shuffles back in once the deck runs dry
+fn has_cards_left(hand: &Hand, deck: &Deck, discard: &DiscardPile) -> bool {
+    hand.get_card_count() > 0 || deck.get_card_count() > 0 || !discard.is_empty()
+}
+
+fn check_out_of_cards(
+    mut commands: Commands,
+    hand: Res<Hand>,
+    deck: Res<Deck>,
+    discard: Res<DiscardPile>,
+    game_state: Res<GameState>,
+    current_level: Res<CurrentLevel>,
+    turn_data: Res<TurnData>,
+    practice: Res<PracticeMode>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    chain_config: Res<ChainConfig>,
+    mushrooms: Query<(&GridPosition, &Mushroom, Option<&MushroomDirection>)>,
+    panels: Query<Entity, With<OutOfCardsPanel>>,
+    theme_assets: Res<ThemeAssets>,
+    asset_server: Res<AssetServer>,
+    mut cached: Local<Option<(u64, u32, f64)>>,
+) {
+    // Scores for the same board change with the upgrades and the level's chain rules
+    if upgrades.is_changed() || chain_config.is_changed() {
+        *cached = None;
+    }
+
+    let changed =
+        hand.is_changed() || deck.is_changed() || discard.is_changed() || game_state.is_changed();
+    if !changed || practice.active {
+        return;
+    }
+    if has_cards_left(&hand, &deck, &discard) {
+        // Cards came back, e.g. an undone placement, so the warning no longer applies
+        for panel in &panels {
+            commands.entity(panel).despawn();
+        }
+        return;
+    }
+    if !panels.is_empty() {
+        return;
+    }
+
+    let board: SimulatedBoard = mushrooms
+        .iter()
+        .map(|(pos, mushroom, direction)| {
+            (*pos, (mushroom.0, direction.copied().unwrap_or_default()))
+        })
+        .collect();
+    // This turn's chain hasn't been played yet, so it counts too
+    let turns_left = (current_level.max_turns + 1).saturating_sub(turn_data.current_turn);
+    let needed = current_level.target_score - current_level.total_spores_earned;
+    // Most changes to the game state leave the board as it was, so only a new board is rescored
+    let fingerprint = game_state.play_field.fingerprint(
+        board
+            .iter()
+            .map(|(position, (mushroom_type, direction))| (*position, *mushroom_type, *direction)),
+    );
+    let potential = match *cached {
+        Some((board_fingerprint, turns, potential))
+            if board_fingerprint == fingerprint && turns == turns_left =>
+        {
+            potential
+        }
+        _ => {
+            let potential = remaining_potential(
+                &definitions,
+                &upgrades,
+                &chain_config,
+                &game_state,
+                &board,
+                turns_left,
+            );
+            *cached = Some((fingerprint, turns_left, potential));
+            potential
+        }
+    };
+    if potential >= needed {
+        return;
+    }
+
+    info!(
+        "Out of cards: board can reach {} of the {} spores still needed",
+        potential, needed
+    );
+
+    let font = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
+    let text_font = |size: f32| TextFont {
+        font: font.clone(),
+        font_size: size,
+        font_smoothing: FontSmoothing::AntiAliased,
+        ..default()
+    };
+    let has_mushrooms = !game_state.play_field.entities.is_empty();
+
+    commands
+        .spawn((
+            Name::new("Out Of Cards Panel"),
+            OutOfCardsPanel,
+            GameplayUI,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(30.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-200.0)),
+                width: Val::Px(400.0),
+                padding: UiRect::all(Val::Px(24.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            ImageNode {
+                image: theme_assets.slice_1.clone(),
+                image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                color: Color::WHITE,
+                ..default()
+            },
+            GlobalZIndex(1),
+            StateScoped(TurnPhase::Planting),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Out of cards"),
+                text_font(26.0),
+                TextColor(ui_palette::HEADER_TEXT),
+            ));
+            parent.spawn((
+                Text::new(format!(
+                    "Your board can make about {} more spores, {} are needed.",
+                    format_spores(potential),
+                    format_spores(needed)
+                )),
+                text_font(18.0),
+                TextColor(ui_palette::LABEL_TEXT),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    // Without mushrooms there's no chain to play out, only giving up is left
+                    if has_mushrooms {
+                        buttons
+                            .spawn(panel_button("End Turn", &text_font, &theme_assets))
+                            .observe(
+                                |_: Trigger<Pointer<Click>>,
+                                 mut next_phase: ResMut<NextState<TurnPhase>>| {
+                                    next_phase.set(TurnPhase::Chain);
+                                },
+                            );
+                    }
+                    buttons
+                        .spawn(panel_button("Give Up", &text_font, &theme_assets))
+                        .observe(give_up_level);
+                });
+        });
+}
+
+fn panel_button(
+    text: &'static str,
+    text_font: &impl Fn(f32) -> TextFont,
+    theme_assets: &ThemeAssets,
+) -> impl Bundle {
+    (
+        Button,
+        Node {
+            padding: UiRect::all(Val::Px(12.0)),
+            ..default()
+        },
+        ImageNode {
+            image: theme_assets.slice_1.clone(),
+            image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+            color: Color::WHITE,
+            ..default()
+        },
+        children![(Text::new(text), text_font(20.0), Pickable::IGNORE)],
+    )
+}
+
+/// Fail the level now, the failure screen offers the retry buttons
+fn give_up_level(
+    _: Trigger<Pointer<Click>>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut level_state: ResMut<NextState<LevelState>>,
+) {
+    info!("Level given up - out of cards");
+    current_level.level_completed_successfully = Some(false);
+    level_state.set(LevelState::EndDialogue);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{carddeck::card::Card, mushrooms::MushroomType};
+
+    fn potential(board: &SimulatedBoard, turns_left: u32) -> f64 {
+        remaining_potential(
+            &MushroomDefinitions::standard(),
+            &MushroomUpgrades::default(),
+            &ChainConfig::default(),
+            &GameState::default(),
+            board,
+            turns_left,
+        )
+    }
+
+    #[test]
+    fn empty_hand_deck_and_discard_leave_no_cards() {
+        let (hand, deck, mut discard) = (Hand::default(), Deck::default(), DiscardPile::default());
+        assert!(!has_cards_left(&hand, &deck, &discard));
+
+        // A played card still comes back with the next reshuffle
+        discard.add(Card::default());
+        assert!(has_cards_left(&hand, &deck, &discard));
+    }
+
+    #[test]
+    fn empty_board_has_no_potential_left() {
+        assert_eq!(potential(&SimulatedBoard::default(), 3), 0.0);
+
+        let board = SimulatedBoard::from_iter([(
+            GridPosition::new(0, 0),
+            (MushroomType::Basic, MushroomDirection::Up),
+        )]);
+        // A basic's 10 spores, for each turn left
+        assert_eq!(potential(&board, 3), 30.0);
+    }
+}
@@ -0,0 +1,100 @@
+//! Practice runs: play a chain on a frozen copy of the board without using up the turn
+
+use bevy::prelude::*;
+
+use crate::game::{
+    game_flow::{LevelState, TurnPhase},
+    mushrooms::{Mushroom, MushroomDirection},
+    play_field::{GridPosition, field_renderer::FieldGround, snapshot::BoardSnapshot},
+    resources::GameState,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PracticeMode>();
+    app.add_event::<StartPracticeEvent>();
+    app.add_observer(start_practice);
+
+    app.add_systems(OnEnter(TurnPhase::Score), finish_practice_run);
+    app.add_systems(OnEnter(TurnPhase::Planting), restore_practice_snapshot);
+    app.add_systems(OnExit(LevelState::Playing), reset_practice_mode);
+}
+
+/// Practice mode state
+#[derive(Resource, Default, Debug)]
+pub struct PracticeMode {
+    /// Is the current chain a practice run?
+    pub active: bool,
+    /// Board as it was when the practice run started
+    pub snapshot: Option<BoardSnapshot>,
+    /// Set once the practice chain has finished and the board should be put back
+    needs_restore: bool,
+}
+
+/// Request a practice chain from the planting phase
+#[derive(Event, Debug)]
+pub struct StartPracticeEvent;
+
+/// Snapshot the board and jump into a practice chain phase
+fn start_practice(
+    _: Trigger<StartPracticeEvent>,
+    mut practice: ResMut<PracticeMode>,
+    current_phase: Option<Res<State<TurnPhase>>>,
+    mut next_phase: ResMut<NextState<TurnPhase>>,
+    game_state: Res<GameState>,
+    mushrooms: Query<(&Mushroom, &GridPosition, &MushroomDirection)>,
+) {
+    if !current_phase.is_some_and(|phase| *phase.get() == TurnPhase::Planting) {
+        info!("Practice runs can only be started while planting");
+        return;
+    }
+
+    if game_state.play_field.entities.is_empty() {
+        info!("Cannot practice - no mushrooms on the board!");
+        return;
+    }
+
+    practice.snapshot = Some(BoardSnapshot::capture(&game_state, mushrooms.iter()));
+    practice.active = true;
+    practice.needs_restore = false;
+
+    info!("=== PRACTICE RUN ===");
+    next_phase.set(TurnPhase::Chain);
+}
+
+/// Practice chains skip scoring and go straight back to planting
+fn finish_practice_run(
+    mut practice: ResMut<PracticeMode>,
+    mut next_phase: ResMut<NextState<TurnPhase>>,
+) {
+    if !practice.active {
+        return;
+    }
+
+    info!("Practice run finished, restoring board");
+    practice.needs_restore = true;
+    next_phase.set(TurnPhase::Planting);
+}
+
+/// Put the board back after a practice run
+fn restore_practice_snapshot(
+    mut commands: Commands,
+    mut practice: ResMut<PracticeMode>,
+    mut game_state: ResMut<GameState>,
+    mushrooms: Query<Entity, With<Mushroom>>,
+    field_grounds: Query<Entity, With<FieldGround>>,
+) {
+    if !practice.needs_restore {
+        return;
+    }
+
+    if let Some(snapshot) = practice.snapshot.take() {
+        snapshot.restore(&mut commands, &mut game_state, mushrooms, field_grounds);
+    }
+
+    practice.active = false;
+    practice.needs_restore = false;
+}
+
+fn reset_practice_mode(mut practice: ResMut<PracticeMode>) {
+    *practice = PracticeMode::default();
+}
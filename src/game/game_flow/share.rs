@@ -0,0 +1,211 @@
+//! Sharing a level result from the success screen
+//!
+//! Native builds save a screenshot to `screenshots/`, with a banner across the top
+//! showing the level and score while it is taken. Web builds can't write files, so
+//! they copy a short result line to the clipboard instead.
+
+#[cfg(not(target_family = "wasm"))]
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    game::{
+        game_flow::{CurrentLevel, LevelState, TurnData},
+        level::definitions::LevelDefinitions,
+        resources::format_spores,
+    },
+    theme::{assets::ThemeAssets, widget::slice_1_slicer},
+};
+
+#[cfg(not(target_family = "wasm"))]
+const SCREENSHOT_DIR: &str = "screenshots";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ShareInProgress>();
+    app.add_systems(OnExit(LevelState::Success), reset_share);
+}
+
+/// Set while a screenshot is being captured, so repeated clicks don't queue more
+#[derive(Resource, Default, Debug)]
+pub(super) struct ShareInProgress(bool);
+
+/// Marker for the text on the share button, which reports how sharing went
+#[derive(Component)]
+pub(super) struct ShareButtonLabel;
+
+/// Banner with the level and score, only shown while the screenshot is taken
+#[cfg(not(target_family = "wasm"))]
+#[derive(Component)]
+struct ShareOverlay;
+
+/// The "Share" button on the success screen
+pub(super) fn share_button(font: Handle<Font>, theme_assets: &ThemeAssets) -> impl Bundle {
+    (
+        Button,
+        Node {
+            padding: UiRect::all(Val::Px(20.0)),
+            ..default()
+        },
+        ImageNode {
+            image: theme_assets.slice_1.clone(),
+            image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+            color: Color::WHITE,
+            ..default()
+        },
+        children![(
+            ShareButtonLabel,
+            Text::new("Share"),
+            TextFont {
+                font,
+                font_size: 48.0,
+                font_smoothing: FontSmoothing::AntiAliased,
+                ..default()
+            },
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+/// One line describing how the level went
+fn result_line(
+    level_definitions: &LevelDefinitions,
+    current_level: &CurrentLevel,
+    turn_data: &TurnData,
+) -> String {
+    let level_name = level_definitions
+        .get_level(current_level.level_index)
+        .map_or_else(
+            || format!("Level {}", current_level.level_index + 1),
+            |level| level.name.clone(),
+        );
+    format!(
+        "Psychocybin - {}: {} / {} spores in {} turns",
+        level_name,
+        format_spores(current_level.total_spores_earned),
+        format_spores(current_level.target_score),
+        turn_data.current_turn
+    )
+}
+
+fn set_label(labels: &mut Query<&mut Text, With<ShareButtonLabel>>, text: &str) {
+    for mut label in labels {
+        label.0 = text.to_string();
+    }
+}
+
+/// Take a screenshot with the result banner and save it
+#[cfg(not(target_family = "wasm"))]
+pub(super) fn share_result(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut in_progress: ResMut<ShareInProgress>,
+    level_definitions: Res<LevelDefinitions>,
+    current_level: Res<CurrentLevel>,
+    turn_data: Res<TurnData>,
+    asset_server: Res<AssetServer>,
+    mut labels: Query<&mut Text, With<ShareButtonLabel>>,
+) {
+    if in_progress.0 {
+        return;
+    }
+
+    if let Err(error) = std::fs::create_dir_all(SCREENSHOT_DIR) {
+        warn!("Couldn't create {SCREENSHOT_DIR}: {error}");
+        set_label(&mut labels, "Couldn't save");
+        return;
+    }
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    let path = format!(
+        "{SCREENSHOT_DIR}/level-{}-{timestamp}.png",
+        current_level.level_index + 1
+    );
+
+    in_progress.0 = true;
+    set_label(&mut labels, "Saving...");
+
+    commands.spawn((
+        Name::new("Share Overlay"),
+        ShareOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(10),
+        StateScoped(LevelState::Success),
+        children![(
+            Text::new(result_line(&level_definitions, &current_level, &turn_data)),
+            TextFont {
+                font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                font_size: 32.0,
+                font_smoothing: FontSmoothing::AntiAliased,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        )],
+    ));
+
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |trigger: Trigger<ScreenshotCaptured>,
+              mut commands: Commands,
+              mut in_progress: ResMut<ShareInProgress>,
+              overlays: Query<Entity, With<ShareOverlay>>,
+              mut labels: Query<&mut Text, With<ShareButtonLabel>>| {
+            for entity in &overlays {
+                commands.entity(entity).despawn();
+            }
+            in_progress.0 = false;
+
+            let saved = match trigger.event().0.clone().try_into_dynamic() {
+                Ok(image) => image
+                    .to_rgb8()
+                    .save(&path)
+                    .map_err(|error| error.to_string()),
+                Err(error) => Err(error.to_string()),
+            };
+            match saved {
+                Ok(()) => {
+                    info!("Saved screenshot to {path}");
+                    set_label(&mut labels, "Saved!");
+                }
+                Err(error) => {
+                    warn!("Couldn't save screenshot to {path}: {error}");
+                    set_label(&mut labels, "Couldn't save");
+                }
+            }
+        },
+    );
+}
+
+/// Copy the result line to the clipboard
+#[cfg(target_family = "wasm")]
+pub(super) fn share_result(
+    _: Trigger<Pointer<Click>>,
+    level_definitions: Res<LevelDefinitions>,
+    current_level: Res<CurrentLevel>,
+    turn_data: Res<TurnData>,
+    mut labels: Query<&mut Text, With<ShareButtonLabel>>,
+) {
+    let text = result_line(&level_definitions, &current_level, &turn_data);
+    match web_sys::window() {
+        Some(window) => {
+            // The copy finishes asynchronously, a refused permission only shows in the console
+            let _ = window.navigator().clipboard().write_text(&text);
+            info!("Copied result to clipboard: {text}");
+            set_label(&mut labels, "Copied!");
+        }
+        None => {
+            warn!("Couldn't reach the clipboard");
+            set_label(&mut labels, "Couldn't copy");
+        }
+    }
+}
+
+fn reset_share(mut in_progress: ResMut<ShareInProgress>) {
+    in_progress.0 = false;
+}
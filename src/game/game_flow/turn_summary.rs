@@ -0,0 +1,139 @@
+//! End-of-turn summary card shown during the score phase
+//!
+//! Reports the turn's spores, chains and goal progress, and moves on to the next draw
+//! when clicked. Can be turned off from the settings menu.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    game::{
+        game_flow::{CurrentLevel, TurnData, TurnPhase, practice::PracticeMode},
+        mushrooms::ChainManager,
+        resources::format_spores,
+        ui::GameplayUI,
+    },
+    settings::TurnSummary,
+    theme::{assets::ThemeAssets, palette as ui_palette, widget::slice_1_slicer},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(TurnPhase::Score),
+        spawn_turn_summary.after(super::enter_score_phase),
+    );
+}
+
+/// Marker for the summary card
+#[derive(Component)]
+struct TurnSummaryCard;
+
+fn spawn_turn_summary(
+    mut commands: Commands,
+    summary_enabled: Res<TurnSummary>,
+    practice: Res<PracticeMode>,
+    chain_manager: Res<ChainManager>,
+    current_level: Res<CurrentLevel>,
+    turn_data: Res<TurnData>,
+    theme_assets: Res<ThemeAssets>,
+    asset_server: Res<AssetServer>,
+) {
+    if !summary_enabled.0 || practice.active {
+        return;
+    }
+
+    // The level is over, the end dialogue takes it from here
+    let level_over = current_level.total_spores_earned >= current_level.target_score
+        || turn_data.current_turn >= current_level.max_turns;
+    if level_over {
+        return;
+    }
+
+    let turn_spores: f64 = chain_manager
+        .chains
+        .iter()
+        .map(|chain| chain.total_spores)
+        .sum();
+    let progress = (current_level.total_spores_earned / current_level.target_score).min(1.0);
+
+    let font = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
+    let text_font = |size: f32| TextFont {
+        font: font.clone(),
+        font_size: size,
+        font_smoothing: FontSmoothing::AntiAliased,
+        ..default()
+    };
+
+    let lines = [
+        format!("Spores this turn: {}", format_spores(turn_spores)),
+        format!("Chains: {}", chain_manager.chains.len()),
+        format!(
+            "Goal: {}/{} ({:.0}%)",
+            format_spores(current_level.total_spores_earned),
+            format_spores(current_level.target_score),
+            progress * 100.0
+        ),
+    ];
+
+    commands
+        .spawn((
+            Name::new("Turn Summary"),
+            TurnSummaryCard,
+            GameplayUI,
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(30.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-180.0)),
+                width: Val::Px(360.0),
+                padding: UiRect::all(Val::Px(24.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            ImageNode {
+                image: theme_assets.slice_1.clone(),
+                image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                color: Color::WHITE,
+                ..default()
+            },
+            GlobalZIndex(1),
+            StateScoped(TurnPhase::Score),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Turn {} complete", turn_data.current_turn)),
+                text_font(26.0),
+                TextColor(ui_palette::HEADER_TEXT),
+                Pickable::IGNORE,
+            ));
+
+            for line in lines {
+                parent.spawn((
+                    Text::new(line),
+                    text_font(18.0),
+                    TextColor(ui_palette::LABEL_TEXT),
+                    Pickable::IGNORE,
+                ));
+            }
+
+            parent.spawn((
+                Text::new("Click to continue"),
+                text_font(14.0),
+                TextColor(ui_palette::LABEL_TEXT.with_alpha(0.7)),
+                Pickable::IGNORE,
+            ));
+        })
+        .observe(dismiss_turn_summary);
+}
+
+/// Close the card and start the next turn
+fn dismiss_turn_summary(
+    _: Trigger<Pointer<Click>>,
+    mut turn_data: ResMut<TurnData>,
+    mut next_phase: ResMut<NextState<TurnPhase>>,
+) {
+    turn_data.current_turn += 1;
+    next_phase.set(TurnPhase::Draw);
+}
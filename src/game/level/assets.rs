@@ -23,6 +23,12 @@ pub struct LevelAssets {
     #[dependency]
     pub sfx_activate: Handle<AudioSource>,
     #[dependency]
+    pub sfx_place: Handle<AudioSource>,
+    #[dependency]
+    pub sfx_chain_complete: Handle<AudioSource>,
+    #[dependency]
+    pub sfx_goal_jingle: Handle<AudioSource>,
+    #[dependency]
     pub card_common: Handle<Image>,
     #[dependency]
     pub card_uncommon: Handle<Image>,
@@ -38,6 +44,15 @@ impl FromWorld for LevelAssets {
         let sfx_activate_handle = world
             .resource::<AssetServer>()
             .load("audio/sound_effects/activate.ogg");
+        let sfx_place_handle = world
+            .resource::<AssetServer>()
+            .load("audio/sound_effects/place.ogg");
+        let sfx_chain_complete_handle = world
+            .resource::<AssetServer>()
+            .load("audio/sound_effects/chain_complete.ogg");
+        let sfx_goal_jingle_handle = world
+            .resource::<AssetServer>()
+            .load("audio/sound_effects/goal_jingle.ogg");
         let mushroom_texture_handle = world
             .resource::<AssetServer>()
             .load("textures/mushrooms.png");
@@ -57,6 +72,9 @@ impl FromWorld for LevelAssets {
         Self {
             music: music_handle,
             sfx_activate: sfx_activate_handle,
+            sfx_place: sfx_place_handle,
+            sfx_chain_complete: sfx_chain_complete_handle,
+            sfx_goal_jingle: sfx_goal_jingle_handle,
             mushroom_texture: mushroom_texture_handle,
             tile_texture: tile_texture_handle,
             background_model_1: background_model_1_handle,
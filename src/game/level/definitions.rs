@@ -1,31 +1,169 @@
 //! Level definitions and configuration
+//!
+//! Levels are read from `assets/levels/campaign.levels.ron` when it exists, so they can be
+//! changed without a recompile (and hot reloaded in native dev builds). Without that file
+//! the levels built into [`LevelDefinitions::default`] are used.
+//! The `.levels.ron` extension keeps other RON assets from being loaded as levels.
 
-use bevy::prelude::*;
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+use bevy_common_assets::ron::RonAssetPlugin;
+use serde::Deserialize;
 
 use crate::game::{
     mushrooms::MushroomType,
-    play_field::{GridPosition, TileType},
-    resources::GameState,
+    play_field::{GridPosition, TileType, topology::GridTopology},
+    resources::{GameState, format_spores},
 };
 
+/// Where level designers put their levels
+const LEVELS_PATH: &str = "levels/campaign.levels.ron";
+
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(RonAssetPlugin::<LevelDefinitions>::new(&["levels.ron"]));
     app.init_resource::<LevelDefinitions>();
+    app.add_systems(Startup, load_level_file);
+    app.add_systems(Update, (apply_loaded_levels, warn_missing_level_file));
+}
+
+/// Handle to the level file, kept so hot reloads keep coming in
+#[derive(Resource)]
+struct LevelFile(Handle<LevelDefinitions>);
+
+fn load_level_file(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LevelFile(asset_server.load(LEVELS_PATH)));
+}
+
+/// Replace the levels in use whenever the level file loads or changes
+fn apply_loaded_levels(
+    mut events: EventReader<AssetEvent<LevelDefinitions>>,
+    level_file: Option<Res<LevelFile>>,
+    assets: Res<Assets<LevelDefinitions>>,
+    mut definitions: ResMut<LevelDefinitions>,
+) {
+    let Some(level_file) = level_file else {
+        return;
+    };
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != level_file.0.id() {
+            continue;
+        }
+        if let Some(loaded) = assets.get(*id) {
+            info!("Loaded {} levels from {}", loaded.levels.len(), LEVELS_PATH);
+            *definitions = loaded.clone();
+        }
+    }
+}
+
+/// Keep the built-in levels when there is no level file, or it can't be read
+fn warn_missing_level_file(mut events: EventReader<AssetLoadFailedEvent<LevelDefinitions>>) {
+    for event in events.read() {
+        warn!(
+            "Couldn't load levels from {} ({}), using the built-in levels",
+            event.path, event.error
+        );
+    }
 }
 
 /// A single level's configuration
-#[derive(Debug, Clone)]
+///
+/// Fields left out of the level file take the values from [`LevelDefinition::default`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct LevelDefinition {
     pub name: String,
     pub grid_width: i32,
     pub grid_height: i32,
     pub target_score: f64,
     pub max_turns: u32,
+    /// Chains the player may start each turn
+    pub max_chains_per_turn: u32,
+    /// Fraction of the target that earns one extra turn when the last turn falls short,
+    /// 1.0 for no overtime
+    pub overtime_threshold: f32,
+    /// Multiplier on the energy each mushroom passes on, 1.0 for no decay
+    pub energy_decay_per_hop: f32,
     pub starting_mushrooms: Vec<StartingMushroom>,
     pub tile_configuration: Vec<(GridPosition, TileType)>,
+    /// Cells cut out of the grid entirely, shown as gaps rather than blocked tiles
+    pub disabled_cells: Vec<GridPosition>,
+    /// Keep starting mushrooms unconnected until the player plants their first mushroom
+    pub hide_starting_connections: bool,
+    /// Optional goals on top of the spore target
+    pub bonus_goals: Vec<BonusGoal>,
+    /// How the level's cells neighbour each other
+    pub topology: GridTopology,
+    /// Cards of each type the deck starts with, used over the player's preset
+    /// Left empty, the deck comes from the preset or random cards
+    pub starting_deck: Vec<(MushroomType, u32)>,
+}
+
+/// An optional level goal and what completing it gives
+#[derive(Debug, Clone, Deserialize)]
+pub struct BonusGoal {
+    pub objective: BonusObjective,
+    pub reward: BonusReward,
+}
+
+/// Extra goals checked at the end of each turn, alongside the spore target
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum BonusObjective {
+    /// Have at least this many mycelium connections on the board
+    FormConnections(usize),
+    /// Reach the spore target within this many turns
+    WinWithinTurns(u32),
+    /// Earn at least this many spores in a single turn
+    SporesInOneTurn(f64),
+}
+
+impl BonusObjective {
+    /// Check the objective against the state at the end of a turn
+    pub fn is_complete(&self, connections: usize, turn: u32, turn_spores: f64, won: bool) -> bool {
+        match self {
+            BonusObjective::FormConnections(required) => connections >= *required,
+            BonusObjective::WinWithinTurns(turns) => won && turn <= *turns,
+            BonusObjective::SporesInOneTurn(required) => turn_spores >= *required,
+        }
+    }
+
+    /// Description for the UI
+    pub fn description(&self) -> String {
+        match self {
+            BonusObjective::FormConnections(required) => format!("Form {required} connections"),
+            BonusObjective::WinWithinTurns(turns) => format!("Win within {turns} turns"),
+            BonusObjective::SporesInOneTurn(required) => {
+                format!("Earn {} spores in one turn", format_spores(*required))
+            }
+        }
+    }
+}
+
+/// What completing a bonus goal gives the player
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum BonusReward {
+    /// Draw an extra card with the next hand, carried to the next level if this one ends
+    ExtraCard,
+    /// Spores added to the lifetime total, bringing unlocks closer
+    UnlockSpores(f64),
+}
+
+impl BonusReward {
+    /// Description for the UI
+    pub fn description(&self) -> String {
+        match self {
+            BonusReward::ExtraCard => "extra card".to_string(),
+            BonusReward::UnlockSpores(spores) => {
+                format!("+{} unlock spores", format_spores(*spores))
+            }
+        }
+    }
 }
 
 /// Mushrooms that are pre-placed on the level
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct StartingMushroom {
     pub x: i32,
     pub y: i32,
@@ -40,14 +178,64 @@ impl Default for LevelDefinition {
             grid_height: 8,
             target_score: 500.0,
             max_turns: 3,
+            max_chains_per_turn: 1,
+            overtime_threshold: 1.0,
+            energy_decay_per_hop: 1.0,
             starting_mushrooms: vec![],
             tile_configuration: vec![],
+            disabled_cells: vec![],
+            hide_starting_connections: false,
+            bonus_goals: vec![],
+            topology: GridTopology::Square,
+            starting_deck: vec![],
         }
     }
 }
 
+/// A cell listed more than once in a level's `tile_configuration`
+#[cfg(feature = "dev")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileConflict {
+    pub position: GridPosition,
+    /// Every tile given for the cell, in listed order. The last one wins.
+    pub tiles: Vec<TileType>,
+}
+
+#[cfg(feature = "dev")]
+impl TileConflict {
+    /// Whether the entries disagree, rather than just repeating the same tile
+    pub fn is_conflicting(&self) -> bool {
+        self.tiles.windows(2).any(|pair| pair[0] != pair[1])
+    }
+}
+
+#[cfg(feature = "dev")]
+impl LevelDefinition {
+    /// Cells that appear more than once in the tile configuration, in first listed order
+    pub fn tile_conflicts(&self) -> Vec<TileConflict> {
+        let mut conflicts: Vec<TileConflict> = Vec::new();
+        for (position, tile) in &self.tile_configuration {
+            if let Some(conflict) = conflicts.iter_mut().find(|c| c.position == *position) {
+                conflict.tiles.push(*tile);
+            } else if self
+                .tile_configuration
+                .iter()
+                .filter(|(other, _)| other == position)
+                .nth(1)
+                .is_some()
+            {
+                conflicts.push(TileConflict {
+                    position: *position,
+                    tiles: vec![*tile],
+                });
+            }
+        }
+        conflicts
+    }
+}
+
 /// Collection of all level definitions
-#[derive(Resource)]
+#[derive(Resource, Asset, TypePath, Debug, Clone, Deserialize)]
 pub struct LevelDefinitions {
     pub levels: Vec<LevelDefinition>,
 }
@@ -90,6 +278,11 @@ impl Default for LevelDefinitions {
                         (GridPosition::new(1, 11), TileType::BlockedMoss),
                         (GridPosition::new(2, 10), TileType::BlockedMoss),
                     ],
+                    bonus_goals: vec![BonusGoal {
+                        objective: BonusObjective::FormConnections(6),
+                        reward: BonusReward::ExtraCard,
+                    }],
+                    ..default()
                 },
                 // Level 2 - More blockers, higher score threshold
                 LevelDefinition {
@@ -132,6 +325,17 @@ impl Default for LevelDefinitions {
                         (GridPosition::new(5, 8), TileType::BlockedRock),
                         (GridPosition::new(2, 5), TileType::BlockedRock),
                     ],
+                    bonus_goals: vec![
+                        BonusGoal {
+                            objective: BonusObjective::SporesInOneTurn(400.0),
+                            reward: BonusReward::ExtraCard,
+                        },
+                        BonusGoal {
+                            objective: BonusObjective::WinWithinTurns(3),
+                            reward: BonusReward::UnlockSpores(250.0),
+                        },
+                    ],
+                    ..default()
                 },
                 // Level 3 - lots of moss & some rocks, same score threshold as previous
                 LevelDefinition {
@@ -223,6 +427,7 @@ impl Default for LevelDefinitions {
                         (GridPosition::new(11, 9), TileType::BlockedWater),
                         (GridPosition::new(3, 11), TileType::BlockedWater),
                     ],
+                    ..default()
                 },
                 // Level 4 - grid of "rooms"
                 LevelDefinition {
@@ -328,6 +533,7 @@ impl Default for LevelDefinitions {
                         (GridPosition::new(11, 9), TileType::BlockedRock),
                         (GridPosition::new(11, 10), TileType::BlockedRock),
                     ],
+                    ..default()
                 },
                 // Level 5 - Central pool, rocky sides, mossy corners
                 LevelDefinition {
@@ -414,6 +620,7 @@ impl Default for LevelDefinitions {
                         (GridPosition::new(9, 9), TileType::BlockedMoss),
                         (GridPosition::new(8, 8), TileType::BlockedMoss),
                     ],
+                    ..default()
                 },
             ],
         }
@@ -427,18 +634,107 @@ pub fn load_level_config(
     game_state: &mut GameState,
 ) -> Option<LevelDefinition> {
     if let Some(level_def) = definitions.get_level(level_index) {
+        // Catch copy-paste mistakes in the level data while designing
+        #[cfg(feature = "dev")]
+        for conflict in level_def.tile_conflicts() {
+            let position = conflict.position;
+            if conflict.is_conflicting() {
+                warn!(
+                    "Level \"{}\": tile ({}, {}) is set {} times with different tiles {:?}, using the last",
+                    level_def.name,
+                    position.x,
+                    position.y,
+                    conflict.tiles.len(),
+                    conflict.tiles
+                );
+            } else {
+                warn!(
+                    "Level \"{}\": tile ({}, {}) is listed {} times",
+                    level_def.name,
+                    position.x,
+                    position.y,
+                    conflict.tiles.len()
+                );
+            }
+        }
+
         // Resize the play field
         game_state
             .play_field
             .resize(level_def.grid_width, level_def.grid_height);
+        game_state.play_field.topology = level_def.topology;
+        game_state.play_field.set_holes(&level_def.disabled_cells);
 
         // Set tile configuration
         game_state
             .play_field
             .set_tiles_from_level(&level_def.tile_configuration);
 
+        // Same look for a level every time it's loaded this run, different between levels
+        game_state.play_field.render_seed = game_state
+            .render_seed
+            .wrapping_add((level_index as u32).wrapping_mul(0x9E37_79B9));
+
         Some(level_def.clone())
     } else {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonus_objectives_check_their_own_measure() {
+        let connections = BonusObjective::FormConnections(10);
+        assert!(!connections.is_complete(9, 1, 0.0, false));
+        assert!(connections.is_complete(10, 1, 0.0, false));
+
+        let spores = BonusObjective::SporesInOneTurn(500.0);
+        assert!(!spores.is_complete(0, 1, 499.0, false));
+        assert!(spores.is_complete(0, 1, 500.0, false));
+    }
+
+    #[test]
+    fn quick_win_bonus_needs_the_win_in_time() {
+        let quick_win = BonusObjective::WinWithinTurns(3);
+        assert!(quick_win.is_complete(0, 3, 0.0, true));
+        // Winning late, or being on time without winning, doesn't count
+        assert!(!quick_win.is_complete(0, 4, 0.0, true));
+        assert!(!quick_win.is_complete(0, 2, 0.0, false));
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn repeated_tile_entries_are_found_and_conflicts_told_apart() {
+        let level = LevelDefinition {
+            tile_configuration: vec![
+                (GridPosition::new(1, 1), TileType::BlockedRock),
+                (GridPosition::new(2, 2), TileType::Fertile),
+                (GridPosition::new(1, 1), TileType::BlockedWater),
+                (GridPosition::new(3, 0), TileType::Fertile),
+                (GridPosition::new(3, 0), TileType::Fertile),
+            ],
+            ..default()
+        };
+
+        let conflicts = level.tile_conflicts();
+        assert_eq!(
+            conflicts,
+            vec![
+                TileConflict {
+                    position: GridPosition::new(1, 1),
+                    tiles: vec![TileType::BlockedRock, TileType::BlockedWater],
+                },
+                TileConflict {
+                    position: GridPosition::new(3, 0),
+                    tiles: vec![TileType::Fertile, TileType::Fertile],
+                },
+            ]
+        );
+        // A plain repeat of the same tile is a duplicate, not a conflict
+        assert!(conflicts[0].is_conflicting());
+        assert!(!conflicts[1].is_conflicting());
+    }
+}
@@ -4,10 +4,22 @@ use bevy::prelude::*;
 
 pub(crate) mod assets;
 pub(crate) mod definitions;
+#[cfg(feature = "dev")]
+pub(crate) mod par;
+pub(crate) mod playback;
+pub(crate) mod recording;
 pub(crate) mod spawning;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((assets::plugin, spawning::plugin, definitions::plugin));
+    app.add_plugins((
+        assets::plugin,
+        spawning::plugin,
+        definitions::plugin,
+        recording::plugin,
+        playback::plugin,
+    ));
+    #[cfg(feature = "dev")]
+    app.add_plugins(par::plugin);
 
     // Add music tracking resource
     app.init_resource::<CurrentGameplayMusic>();
@@ -0,0 +1,220 @@
+//! Par score: a greedy estimate of the most spores a level can give with the current deck
+//!
+//! Only built in dev builds. Each turn the drawn cards are placed one at a time wherever
+//! they raise the best chain the most, then that turn's best chain is scored. The search
+//! is bounded by [`PAR_SIMULATION_BUDGET`], so this is an estimate rather than a true maximum.
+
+use bevy::{prelude::*, text::FontSmoothing};
+
+use crate::{
+    game::{
+        carddeck::deck::Deck,
+        game_flow::{CurrentLevel, LevelState, cards_drawn_on_turn},
+        level::definitions::{LevelDefinition, LevelDefinitions},
+        mushrooms::{
+            MushroomDefinitions, MushroomDirection, MushroomType,
+            chain_activation::ChainConfig,
+            simulation::{SimulatedBoard, best_chain, simulate_chain},
+            upgrades::MushroomUpgrades,
+        },
+        play_field::{GridPosition, PlayField},
+        resources::{GameState, format_spores},
+        ui::GameplayUI,
+    },
+    theme::palette as ui_palette,
+};
+
+/// Maximum number of chain simulations spent on one par estimate
+const PAR_SIMULATION_BUDGET: usize = 20_000;
+
+const DIRECTIONS: [MushroomDirection; 4] = [
+    MushroomDirection::Up,
+    MushroomDirection::Right,
+    MushroomDirection::Down,
+    MushroomDirection::Left,
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelPar>();
+    app.add_systems(
+        OnEnter(LevelState::Playing),
+        (compute_level_par, spawn_par_display).chain(),
+    );
+}
+
+/// Estimated par for the current level, if one has been computed
+#[derive(Resource, Default, Debug)]
+pub struct LevelPar(pub Option<f64>);
+
+fn compute_level_par(
+    mut par: ResMut<LevelPar>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    level_definitions: Res<LevelDefinitions>,
+    current_level: Res<CurrentLevel>,
+    deck: Res<Deck>,
+    game_state: Res<GameState>,
+) {
+    let Some(level) = level_definitions.get_level(current_level.level_index) else {
+        par.0 = None;
+        return;
+    };
+
+    let unlocked: Vec<MushroomType> = definitions
+        .all_types()
+        .into_iter()
+        .filter(|t| definitions.is_unlocked(*t, &game_state, current_level.level_index))
+        .collect();
+
+    // Wildcards can become any unlocked mushroom
+    let cards: Vec<Option<MushroomType>> = deck
+        .iter()
+        .map(|card| (!card.wildcard).then_some(card.mushroom_type))
+        .collect();
+
+    let estimate = estimate_par(&definitions, &upgrades, level, &cards, &unlocked);
+    info!(
+        "Par for level {}: {:.0} spores",
+        current_level.level_index + 1,
+        estimate
+    );
+    par.0 = Some(estimate);
+}
+
+/// Estimate the best total score for a level, drawing `cards` in order
+/// `None` cards are wildcards and may be any of the `unlocked` types
+pub fn estimate_par(
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+    level: &LevelDefinition,
+    cards: &[Option<MushroomType>],
+    unlocked: &[MushroomType],
+) -> f64 {
+    let mut field = PlayField::new(level.grid_width, level.grid_height);
+    field.set_tiles_from_level(&level.tile_configuration);
+    field.set_holes(&level.disabled_cells);
+
+    let mut board = SimulatedBoard::default();
+    for starting in &level.starting_mushrooms {
+        let position = GridPosition::new(starting.x, starting.y);
+        if field.contains(position) {
+            board.insert(
+                position,
+                (starting.mushroom_type, MushroomDirection::default()),
+            );
+        }
+    }
+
+    let config = ChainConfig::for_level(level);
+    let mut budget = PAR_SIMULATION_BUDGET;
+    let mut cards = cards.iter();
+    let mut total = 0.0;
+
+    for turn in 1..=level.max_turns {
+        for card in cards.by_ref().take(cards_drawn_on_turn(turn) as usize) {
+            let choices = match card {
+                Some(mushroom_type) => vec![*mushroom_type],
+                None => unlocked.to_vec(),
+            };
+            place_greedily(
+                definitions,
+                upgrades,
+                &config,
+                &field,
+                &mut board,
+                &choices,
+                &mut budget,
+            );
+        }
+
+        total += best_chain(definitions, upgrades, &config, &field, &board)
+            .map_or(0.0, |(_, score)| score);
+    }
+
+    total
+}
+
+/// Place one card where it raises the best chain the most, or nowhere if nothing helps
+fn place_greedily(
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+    config: &ChainConfig,
+    field: &PlayField,
+    board: &mut SimulatedBoard,
+    choices: &[MushroomType],
+    budget: &mut usize,
+) {
+    let current = best_chain(definitions, upgrades, config, field, board);
+    *budget = budget.saturating_sub(board.len());
+
+    let best_start = current.map(|(start, _)| start);
+    let mut best_score = current.map_or(0.0, |(_, score)| score);
+    let mut best_placement = None;
+
+    'search: for y in 0..field.height {
+        for x in 0..field.width {
+            let position = GridPosition::new(x, y);
+            let placeable = field
+                .get_tile(position)
+                .is_some_and(|tile| tile.allows_mushroom());
+            if board.contains_key(&position) || !placeable {
+                continue;
+            }
+
+            for &mushroom_type in choices {
+                for direction in DIRECTIONS {
+                    if *budget < 2 {
+                        break 'search;
+                    }
+
+                    // Only try the previous best starter and the new mushroom, to keep the search bounded
+                    board.insert(position, (mushroom_type, direction));
+                    let score = best_start
+                        .into_iter()
+                        .chain([position])
+                        .map(|start| {
+                            simulate_chain(definitions, upgrades, config, field, board, start)
+                        })
+                        .fold(0.0, f64::max);
+                    board.remove(&position);
+                    *budget -= 2;
+
+                    if score > best_score {
+                        best_score = score;
+                        best_placement = Some((position, mushroom_type, direction));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some((position, mushroom_type, direction)) = best_placement {
+        board.insert(position, (mushroom_type, direction));
+    }
+}
+
+fn spawn_par_display(mut commands: Commands, par: Res<LevelPar>, asset_server: Res<AssetServer>) {
+    let Some(par) = par.0 else {
+        return;
+    };
+
+    commands.spawn((
+        Name::new("Par Display"),
+        Text::new(format!("Par: {}", format_spores(par))),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 20.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(ui_palette::LABEL_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        GameplayUI,
+        StateScoped(LevelState::Playing),
+    ));
+}
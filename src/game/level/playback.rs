@@ -0,0 +1,396 @@
+//! Watching the best run for the current level
+//!
+//! Press B while planting to play the level's best run back on the board it started
+//! from: placements, moves and removals go through the same events as during play,
+//! and chain starts pulse the starter with the spores the chain would make on that
+//! board. Cards, spores and the recording are left alone. When the run ends, B is
+//! pressed again or planting ends, the board is put back the way it was.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, text::FontSmoothing};
+use std::collections::VecDeque;
+
+use super::recording::{BestRuns, RunActionKind, RunRecorder, RunRecording};
+use crate::{
+    PausableSystems,
+    game::{
+        game_flow::{CurrentLevel, LevelState, TurnPhase, practice::PracticeMode},
+        mushrooms::{
+            Mushroom, MushroomDefinitions, MushroomDirection, SpawnMushroomEvent, SpawnSource,
+            chain_activation::ChainConfig,
+            events::SporeScoreEvent,
+            simulation::{SimulatedBoard, simulate_chain},
+            upgrades::MushroomUpgrades,
+        },
+        play_field::{GridPosition, field_renderer::FieldGround, snapshot::BoardSnapshot},
+        resources::{GameState, format_spores},
+        ui::GameplayUI,
+        visual_effects::ActivationAnimation,
+    },
+    theme::palette as ui_palette,
+};
+
+const WATCH_KEY: KeyCode = KeyCode::KeyB;
+/// Shortest gap between played back actions, so each one can be seen
+const MIN_ACTION_GAP: f32 = 0.15;
+/// Longest gap between played back actions, the player's thinking time is cut short
+const MAX_ACTION_GAP: f32 = 1.0;
+/// How long the final board stays up before it's put back
+const END_HOLD: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(start_playback);
+    app.add_systems(
+        Update,
+        (
+            toggle_playback.run_if(input_just_pressed(WATCH_KEY)),
+            play_back.run_if(resource_exists::<BestRunPlayback>),
+        )
+            .chain()
+            .run_if(in_state(TurnPhase::Planting))
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnExit(TurnPhase::Planting), stop_playback);
+}
+
+/// Start watching the current level's best run
+#[derive(Event, Debug)]
+pub struct WatchBestRunEvent;
+
+/// Playback in progress, only exists while a best run is being watched
+#[derive(Resource, Debug)]
+pub struct BestRunPlayback {
+    /// Actions still to play, with the playback time they're due at
+    remaining: VecDeque<(f32, RunActionKind)>,
+    elapsed: f32,
+    /// When the last action plays
+    end: f32,
+    /// Board as it was before the playback
+    return_to: BoardSnapshot,
+}
+
+/// Banner shown while watching, also catches clicks so the board can't be edited
+#[derive(Component)]
+struct PlaybackOverlay;
+
+/// Playback times for a recording, gaps are clamped so it keeps moving
+fn playback_schedule(recording: &RunRecording) -> VecDeque<(f32, RunActionKind)> {
+    let mut schedule = VecDeque::with_capacity(recording.actions.len());
+    let mut at = 0.0;
+    let mut previous: Option<f32> = None;
+    for action in &recording.actions {
+        if let Some(previous) = previous {
+            at += (action.time - previous).clamp(MIN_ACTION_GAP, MAX_ACTION_GAP);
+        }
+        previous = Some(action.time);
+        schedule.push_back((at, action.kind.clone()));
+    }
+    schedule
+}
+
+fn toggle_playback(
+    mut commands: Commands,
+    playback: Option<Res<BestRunPlayback>>,
+    mut game_state: ResMut<GameState>,
+    mushrooms: Query<Entity, With<Mushroom>>,
+    field_grounds: Query<Entity, With<FieldGround>>,
+    overlays: Query<Entity, With<PlaybackOverlay>>,
+) {
+    match playback {
+        Some(playback) => finish_playback(
+            &mut commands,
+            &playback,
+            &mut game_state,
+            mushrooms,
+            field_grounds,
+            overlays,
+        ),
+        None => commands.trigger(WatchBestRunEvent),
+    }
+}
+
+fn start_playback(
+    _: Trigger<WatchBestRunEvent>,
+    mut commands: Commands,
+    current_phase: Option<Res<State<TurnPhase>>>,
+    practice: Res<PracticeMode>,
+    current_level: Res<CurrentLevel>,
+    best_runs: Res<BestRuns>,
+    recorder: Res<RunRecorder>,
+    mut game_state: ResMut<GameState>,
+    mushrooms: Query<(Entity, &Mushroom, &GridPosition, &MushroomDirection)>,
+    field_grounds: Query<Entity, With<FieldGround>>,
+    asset_server: Res<AssetServer>,
+) {
+    if !current_phase.is_some_and(|phase| *phase.get() == TurnPhase::Planting) || practice.active {
+        info!("Best runs can only be watched while planting");
+        return;
+    }
+    let Some(recording) = best_runs.get(current_level.level_index) else {
+        info!("No best run for this level yet");
+        return;
+    };
+    let Some(start) = &recorder.start else {
+        return;
+    };
+
+    let return_to = BoardSnapshot::capture(
+        &game_state,
+        mushrooms
+            .iter()
+            .map(|(_, mushroom, position, direction)| (mushroom, position, direction)),
+    );
+    // Only the board goes back to the start, the spore counters stay as they are
+    let start = BoardSnapshot {
+        tiles: start.tiles.clone(),
+        mushrooms: start.mushrooms.clone(),
+        ..return_to.clone()
+    };
+    start.restore(
+        &mut commands,
+        &mut game_state,
+        mushrooms.iter().map(|(entity, ..)| entity),
+        field_grounds,
+    );
+
+    let remaining = playback_schedule(recording);
+    let end = remaining.back().map_or(0.0, |(at, _)| *at);
+    info!(
+        "Watching best run for level {}: {:.0} spores, {} actions",
+        current_level.level_index + 1,
+        recording.score,
+        remaining.len()
+    );
+    commands.insert_resource(BestRunPlayback {
+        remaining,
+        elapsed: 0.0,
+        end,
+        return_to,
+    });
+
+    commands.spawn((
+        Name::new("Best Run Playback"),
+        PlaybackOverlay,
+        GameplayUI,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::FlexStart,
+            padding: UiRect::top(Val::Px(96.0)),
+            ..default()
+        },
+        GlobalZIndex(1),
+        StateScoped(LevelState::Playing),
+        children![(
+            Name::new("Best Run Banner"),
+            Text::new(format!(
+                "Watching best run ({} spores) - press B to stop",
+                format_spores(recording.score)
+            )),
+            TextFont {
+                font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                font_size: 28.0,
+                font_smoothing: FontSmoothing::AntiAliased,
+                ..default()
+            },
+            TextColor(ui_palette::HEADER_TEXT),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn play_back(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut playback: ResMut<BestRunPlayback>,
+    mut game_state: ResMut<GameState>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    chain_config: Res<ChainConfig>,
+    mut mushrooms: Query<(
+        Entity,
+        &Mushroom,
+        &mut GridPosition,
+        &mut Transform,
+        &MushroomDirection,
+    )>,
+    field_grounds: Query<Entity, With<FieldGround>>,
+    overlays: Query<Entity, With<PlaybackOverlay>>,
+) {
+    playback.elapsed += time.delta_secs();
+
+    if playback.remaining.is_empty() {
+        if playback.elapsed >= playback.end + END_HOLD {
+            finish_playback(
+                &mut commands,
+                &playback,
+                &mut game_state,
+                mushrooms.iter().map(|(entity, ..)| entity),
+                field_grounds,
+                overlays,
+            );
+        }
+        return;
+    }
+
+    // One action a frame, a placement's entity only exists once its spawn has run
+    if playback
+        .remaining
+        .front()
+        .is_none_or(|(at, _)| *at > playback.elapsed)
+    {
+        return;
+    }
+    let Some((_, action)) = playback.remaining.pop_front() else {
+        return;
+    };
+
+    match action {
+        RunActionKind::Place {
+            mushroom_type,
+            position,
+            direction,
+        } => {
+            if game_state.play_field.get(position).is_none() {
+                commands.trigger(SpawnMushroomEvent {
+                    position,
+                    mushroom_type,
+                    direction,
+                    entity: None,
+                    source: SpawnSource::Restored,
+                });
+            }
+        }
+        RunActionKind::Move { from, to } => {
+            let Some(entity) = game_state.play_field.get(from) else {
+                return;
+            };
+            if game_state.play_field.get(to).is_some() {
+                return;
+            }
+            if let Ok((_, _, mut position, mut transform, _)) = mushrooms.get_mut(entity) {
+                game_state.play_field.remove(from);
+                game_state.play_field.insert(to, entity);
+                *position = to;
+                let world_pos = to.to_world_in(&game_state.play_field);
+                transform.translation = Vec3::new(world_pos.x, 0.5, -world_pos.z);
+            }
+        }
+        RunActionKind::Remove { position } => {
+            if let Some(entity) = game_state.play_field.remove(position) {
+                commands.entity(entity).despawn();
+            }
+        }
+        RunActionKind::StartChain { position } => {
+            let Some(entity) = game_state.play_field.get(position) else {
+                return;
+            };
+            let board: SimulatedBoard = mushrooms
+                .iter()
+                .map(|(_, mushroom, at, _, direction)| (*at, (mushroom.0, *direction)))
+                .collect();
+            let production = simulate_chain(
+                &definitions,
+                &upgrades,
+                &chain_config,
+                &game_state.play_field,
+                &board,
+                position,
+            );
+            commands.trigger(SporeScoreEvent {
+                position,
+                production,
+                depth: 0,
+            });
+            if let Ok((_, _, _, transform, _)) = mushrooms.get(entity) {
+                commands
+                    .entity(entity)
+                    .insert(ActivationAnimation::new(0.4, 1.3, transform.scale));
+            }
+        }
+    }
+}
+
+/// Put the board back if planting ends mid-playback
+fn stop_playback(
+    mut commands: Commands,
+    playback: Option<Res<BestRunPlayback>>,
+    mut game_state: ResMut<GameState>,
+    mushrooms: Query<Entity, With<Mushroom>>,
+    field_grounds: Query<Entity, With<FieldGround>>,
+    overlays: Query<Entity, With<PlaybackOverlay>>,
+) {
+    if let Some(playback) = playback {
+        finish_playback(
+            &mut commands,
+            &playback,
+            &mut game_state,
+            mushrooms,
+            field_grounds,
+            overlays,
+        );
+    }
+}
+
+fn finish_playback(
+    commands: &mut Commands,
+    playback: &BestRunPlayback,
+    game_state: &mut GameState,
+    mushrooms: impl IntoIterator<Item = Entity>,
+    field_grounds: impl IntoIterator<Item = Entity>,
+    overlays: impl IntoIterator<Item = Entity>,
+) {
+    info!("Best run playback finished, restoring board");
+    playback
+        .return_to
+        .restore(commands, game_state, mushrooms, field_grounds);
+    for entity in overlays {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<BestRunPlayback>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::level::recording::{RECORDING_VERSION, RunAction};
+
+    fn action(time: f32, x: i32) -> RunAction {
+        RunAction {
+            turn: 1,
+            time,
+            kind: RunActionKind::Remove {
+                position: GridPosition::new(x, 0),
+            },
+        }
+    }
+
+    #[test]
+    fn schedule_clamps_gaps_between_actions() {
+        let recording = RunRecording {
+            version: RECORDING_VERSION,
+            level_index: 0,
+            score: 100.0,
+            actions: vec![
+                action(3.0, 0),
+                action(3.01, 1),
+                action(4.5, 2),
+                action(30.0, 3),
+            ],
+        };
+
+        let times: Vec<f32> = playback_schedule(&recording)
+            .into_iter()
+            .map(|(at, _)| at)
+            .collect();
+
+        // The first action plays straight away, a near-instant follow up is spread out
+        // and a long pause is cut down
+        assert_eq!(times.len(), 4);
+        assert_eq!(times[0], 0.0);
+        assert!((times[1] - MIN_ACTION_GAP).abs() < 1e-6);
+        assert!((times[2] - (MIN_ACTION_GAP + MAX_ACTION_GAP)).abs() < 1e-6);
+        assert!((times[3] - (MIN_ACTION_GAP + 2.0 * MAX_ACTION_GAP)).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,226 @@
+//! Recording the player's actions during a level, and keeping the best run per level
+//!
+//! Placements, moves, removals and chain starts are logged with the turn and time they
+//! happened, along with the board the level started from. When a level is won, the run
+//! replaces the stored one if it scored higher.
+//!
+//! Best runs are written to the save and played back by `playback`. Recordings carry a
+//! schema version, ones from an older schema are dropped when the save is read rather
+//! than misread. Ones that no longer parse at all are dropped by the save on their own,
+//! the rest of the save still loads.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    game_flow::{CurrentLevel, LevelState, TurnData, practice::PracticeMode},
+    mushrooms::{Mushroom, MushroomDirection, MushroomType},
+    play_field::{GridPosition, snapshot::BoardSnapshot},
+    resources::GameState,
+};
+
+/// Bump when [`RunAction`] changes shape
+pub const RECORDING_VERSION: u32 = 1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunRecorder>();
+    app.init_resource::<BestRuns>();
+    app.add_event::<RecordActionEvent>();
+    app.add_observer(record_action);
+
+    app.add_systems(OnEnter(LevelState::Playing), start_recording);
+    app.add_systems(OnEnter(LevelState::Success), keep_best_run);
+}
+
+/// Something the player did that changes the board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RunActionKind {
+    Place {
+        mushroom_type: MushroomType,
+        position: GridPosition,
+        direction: Option<MushroomDirection>,
+    },
+    Move {
+        from: GridPosition,
+        to: GridPosition,
+    },
+    Remove {
+        position: GridPosition,
+    },
+    StartChain {
+        position: GridPosition,
+    },
+}
+
+/// A recorded action, with when it happened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunAction {
+    pub turn: u32,
+    /// Seconds since the level started
+    pub time: f32,
+    pub kind: RunActionKind,
+}
+
+/// Every action from one playthrough of a level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecording {
+    pub version: u32,
+    pub level_index: usize,
+    pub score: f64,
+    pub actions: Vec<RunAction>,
+}
+
+impl RunRecording {
+    /// Whether this recording can be replayed by the current build
+    pub fn is_compatible(&self) -> bool {
+        self.version == RECORDING_VERSION
+    }
+}
+
+/// The run currently being recorded
+#[derive(Resource, Default, Debug)]
+pub struct RunRecorder {
+    pub actions: Vec<RunAction>,
+    /// Board the level started from, what a recording plays back on
+    pub start: Option<BoardSnapshot>,
+    started_at: f32,
+}
+
+/// Highest scoring run for each level, by level index
+#[derive(Resource, Default, Debug)]
+pub struct BestRuns(pub HashMap<usize, RunRecording>);
+
+impl BestRuns {
+    /// The best run for a level, ignoring recordings from an older schema
+    pub fn get(&self, level_index: usize) -> Option<&RunRecording> {
+        self.0
+            .get(&level_index)
+            .filter(|recording| recording.is_compatible())
+    }
+
+    /// Runs to write to the save, by level
+    pub fn to_saved(&self) -> Vec<RunRecording> {
+        let mut runs: Vec<_> = self.0.values().cloned().collect();
+        runs.sort_by_key(|recording| recording.level_index);
+        runs
+    }
+
+    /// Take the runs read from a save, dropping any from an older schema
+    pub fn load(&mut self, runs: &[RunRecording]) {
+        self.0.clear();
+        for recording in runs {
+            if !recording.is_compatible() {
+                info!(
+                    "Dropping best run for level {}, it was recorded by an older version",
+                    recording.level_index + 1
+                );
+                continue;
+            }
+            self.0.insert(recording.level_index, recording.clone());
+        }
+    }
+}
+
+/// Event to log a player action in the current run
+#[derive(Event, Debug, Clone)]
+pub struct RecordActionEvent(pub RunActionKind);
+
+fn start_recording(
+    mut recorder: ResMut<RunRecorder>,
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mushrooms: Query<(&Mushroom, &GridPosition, &MushroomDirection)>,
+) {
+    recorder.actions.clear();
+    recorder.start = Some(BoardSnapshot::capture(&game_state, mushrooms.iter()));
+    recorder.started_at = time.elapsed_secs();
+}
+
+fn record_action(
+    trigger: Trigger<RecordActionEvent>,
+    mut recorder: ResMut<RunRecorder>,
+    practice: Res<PracticeMode>,
+    turn_data: Res<TurnData>,
+    time: Res<Time>,
+) {
+    // Practice chains are thrown away, so they aren't part of the run
+    if practice.active {
+        return;
+    }
+
+    let time = time.elapsed_secs() - recorder.started_at;
+    recorder.actions.push(RunAction {
+        turn: turn_data.current_turn,
+        time,
+        kind: trigger.0.clone(),
+    });
+}
+
+fn keep_best_run(
+    recorder: Res<RunRecorder>,
+    current_level: Res<CurrentLevel>,
+    mut best_runs: ResMut<BestRuns>,
+) {
+    let score = current_level.total_spores_earned;
+    let level_index = current_level.level_index;
+
+    // Replace the stored run if this one is better, or if the stored one is from an older schema
+    let is_better = best_runs
+        .get(level_index)
+        .is_none_or(|best| score > best.score);
+    if !is_better {
+        return;
+    }
+
+    info!(
+        "New best run for level {}: {:.0} spores, {} actions",
+        level_index + 1,
+        score,
+        recorder.actions.len()
+    );
+    best_runs.0.insert(
+        level_index,
+        RunRecording {
+            version: RECORDING_VERSION,
+            level_index,
+            score,
+            actions: recorder.actions.clone(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(version: u32, level_index: usize) -> RunRecording {
+        RunRecording {
+            version,
+            level_index,
+            score: 50.0,
+            actions: vec![RunAction {
+                turn: 1,
+                time: 2.0,
+                kind: RunActionKind::Remove {
+                    position: GridPosition::new(1, 2),
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn saved_runs_round_trip_and_older_schemas_are_dropped() {
+        let mut best_runs = BestRuns::default();
+        best_runs.load(&[
+            recording(RECORDING_VERSION, 0),
+            recording(RECORDING_VERSION - 1, 1),
+        ]);
+        assert!(best_runs.get(0).is_some());
+        assert!(best_runs.get(1).is_none());
+
+        let contents = ron::to_string(&best_runs.to_saved()).unwrap();
+        let runs: Vec<RunRecording> = ron::from_str(&contents).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].actions[0].kind, best_runs.0[&0].actions[0].kind);
+    }
+}
@@ -8,11 +8,15 @@ use crate::{
     game::{
         game_flow::{CurrentLevel, LevelLifecycle, LevelState},
         level::{CurrentGameplayMusic, definitions::LevelDefinitions},
-        mushrooms::{MushroomDefinitions, events::SpawnMushroomEvent},
+        mushrooms::{
+            MushroomDefinitions,
+            events::{SpawnMushroomEvent, SpawnSource},
+        },
         play_field::{
-            CELL_SIZE, GridPosition,
+            GridPosition,
             events::GridCell,
             field_renderer::{FieldGroundExtension, spawn_field_ground},
+            mycelium::MyceliumConnectionsEnabled,
         },
         resources::GameState,
     },
@@ -42,6 +46,7 @@ pub fn spawn_level(
     mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
     _music_query: Query<&AudioPlayer, With<Music>>,
     mut gameplay_music: ResMut<CurrentGameplayMusic>,
+    mut connections_enabled: ResMut<MyceliumConnectionsEnabled>,
 ) {
     // Get level definition
     let level_def = level_definitions
@@ -69,6 +74,8 @@ pub fn spawn_level(
 
     // Spawn starting mushrooms if any are defined
     if let Some(level_def) = level_def {
+        connections_enabled.0 = !level_def.hide_starting_connections;
+
         commands.spawn((
             Name::new("Level Background"),
             SceneRoot(level_assets.background_model_1.clone()),
@@ -94,6 +101,8 @@ pub fn spawn_level(
                     position,
                     mushroom_type: starting_mushroom.mushroom_type,
                     direction: None,
+                    entity: None,
+                    source: SpawnSource::Level,
                 });
             } else {
                 warn!(
@@ -175,6 +184,10 @@ pub fn spawn_game_grid(
     for y in 0..game_state.play_field.height {
         for x in 0..game_state.play_field.width {
             let position = GridPosition::new(x, y);
+            // Holes get no cell, so they can't be hovered or clicked
+            if !game_state.play_field.contains(position) {
+                continue;
+            }
             let world_pos = position.to_world_in(&game_state.play_field);
 
             let cell = commands
@@ -182,7 +195,7 @@ pub fn spawn_game_grid(
                     Name::new(format!("Grid Cell ({x}, {y})")),
                     GridCell { position },
                     // Invisible collider for click detection only
-                    Mesh3d(meshes.add(Rectangle::new(CELL_SIZE, CELL_SIZE))),
+                    Mesh3d(meshes.add(game_state.play_field.topology.cell_mesh())),
                     MeshMaterial3d(materials.add(StandardMaterial {
                         base_color: Color::NONE, // Fully transparent
                         alpha_mode: AlphaMode::Blend,
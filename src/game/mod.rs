@@ -2,26 +2,34 @@
 
 use bevy::prelude::*;
 
+use crate::PausableSystems;
+
 pub mod carddeck;
+mod cursor;
 pub(crate) mod dialogue;
 pub(crate) mod fixed_timestep;
 pub(crate) mod game_flow;
 pub(crate) mod level;
-mod mushrooms;
+pub(crate) mod mushrooms;
 mod particles;
 pub(crate) mod play_field;
 mod resources;
+pub(crate) mod rng;
+pub(crate) mod save;
 mod ui;
-mod visual_effects;
+pub(crate) mod visual_effects;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, despawn_timer_system);
+    // Frozen while paused, so effects don't vanish behind the pause menu
+    app.add_systems(Update, despawn_timer_system.in_set(PausableSystems));
 
     app.add_plugins((
         dialogue::plugin,
         particles::plugin,
         fixed_timestep::plugin,
         resources::plugin,
+        rng::plugin,
+        save::plugin,
         play_field::plugin,
         mushrooms::plugin,
         visual_effects::plugin,
@@ -29,6 +37,7 @@ pub(super) fn plugin(app: &mut App) {
         game_flow::plugin,
         ui::plugin,
         carddeck::plugin,
+        cursor::plugin,
     ));
 }
 
@@ -49,18 +58,25 @@ impl DespawnTimer {
 }
 
 /// System that updates despawn timers and removes entities when their time is up
+///
+/// Pooled particle effects are returned to the [`particles::ParticleEffectPool`] instead
 pub fn despawn_timer_system(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut DespawnTimer)>,
+    mut pool: ResMut<particles::ParticleEffectPool>,
+    mut query: Query<(Entity, &mut DespawnTimer, Has<particles::PooledEffect>)>,
 ) {
-    for (entity, mut despawn_timer) in &mut query {
+    for (entity, mut despawn_timer, pooled) in &mut query {
         // Tick the timer with the time that has passed since the last frame
         despawn_timer.timer.tick(time.delta());
 
-        // If the timer has finished, despawn the entity
+        // If the timer has finished, despawn (or recycle) the entity
         if despawn_timer.timer.finished() {
-            commands.entity(entity).despawn();
+            if pooled {
+                particles::recycle_effect(&mut commands, &mut pool, entity);
+            } else {
+                commands.entity(entity).despawn();
+            }
         }
     }
 }
@@ -1,24 +1,31 @@
 //! Chain activation system
 
 use bevy::prelude::*;
-use bevy_hanabi::{EffectAsset, ParticleEffect};
 use std::collections::VecDeque;
 use std::time::Duration;
 
+use crate::PausableSystems;
 use crate::game::{
-    DespawnTimer,
     fixed_timestep::GameTime,
-    game_flow::{CurrentLevel, TurnData},
-    mushrooms::events::SporeScoreEvent,
-    particles::assets::activate_effect,
-    play_field::GridPosition,
-    resources::GameState,
+    game_flow::{CurrentLevel, LevelState, TurnData, TurnPhase},
+    level::definitions::{LevelDefinition, LevelDefinitions},
+    mushrooms::events::{
+        ActivationEnergyEvent, ChainCompleteEvent, SpawnMushroomEvent, SpawnSource, SporeScoreEvent,
+    },
+    particles::{ParticleEffectPool, ParticleEffects},
+    play_field::{
+        GridPosition, PlayField, TileType,
+        field_renderer::{FieldGround, TilesDirty},
+        mycelium::ConnectionBuilder,
+    },
+    resources::{GameState, clamp_spores},
     visual_effects::ActivationAnimation,
 };
 
 use super::{
-    Mushroom, MushroomDirection,
-    definitions::{ActivationBehavior, GridOffset, MushroomDefinition, MushroomDefinitions},
+    Mushroom, MushroomDirection, MushroomType, TemporaryMushroom,
+    definitions::{ActivationBehavior, MushroomDefinition, MushroomDefinitions},
+    upgrades::MushroomUpgrades,
 };
 
 /// A chain represents a single activation sequence
@@ -35,11 +42,65 @@ pub struct Chain {
     pub total_spores: f64,
     /// Whether this chain is still active
     pub active: bool,
+    /// Where and why each branch of the chain stopped
+    pub endings: Vec<ChainEnding>,
+}
+
+impl Chain {
+    /// Count of endings by reason, e.g. "2 reached max uses, 1 on cooldown"
+    pub fn ending_summary(&self) -> String {
+        let mut counts: Vec<(ChainEndReason, usize)> = Vec::new();
+        for ending in &self.endings {
+            match counts
+                .iter_mut()
+                .find(|(reason, _)| *reason == ending.reason)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((ending.reason, 1)),
+            }
+        }
+
+        counts
+            .iter()
+            .map(|(reason, count)| format!("{} {}", count, reason.description()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Why a branch of a chain stopped propagating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainEndReason {
+    /// The mushroom had already been activated as many times as it can this turn
+    MaxUses,
+    /// The mushroom was still cooling down from an earlier activation
+    OnCooldown,
+    /// None of its connection points had a mushroom on them
+    NoConnectionTarget,
+    /// All of its connection points fell off the edge of the field
+    OutOfBounds,
+}
+
+impl ChainEndReason {
+    pub fn description(&self) -> &'static str {
+        match self {
+            ChainEndReason::MaxUses => "reached max uses",
+            ChainEndReason::OnCooldown => "on cooldown",
+            ChainEndReason::NoConnectionTarget => "no connection target",
+            ChainEndReason::OutOfBounds => "out of bounds",
+        }
+    }
+}
+
+/// A leaf of a chain and why the chain stopped there
+#[derive(Debug, Clone)]
+pub struct ChainEnding {
+    pub position: GridPosition,
+    pub reason: ChainEndReason,
 }
 
 /// A single activation within a chain
-/// This is for storing the information about each activation, it is not functional
-/// may be used in future for replay system or debugging purposes
+/// Only a record, the chain replay plays these back without re-running the chain
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct ChainActivation {
@@ -48,6 +109,8 @@ pub struct ChainActivation {
     pub energy: f32,
     pub depth: u32,
     pub parent: Option<Entity>,
+    /// Spores this activation produced
+    pub production: f64,
 }
 
 /// Energy packet traveling through the network
@@ -57,10 +120,22 @@ pub struct EnergyPacket {
     pub source_entity: Entity,
     /// Full path of entities visited in order
     pub path: Vec<Entity>,
+    /// Number of hops from the chain's starter
+    pub depth: u32,
 }
 
+/// Cap on the energy a packet can carry, so compounding amplifiers stay finite
+pub const MAX_ENERGY: f32 = 1.0e6;
+
+/// Longest an activation will wait for its target to come off cooldown, in seconds
+/// Targets cooling down for longer end the branch instead
+pub const MAX_COOLDOWN_WAIT: f32 = 1.0;
+
+/// Default for [`ChainManager::max_chain_depth`]
+pub const DEFAULT_MAX_CHAIN_DEPTH: u32 = 64;
+
 /// Resource for managing active chains
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct ChainManager {
     /// All chains that have been started in this run
     pub chains: Vec<Chain>,
@@ -68,10 +143,28 @@ pub struct ChainManager {
     next_chain_id: u32,
     /// Queue of pending activations
     pub activation_queue: VecDeque<PendingActivation>,
+    /// Next `PendingActivation::sequence` to hand out
+    next_sequence: u64,
     /// Currently processing chain
     pub current_chain: Option<u32>,
-    /// Has a chain been started this turn?
-    pub chain_started_this_turn: bool,
+    /// Chains that can still be started this turn
+    pub chains_remaining: u32,
+    /// Deepest a packet may travel, so mushrooms feeding each other can't queue forever
+    pub max_chain_depth: u32,
+}
+
+impl Default for ChainManager {
+    fn default() -> Self {
+        Self {
+            chains: Vec::new(),
+            next_chain_id: 0,
+            activation_queue: VecDeque::new(),
+            next_sequence: 0,
+            current_chain: None,
+            chains_remaining: 1,
+            max_chain_depth: DEFAULT_MAX_CHAIN_DEPTH,
+        }
+    }
 }
 
 /// A pending activation waiting to be processed
@@ -81,6 +174,8 @@ pub struct PendingActivation {
     pub energy_packet: EnergyPacket,
     pub delay: Timer,
     pub chain_id: u32,
+    /// Order the activation was queued in, breaks ties between activations ready on the same tick
+    pub sequence: u64,
 }
 
 /// Component tracking per-mushroom activation state
@@ -94,8 +189,8 @@ pub struct MushroomActivationState {
 impl ChainManager {
     /// Start a new chain from a mushroom
     pub fn start_chain(&mut self, starter: Entity, position: GridPosition) -> Option<u32> {
-        if self.chain_started_this_turn {
-            info!("Chain already started this turn!");
+        if self.chains_remaining == 0 {
+            info!("No chains left to start this turn!");
             return None;
         }
 
@@ -108,17 +203,19 @@ impl ChainManager {
             activations: vec![],
             total_spores: 0.0,
             active: true,
+            endings: vec![],
         };
 
         self.chains.push(chain);
         self.current_chain = Some(chain_id);
-        self.chain_started_this_turn = true;
+        self.chains_remaining -= 1;
 
         // Queue the initial activation
         let energy_packet = EnergyPacket {
             energy: 1.0,
             source_entity: starter,
             path: vec![],
+            depth: 0,
         };
 
         self.queue_activation(starter, energy_packet, 0.0, chain_id);
@@ -128,6 +225,7 @@ impl ChainManager {
     }
 
     /// Queue an activation for processing
+    /// Packets deeper than [`Self::max_chain_depth`] are dropped instead
     pub fn queue_activation(
         &mut self,
         entity: Entity,
@@ -135,12 +233,22 @@ impl ChainManager {
         delay: f32,
         chain_id: u32,
     ) {
+        if energy_packet.depth > self.max_chain_depth {
+            debug!(
+                "Chain {} hit the depth cap of {}, dropping packet for {:?}",
+                chain_id, self.max_chain_depth, entity
+            );
+            return;
+        }
+
         self.activation_queue.push_back(PendingActivation {
             entity,
             energy_packet,
             delay: Timer::from_seconds(delay, TimerMode::Once),
             chain_id,
+            sequence: self.next_sequence,
         });
+        self.next_sequence += 1;
     }
 
     /// Get the currently active chain
@@ -159,20 +267,154 @@ impl ChainManager {
         !self.activation_queue.is_empty() || self.chains.iter().any(|c| c.active)
     }
 
-    /// Reset for new turn
-    pub fn reset_turn(&mut self) {
+    /// Drop every pending activation and mark all chains finished
+    /// Spores already earned by the chains are kept
+    pub fn flush(&mut self) {
+        if !self.activation_queue.is_empty() {
+            info!(
+                "Flushing {} pending activations",
+                self.activation_queue.len()
+            );
+        }
+        self.activation_queue.clear();
+        for chain in &mut self.chains {
+            chain.active = false;
+        }
+        self.current_chain = None;
+    }
+
+    /// Note that a branch of a chain stopped at `position`
+    pub fn record_ending(&mut self, chain_id: u32, position: GridPosition, reason: ChainEndReason) {
+        if let Some(chain) = self.get_chain_mut(chain_id) {
+            chain.endings.push(ChainEnding { position, reason });
+        }
+    }
+
+    /// Whether any chain has been started this turn
+    pub fn any_chain_started(&self) -> bool {
+        !self.chains.is_empty()
+    }
+
+    /// Reset for new turn, allowing `chains_per_turn` chain starts
+    pub fn reset_turn(&mut self, chains_per_turn: u32) {
         self.chains.clear();
         self.activation_queue.clear();
+        self.next_sequence = 0;
         self.current_chain = None;
-        self.chain_started_this_turn = false;
+        self.chains_remaining = chains_per_turn.max(1);
     }
 }
 
 pub(super) fn plugin(app: &mut App) {
+    // Paused with the game, queued activations keep their remaining delay until resumed
     app.init_resource::<ChainManager>().add_systems(
         FixedUpdate,
-        (process_activation_queue, update_mushroom_cooldowns).chain(),
+        (process_activation_queue, update_mushroom_cooldowns)
+            .chain()
+            .in_set(PausableSystems),
     );
+
+    app.init_resource::<ChainConfig>();
+    app.add_systems(OnEnter(LevelState::StartDialogue), apply_level_chain_config);
+
+    app.init_resource::<ChainSpeed>();
+    app.add_systems(Update, cycle_chain_speed.run_if(in_state(TurnPhase::Chain)));
+    app.add_systems(OnExit(TurnPhase::Chain), reset_chain_speed);
+}
+
+/// Tuning for how energy moves through a chain, set from each level's definition
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ChainConfig {
+    /// Multiplier on the energy a mushroom passes on, 1.0 for no decay
+    pub decay_per_hop: f32,
+    /// Depth cap for the level, copied onto [`ChainManager::max_chain_depth`] and used by
+    /// the chain simulation
+    pub max_chain_depth: u32,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            decay_per_hop: 1.0,
+            max_chain_depth: DEFAULT_MAX_CHAIN_DEPTH,
+        }
+    }
+}
+
+impl ChainConfig {
+    pub fn for_level(level: &LevelDefinition) -> Self {
+        Self {
+            decay_per_hop: level.energy_decay_per_hop,
+            ..default()
+        }
+    }
+
+    /// Energy each sprout of a spawner starts with
+    /// The spawner's energy decays like any other hop, then is shared between the sprouts
+    pub fn sprout_energy(&self, energy: f32, tile_modifier: f32, sprouts: usize) -> f32 {
+        energy * tile_modifier * self.decay_per_hop / sprouts.max(1) as f32
+    }
+}
+
+fn apply_level_chain_config(
+    mut config: ResMut<ChainConfig>,
+    mut chain_manager: ResMut<ChainManager>,
+    level_definitions: Res<LevelDefinitions>,
+    current_level: Res<CurrentLevel>,
+) {
+    *config = level_definitions
+        .get_level(current_level.level_index)
+        .map(ChainConfig::for_level)
+        .unwrap_or_default();
+    chain_manager.max_chain_depth = config.max_chain_depth;
+}
+
+const CHAIN_SPEED_KEY: KeyCode = KeyCode::Tab;
+
+/// How fast pending activations and cooldowns tick during the chain phase
+///
+/// Only the pacing changes, every activation still happens in the same order.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChainSpeed {
+    #[default]
+    Normal,
+    Double,
+    Quadruple,
+}
+
+impl ChainSpeed {
+    pub fn factor(&self) -> f32 {
+        match self {
+            ChainSpeed::Normal => 1.0,
+            ChainSpeed::Double => 2.0,
+            ChainSpeed::Quadruple => 4.0,
+        }
+    }
+
+    fn next(&self) -> ChainSpeed {
+        match self {
+            ChainSpeed::Normal => ChainSpeed::Double,
+            ChainSpeed::Double => ChainSpeed::Quadruple,
+            ChainSpeed::Quadruple => ChainSpeed::Normal,
+        }
+    }
+
+    /// Game time that passes for the chain this tick
+    fn delta(&self, time: &GameTime) -> Duration {
+        Duration::from_secs_f32(time.delta_seconds * self.factor())
+    }
+}
+
+/// Step through 1x, 2x and 4x chain speed
+fn cycle_chain_speed(keyboard: Res<ButtonInput<KeyCode>>, mut speed: ResMut<ChainSpeed>) {
+    if keyboard.just_pressed(CHAIN_SPEED_KEY) {
+        *speed = speed.next();
+        info!("Chain speed set to {}x", speed.factor());
+    }
+}
+
+fn reset_chain_speed(mut speed: ResMut<ChainSpeed>) {
+    *speed = ChainSpeed::Normal;
 }
 
 /// Process pending activations in the queue
@@ -183,7 +425,10 @@ fn process_activation_queue(
     mut turn_data: ResMut<TurnData>,
     mut current_level: ResMut<CurrentLevel>,
     time: Res<GameTime>,
+    speed: Res<ChainSpeed>,
+    config: Res<ChainConfig>,
     definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
     mut mushrooms: Query<(
         &Mushroom,
         &mut MushroomActivationState,
@@ -191,15 +436,17 @@ fn process_activation_queue(
         Option<&MushroomDirection>,
         &Transform,
     )>,
-    mut effects: ResMut<Assets<EffectAsset>>,
+    particle_effects: Res<ParticleEffects>,
+    mut effect_pool: ResMut<ParticleEffectPool>,
+    field_grounds: Query<Entity, With<FieldGround>>,
+    mut connection_builder: ResMut<ConnectionBuilder>,
 ) {
     // Update timers and collect ready activations
     let mut ready_activations = Vec::new();
 
+    let delta = speed.delta(&time);
     for activation in &mut chain_manager.activation_queue {
-        activation
-            .delay
-            .tick(Duration::from_secs_f32(time.delta_seconds));
+        activation.delay.tick(delta);
         if activation.delay.finished() {
             ready_activations.push(activation.clone());
         }
@@ -210,6 +457,9 @@ fn process_activation_queue(
         .activation_queue
         .retain(|a| !a.delay.finished());
 
+    // Activations ready on the same tick go in a fixed order, so seeded runs play out the same
+    ready_activations.sort_by_key(|a| (a.chain_id, a.sequence));
+
     // Process ready activations
     for activation in ready_activations {
         process_single_activation(
@@ -218,9 +468,14 @@ fn process_activation_queue(
             &mut game_state,
             &mut turn_data,
             &mut current_level,
+            &config,
             &definitions,
-            &mut effects,
+            &upgrades,
+            &particle_effects,
+            &mut effect_pool,
             &mut mushrooms,
+            &field_grounds,
+            &mut connection_builder,
             activation,
         );
     }
@@ -247,6 +502,14 @@ fn process_activation_queue(
                 "Chain {} complete! Total spores: {}",
                 chain_id, total_spores
             );
+            commands.trigger(ChainCompleteEvent);
+            for ending in &chain.endings {
+                info!(
+                    "  Branch ended at {:?}: {}",
+                    ending.position,
+                    ending.reason.description()
+                );
+            }
         }
     }
 }
@@ -258,8 +521,11 @@ fn process_single_activation(
     game_state: &mut GameState,
     turn_data: &mut TurnData,
     _current_level: &mut CurrentLevel,
+    config: &ChainConfig,
     definitions: &MushroomDefinitions,
-    effects: &mut ResMut<Assets<EffectAsset>>,
+    upgrades: &MushroomUpgrades,
+    particle_effects: &ParticleEffects,
+    effect_pool: &mut ParticleEffectPool,
     mushrooms: &mut Query<(
         &Mushroom,
         &mut MushroomActivationState,
@@ -267,6 +533,8 @@ fn process_single_activation(
         Option<&MushroomDirection>,
         &Transform,
     )>,
+    field_grounds: &Query<Entity, With<FieldGround>>,
+    connection_builder: &mut ConnectionBuilder,
     activation: PendingActivation,
 ) {
     let Ok((mushroom, mut state, position, direction, transform)) =
@@ -276,25 +544,46 @@ fn process_single_activation(
         return;
     };
 
-    let Some(definition) = definitions.get(mushroom.0) else {
-        warn!("No definition for mushroom type {:?}", mushroom.0);
-        return;
-    };
+    let definition = definitions.get_or_default(mushroom.0);
 
     // Check if mushroom can activate
-    if state.activations_this_turn >= definition.max_uses_per_turn {
+    if state.activations_this_turn >= upgrades.max_uses(mushroom.0, definition) {
         info!("Mushroom at {:?} reached max activations", position);
+        chain_manager.record_ending(activation.chain_id, *position, ChainEndReason::MaxUses);
         return;
     }
 
-    if state.cooldown_timer.is_some() {
-        info!("Mushroom at {:?} is on cooldown", position);
+    if let Some(cooldown) = &state.cooldown_timer {
+        // Wait for the cooldown instead of dropping the activation, unless it is too long
+        let wait = cooldown.remaining_secs();
+        if wait <= MAX_COOLDOWN_WAIT {
+            info!(
+                "Mushroom at {:?} is on cooldown, retrying in {:.2}s",
+                position, wait
+            );
+            chain_manager.queue_activation(
+                activation.entity,
+                activation.energy_packet,
+                wait,
+                activation.chain_id,
+            );
+        } else {
+            info!("Mushroom at {:?} is on cooldown", position);
+            chain_manager.record_ending(activation.chain_id, *position, ChainEndReason::OnCooldown);
+        }
         return;
     }
 
     // Activate the mushroom
     state.activations_this_turn += 1;
     state.last_activation_energy = activation.energy_packet.energy;
+    if let Some(&sender) = activation.energy_packet.path.last() {
+        game_state.play_field.record_edge_energy(
+            sender,
+            activation.entity,
+            activation.energy_packet.energy,
+        );
+    }
     state.cooldown_timer = Some(Timer::from_seconds(
         definition.cooldown_time,
         TimerMode::Once,
@@ -307,8 +596,9 @@ fn process_single_activation(
         .map(|t| t.production_multiplier())
         .unwrap_or(1.0);
 
-    let mut production =
-        definition.base_production * activation.energy_packet.energy as f64 * tile_modifier as f64;
+    let mut production = clamp_spores(
+        definition.base_production * activation.energy_packet.energy as f64 * tile_modifier as f64,
+    );
 
     // Apply behavior-specific modifications
     let should_propagate = match &definition.activation_behavior {
@@ -317,17 +607,12 @@ fn process_single_activation(
             let mut targets_to_delete = Vec::new();
 
             // Calculate target positions based on connection points
-            for connection_point in &definition.connection_points {
-                let target_pos = if let Some(dir) = direction {
-                    let rotated = rotate_connection_point(connection_point, dir);
-                    GridPosition::new(position.x + rotated.x, position.y + rotated.y)
-                } else {
-                    GridPosition::new(
-                        position.x + connection_point.x,
-                        position.y + connection_point.y,
-                    )
-                };
-
+            let target_positions = game_state.play_field.topology.targets(
+                *position,
+                definition,
+                direction.copied().unwrap_or_default(),
+            );
+            for target_pos in target_positions {
                 // Check if there's a mushroom at the target position
                 if let Some(target_entity) = game_state.play_field.get(target_pos) {
                     targets_to_delete.push((target_entity, target_pos));
@@ -338,19 +623,14 @@ fn process_single_activation(
             let mut deleted_count = 0;
             for (target_entity, target_pos) in targets_to_delete {
                 // Spawn delete effect at target position before deletion
-                let delete_effect = effects.add(crate::game::particles::assets::delete_effect());
                 let target_world_pos = target_pos.to_world_in(&game_state.play_field);
 
-                commands.spawn((
-                    Name::new("Delete Effect"),
-                    ParticleEffect::new(delete_effect),
-                    Transform::from_translation(Vec3::new(
-                        target_world_pos.x,
-                        0.7,
-                        -target_world_pos.z,
-                    )),
-                    DespawnTimer::new(1.0),
-                ));
+                effect_pool.spawn(
+                    commands,
+                    "Delete Effect",
+                    particle_effects.delete.clone(),
+                    Vec3::new(target_world_pos.x, 0.7, -target_world_pos.z),
+                );
 
                 // Delete the mushroom
                 commands.entity(target_entity).despawn();
@@ -364,7 +644,7 @@ fn process_single_activation(
 
             // Award bonus spores: base production * number of mushrooms destroyed
             if deleted_count > 0 {
-                production *= deleted_count as f64;
+                production = clamp_spores(production * deleted_count as f64);
                 info!(
                     "Deleter mushroom destroyed {} mushrooms, production multiplied to: {}",
                     deleted_count, production
@@ -380,41 +660,164 @@ fn process_single_activation(
             // Deleter does not propagate
             false
         }
+        ActivationBehavior::Unblocker {
+            clear_count,
+            bonus_per_tile,
+        } => {
+            let blocked_positions: Vec<GridPosition> = game_state
+                .play_field
+                .topology
+                .targets(
+                    *position,
+                    definition,
+                    direction.copied().unwrap_or_default(),
+                )
+                .into_iter()
+                .filter(|target| {
+                    game_state
+                        .play_field
+                        .get_tile(*target)
+                        .is_some_and(|tile| tile.can_be_cleared())
+                })
+                .take(*clear_count as usize)
+                .collect();
+
+            for target_pos in &blocked_positions {
+                game_state.play_field.set_tile(*target_pos, TileType::Empty);
+
+                let target_world_pos = target_pos.to_world_in(&game_state.play_field);
+                effect_pool.spawn(
+                    commands,
+                    "Tile Conversion Effect",
+                    particle_effects.tile_conversion.clone(),
+                    Vec3::new(target_world_pos.x, 0.1, -target_world_pos.z),
+                );
+                info!(
+                    "Unblocker mushroom at {:?} cleared tile at {:?}",
+                    position, target_pos
+                );
+            }
+
+            if blocked_positions.is_empty() {
+                info!(
+                    "Unblocker mushroom at {:?} found nothing to clear",
+                    position
+                );
+            } else {
+                production =
+                    clamp_spores(production + bonus_per_tile * blocked_positions.len() as f64);
+                for entity in field_grounds {
+                    commands.entity(entity).insert(TilesDirty);
+                }
+                // Mycelium can now grow through the cleared cells
+                connection_builder.dirty = true;
+            }
+
+            // Unblocker does not propagate, the cleared tiles have no mushrooms yet
+            false
+        }
+        ActivationBehavior::Spawner { spawn_count } => {
+            let field = &game_state.play_field;
+            let spawn_positions = sprout_positions(field, *position, *spawn_count, |target| {
+                field.get(target).is_none()
+                    && field
+                        .get_tile(target)
+                        .is_some_and(|tile| tile.allows_mushroom())
+            });
+
+            if spawn_positions.is_empty() {
+                info!("Spawner at {:?} has no room to sprout", position);
+            }
+
+            // Reserve the entities now so the sprouts can be queued in this chain
+            let mut sprout_packet = activation.energy_packet.clone();
+            sprout_packet.path.push(activation.entity);
+            sprout_packet.depth += 1;
+            sprout_packet.energy =
+                config.sprout_energy(sprout_packet.energy, tile_modifier, spawn_positions.len());
+            for (i, target_pos) in spawn_positions.into_iter().enumerate() {
+                let sprout = commands.spawn(TemporaryMushroom).id();
+                game_state.play_field.insert(target_pos, sprout);
+                commands.trigger(SpawnMushroomEvent {
+                    position: target_pos,
+                    mushroom_type: MushroomType::Basic,
+                    direction: None,
+                    entity: Some(sprout),
+                    source: SpawnSource::Sprouted,
+                });
+
+                let mut packet = sprout_packet.clone();
+                packet.source_entity = sprout;
+                chain_manager.queue_activation(
+                    sprout,
+                    packet,
+                    0.2 + i as f32 * 0.05,
+                    activation.chain_id,
+                );
+                info!("Spawner at {:?} sprouted at {:?}", position, target_pos);
+            }
+
+            true
+        }
+        ActivationBehavior::Networked {
+            bonus_per_connection,
+        } => {
+            let connections = game_state.play_field.get_all_connections().len();
+            production = networked_production(production, *bonus_per_connection, connections);
+            info!(
+                "Hub mushroom at {:?} counted {} connections, production: {}",
+                position, connections, production
+            );
+            true
+        }
+        ActivationBehavior::Capacitor { per_depth } => {
+            let depth = activation.energy_packet.path.len();
+            production = capacitor_production(definition, *per_depth, depth, tile_modifier);
+            info!(
+                "Capacitor mushroom at {:?} reached at depth {}, production: {}",
+                position, depth, production
+            );
+            true
+        }
         _ => true, // Other behaviors propagate normally
     };
 
     // Add spores
     game_state.add_spores(production);
-    turn_data.spores_this_chain += production;
+    turn_data.spores_this_chain = clamp_spores(turn_data.spores_this_chain + production);
     game_state.total_activations += 1;
 
     // Spawn spore popup
     commands.trigger(SporeScoreEvent {
         position: *position,
         production,
+        depth: activation.energy_packet.depth,
+    });
+    commands.trigger(ActivationEnergyEvent {
+        position: *position,
+        energy: activation.energy_packet.energy,
     });
 
     //Spawn particle effect
-    let activate_effect = effects.add(activate_effect());
-
     let world_pos = position.to_world_in(&game_state.play_field);
 
-    commands.spawn((
-        Name::new("Spore Effect"),
-        ParticleEffect::new(activate_effect),
-        Transform::from_translation(Vec3::new(world_pos.x, 0.7, -world_pos.z)),
-        DespawnTimer::new(1.0),
-    ));
+    effect_pool.spawn(
+        commands,
+        "Spore Effect",
+        particle_effects.activate_for(mushroom.0),
+        Vec3::new(world_pos.x, 0.7, -world_pos.z),
+    );
 
     // Update chain
     if let Some(chain) = chain_manager.get_chain_mut(activation.chain_id) {
-        chain.total_spores += production;
+        chain.total_spores = clamp_spores(chain.total_spores + production);
         chain.activations.push(ChainActivation {
             entity: activation.entity,
             position: *position,
             energy: activation.energy_packet.energy,
-            depth: activation.energy_packet.path.len() as u32,
+            depth: activation.energy_packet.depth,
             parent: Some(activation.energy_packet.source_entity),
+            production,
         });
     }
 
@@ -446,10 +849,43 @@ fn process_single_activation(
             modified_energy_packet,
             activation.chain_id,
             game_state,
+            config,
         );
     }
 }
 
+/// Cells a spawner at `position` sprouts into, the first `spawn_count` free neighbours
+/// `is_free` tells whether a cell on the field can take a mushroom right now
+pub fn sprout_positions(
+    field: &PlayField,
+    position: GridPosition,
+    spawn_count: u32,
+    is_free: impl Fn(GridPosition) -> bool,
+) -> Vec<GridPosition> {
+    field
+        .topology
+        .neighbours(position)
+        .into_iter()
+        .filter(|target| field.contains(*target) && is_free(*target))
+        .take(spawn_count as usize)
+        .collect()
+}
+
+/// Production of a networked mushroom with `connections` on the field
+pub fn networked_production(production: f64, bonus_per_connection: f64, connections: usize) -> f64 {
+    clamp_spores(production * (1.0 + bonus_per_connection * connections as f64))
+}
+
+/// Production of a capacitor reached after `depth` mushrooms, ignoring its energy
+pub fn capacitor_production(
+    definition: &MushroomDefinition,
+    per_depth: f64,
+    depth: usize,
+    tile_modifier: f32,
+) -> f64 {
+    clamp_spores((definition.base_production + per_depth * depth as f64) * tile_modifier as f64)
+}
+
 /// Process energy propagation based on mushroom behavior
 fn process_propagation(
     chain_manager: &mut ChainManager,
@@ -461,12 +897,11 @@ fn process_propagation(
     mut energy_packet: EnergyPacket,
     chain_id: u32,
     game_state: &GameState,
+    config: &ChainConfig,
 ) {
     // Add this mushroom to the path
     energy_packet.path.push(source_entity);
-
-    // Get connection points from definition
-    let connection_points = &definition.connection_points;
+    energy_packet.depth += 1;
 
     // Process based on behavior type (for special modifications)
     match behavior {
@@ -476,16 +911,19 @@ fn process_propagation(
 
         ActivationBehavior::Amplifier { boost_factor } => {
             // Boost energy before propagating
-            energy_packet.energy *= boost_factor;
+            energy_packet.energy = (energy_packet.energy * boost_factor).min(MAX_ENERGY);
         }
         _ => {}
     }
 
+    // Long chains taper off when the level has decay
+    energy_packet.energy *= config.decay_per_hop;
+
     // Perform propagation to connection points
     propagate_to_connection_points(
         chain_manager,
         source_pos,
-        connection_points,
+        definition,
         direction,
         energy_packet,
         chain_id,
@@ -497,43 +935,52 @@ fn process_propagation(
 fn propagate_to_connection_points(
     chain_manager: &mut ChainManager,
     source_pos: GridPosition,
-    connection_points: &[GridOffset],
+    definition: &MushroomDefinition,
     direction: Option<MushroomDirection>,
     energy_packet: EnergyPacket,
     chain_id: u32,
     game_state: &GameState,
 ) {
-    if connection_points.is_empty() {
+    if definition.connection_points.is_empty() {
+        chain_manager.record_ending(chain_id, source_pos, ChainEndReason::NoConnectionTarget);
         return;
     }
 
     // Calculate actual target positions
     let mut targets = Vec::new();
+    let mut any_in_bounds = false;
 
-    for point in connection_points {
-        let target_pos = if let Some(dir) = direction {
-            // Rotate connection point based on facing
-            let rotated = rotate_connection_point(point, &dir);
-            GridPosition::new(source_pos.x + rotated.x, source_pos.y + rotated.y)
-        } else {
-            // No direction, use as-is
-            GridPosition::new(source_pos.x + point.x, source_pos.y + point.y)
-        };
-
+    // No direction uses the connection points as-is
+    let target_positions = game_state.play_field.topology.targets(
+        source_pos,
+        definition,
+        direction.unwrap_or_default(),
+    );
+    for target_pos in target_positions {
+        any_in_bounds |= game_state.play_field.contains(target_pos);
         if let Some(entity) = game_state.play_field.get(target_pos) {
             targets.push((entity, target_pos));
         }
     }
 
     if targets.is_empty() {
+        let reason = if any_in_bounds {
+            ChainEndReason::NoConnectionTarget
+        } else {
+            ChainEndReason::OutOfBounds
+        };
+        chain_manager.record_ending(chain_id, source_pos, reason);
         return;
     }
 
-    // Split energy among targets if there are multiple
-    let split_energy = if targets.len() > 1 {
-        energy_packet.energy / targets.len() as f32
-    } else {
-        energy_packet.energy
+    // Split energy among targets if there are multiple, splitters copy it instead
+    let split_energy = match definition.activation_behavior {
+        ActivationBehavior::Splitter { copies } => {
+            targets.truncate(copies as usize);
+            energy_packet.energy
+        }
+        _ if targets.len() > 1 => energy_packet.energy / targets.len() as f32,
+        _ => energy_packet.energy,
     };
 
     for (i, (target_entity, _)) in targets.into_iter().enumerate() {
@@ -546,36 +993,17 @@ fn propagate_to_connection_points(
     }
 }
 
-/// Rotate a connection point based on mushroom direction
-fn rotate_connection_point(point: &GridOffset, direction: &MushroomDirection) -> GridOffset {
-    match direction {
-        MushroomDirection::Up => {
-            // No rotation needed, this is the default
-            GridOffset::new(point.x, point.y)
-        }
-        MushroomDirection::Right => {
-            // Rotate 90 degrees clockwise: (x, y) -> (y, -x)
-            GridOffset::new(point.y, -point.x)
-        }
-        MushroomDirection::Down => {
-            // Rotate 180 degrees: (x, y) -> (-x, -y)
-            GridOffset::new(-point.x, -point.y)
-        }
-        MushroomDirection::Left => {
-            // Rotate 270 degrees clockwise: (x, y) -> (-y, x)
-            GridOffset::new(-point.y, point.x)
-        }
-    }
-}
-
 /// Update mushroom cooldowns
 fn update_mushroom_cooldowns(
     time: Res<GameTime>,
+    speed: Res<ChainSpeed>,
     mut mushrooms: Query<&mut MushroomActivationState>,
 ) {
+    // Cooldowns speed up with the activations, so the same ones are still blocked
+    let delta = speed.delta(&time);
     for mut state in mushrooms.iter_mut() {
         if let Some(ref mut timer) = state.cooldown_timer {
-            timer.tick(Duration::from_secs_f32(time.delta_seconds));
+            timer.tick(delta);
             if timer.finished() {
                 state.cooldown_timer = None;
             }
@@ -587,12 +1015,547 @@ fn update_mushroom_cooldowns(
 pub fn reset_mushroom_states(
     mut mushrooms: Query<&mut MushroomActivationState>,
     mut chain_manager: ResMut<ChainManager>,
+    mut game_state: ResMut<GameState>,
+    current_level: Res<CurrentLevel>,
 ) {
     for mut state in mushrooms.iter_mut() {
         state.activations_this_turn = 0;
         state.last_activation_energy = 0.0;
         state.cooldown_timer = None; // Reset cooldowns
     }
+    game_state.play_field.reset_edge_energy();
+
+    chain_manager.reset_turn(current_level.max_chains_per_turn);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    /// Seconds of game time that pass each test tick
+    const TICK: f32 = 0.05;
+
+    /// World with everything the activation queue reads, and the mushrooms placed on `field`
+    fn chain_world(
+        field: PlayField,
+        mushrooms: &[(i32, i32, MushroomType, MushroomDirection)],
+    ) -> (World, Vec<Entity>) {
+        let mut world = World::new();
+        let mut game_state = GameState {
+            play_field: field,
+            ..default()
+        };
+        let entities = mushrooms
+            .iter()
+            .map(|(x, y, mushroom_type, direction)| {
+                let position = GridPosition::new(*x, *y);
+                let entity = world
+                    .spawn((
+                        Mushroom(*mushroom_type),
+                        position,
+                        *direction,
+                        Transform::default(),
+                    ))
+                    .id();
+                game_state.play_field.insert(position, entity);
+                entity
+            })
+            .collect();
+
+        world.insert_resource(game_state);
+        world.insert_resource(ChainManager::default());
+        world.insert_resource(TurnData::default());
+        world.insert_resource(CurrentLevel::default());
+        world.insert_resource(GameTime {
+            delta_seconds: TICK,
+        });
+        world.insert_resource(ChainSpeed::default());
+        world.insert_resource(ChainConfig::default());
+        world.insert_resource(MushroomDefinitions::standard());
+        world.insert_resource(MushroomUpgrades::default());
+        world.insert_resource(ParticleEffects {
+            activate: default(),
+            activate_by_type: default(),
+            delete: default(),
+            tile_conversion: default(),
+            ambient_spores: default(),
+        });
+        let mut effect_pool = ParticleEffectPool::default();
+        effect_pool.max_active = 0;
+        world.insert_resource(effect_pool);
+        world.init_resource::<ConnectionBuilder>();
+        (world, entities)
+    }
+
+    /// Start a chain from `starter` and step the chain systems for `ticks` ticks
+    fn run_chain(world: &mut World, starter: Entity, ticks: usize) {
+        let position = *world.get::<GridPosition>(starter).unwrap();
+        // Picked up from the config as when a level starts
+        let max_chain_depth = world.resource::<ChainConfig>().max_chain_depth;
+        let mut chain_manager = world.resource_mut::<ChainManager>();
+        chain_manager.max_chain_depth = max_chain_depth;
+        chain_manager.start_chain(starter, position);
+        for _ in 0..ticks {
+            world.run_system_once(process_activation_queue).unwrap();
+            world.run_system_once(update_mushroom_cooldowns).unwrap();
+        }
+    }
+
+    fn uses(world: &World, entity: Entity) -> u32 {
+        world
+            .get::<MushroomActivationState>(entity)
+            .unwrap()
+            .activations_this_turn
+    }
+
+    #[test]
+    fn spawner_sprouts_into_free_neighbours() {
+        let mut field = PlayField::new(3, 3);
+        let spawner = GridPosition::new(1, 1);
+        field.set_tile(GridPosition::new(0, 1), TileType::BlockedRock);
+        let taken = GridPosition::new(2, 1);
+
+        let free = |cell: GridPosition| {
+            cell != taken
+                && field
+                    .get_tile(cell)
+                    .is_some_and(|tile| tile.allows_mushroom())
+        };
+        let sprouts = sprout_positions(&field, spawner, 2, free);
+
+        assert_eq!(
+            sprouts,
+            vec![GridPosition::new(1, 0), GridPosition::new(1, 2)]
+        );
+    }
+
+    #[test]
+    fn spawner_on_a_full_board_sprouts_nothing() {
+        let field = PlayField::new(3, 3);
+
+        // Neighbours past the edge are never picked
+        let corner = sprout_positions(&field, GridPosition::new(0, 0), 4, |_| true);
+        assert_eq!(corner.len(), 2);
+
+        let full = sprout_positions(&field, GridPosition::new(1, 1), 2, |_| false);
+        assert!(full.is_empty());
+    }
+
+    #[test]
+    fn activation_waits_for_a_cooling_down_target() {
+        // The pair bounces energy back before the first one's 0.5s cooldown is up
+        let (mut world, entities) = chain_world(
+            PlayField::new(2, 1),
+            &[
+                (0, 0, MushroomType::Sideways, MushroomDirection::Up),
+                (1, 0, MushroomType::Sideways, MushroomDirection::Up),
+            ],
+        );
+
+        run_chain(&mut world, entities[0], 40);
+
+        let chain_manager = world.resource::<ChainManager>();
+        assert!(!chain_manager.has_active_chains());
+        assert!(
+            !chain_manager.chains[0]
+                .endings
+                .iter()
+                .any(|ending| matches!(ending.reason, ChainEndReason::OnCooldown))
+        );
+        // Both mushrooms use up every activation they have
+        assert_eq!(uses(&world, entities[0]), 3);
+        assert_eq!(uses(&world, entities[1]), 3);
+    }
+
+    #[test]
+    fn splitter_gives_each_target_the_full_energy() {
+        let (mut world, entities) = chain_world(
+            PlayField::new(3, 3),
+            &[
+                (1, 1, MushroomType::Splitter, MushroomDirection::Up),
+                (1, 2, MushroomType::Basic, MushroomDirection::Up),
+                (2, 1, MushroomType::Basic, MushroomDirection::Up),
+                (0, 1, MushroomType::Basic, MushroomDirection::Up),
+            ],
+        );
+
+        run_chain(&mut world, entities[0], 10);
+
+        for basic in &entities[1..] {
+            let state = world.get::<MushroomActivationState>(*basic).unwrap();
+            assert_eq!(state.activations_this_turn, 1);
+            assert_eq!(state.last_activation_energy, 1.0);
+        }
+    }
+
+    #[test]
+    fn simultaneous_activations_run_in_chain_then_queue_order() {
+        #[derive(Resource, Default)]
+        struct ScoredOrder(Vec<GridPosition>);
+
+        let (mut world, entities) = chain_world(
+            PlayField::new(3, 1),
+            &[
+                (0, 0, MushroomType::Basic, MushroomDirection::Up),
+                (1, 0, MushroomType::Basic, MushroomDirection::Up),
+                (2, 0, MushroomType::Basic, MushroomDirection::Up),
+            ],
+        );
+        world.init_resource::<ScoredOrder>();
+        world.add_observer(
+            |trigger: Trigger<SporeScoreEvent>, mut order: ResMut<ScoredOrder>| {
+                order.0.push(trigger.position);
+            },
+        );
+
+        let mut chain_manager = world.resource_mut::<ChainManager>();
+        chain_manager.chains_remaining = 2;
+        let first = chain_manager
+            .start_chain(entities[0], GridPosition::new(0, 0))
+            .unwrap();
+        chain_manager.start_chain(entities[1], GridPosition::new(1, 0));
+        let packet = EnergyPacket {
+            energy: 1.0,
+            source_entity: entities[2],
+            path: vec![],
+            depth: 0,
+        };
+        chain_manager.queue_activation(entities[2], packet, 0.0, first);
+        // Ready on the same tick, queued back to front
+        chain_manager.activation_queue.make_contiguous().reverse();
+
+        world.run_system_once(process_activation_queue).unwrap();
+
+        assert_eq!(
+            world.resource::<ScoredOrder>().0,
+            vec![
+                GridPosition::new(0, 0),
+                GridPosition::new(2, 0),
+                GridPosition::new(1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn loop_of_four_stops_at_the_depth_cap() {
+        // Four pulses passing energy round a square
+        let (mut world, entities) = chain_world(
+            PlayField::new(2, 2),
+            &[
+                (0, 0, MushroomType::Pulse, MushroomDirection::Up),
+                (0, 1, MushroomType::Pulse, MushroomDirection::Right),
+                (1, 1, MushroomType::Pulse, MushroomDirection::Down),
+                (1, 0, MushroomType::Pulse, MushroomDirection::Left),
+            ],
+        );
+        // Plenty of uses, so only the cap can end the loop
+        world
+            .resource_mut::<MushroomUpgrades>()
+            .load(&[(MushroomType::Pulse, 20)]);
+        world.insert_resource(ChainConfig {
+            max_chain_depth: 5,
+            ..default()
+        });
+
+        run_chain(&mut world, entities[0], 60);
+
+        let chain_manager = world.resource::<ChainManager>();
+        assert!(!chain_manager.has_active_chains());
+        let depths: Vec<u32> = chain_manager.chains[0]
+            .activations
+            .iter()
+            .map(|activation| activation.depth)
+            .collect();
+        assert_eq!(depths, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn packets_past_the_depth_cap_are_never_queued() {
+        let mut chain_manager = ChainManager {
+            max_chain_depth: 2,
+            ..default()
+        };
+
+        for depth in 0..5 {
+            let packet = EnergyPacket {
+                energy: 1.0,
+                source_entity: Entity::PLACEHOLDER,
+                path: vec![],
+                depth,
+            };
+            chain_manager.queue_activation(Entity::PLACEHOLDER, packet, 0.2, 0);
+        }
+
+        let depths: Vec<u32> = chain_manager
+            .activation_queue
+            .iter()
+            .map(|pending| pending.energy_packet.depth)
+            .collect();
+        assert_eq!(depths, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn third_chain_is_rejected_with_two_per_turn() {
+        let mut chain_manager = ChainManager::default();
+        chain_manager.reset_turn(2);
+        assert_eq!(chain_manager.chains_remaining, 2);
+
+        let (starter, position) = (Entity::PLACEHOLDER, GridPosition::new(0, 0));
+        assert!(chain_manager.start_chain(starter, position).is_some());
+        assert!(chain_manager.start_chain(starter, position).is_some());
+        assert_eq!(chain_manager.start_chain(starter, position), None);
+        assert_eq!(chain_manager.chains.len(), 2);
+        assert_eq!(chain_manager.chains_remaining, 0);
+    }
+
+    #[test]
+    fn unblocker_clears_one_adjacent_rock() {
+        let mut field = PlayField::new(3, 3);
+        let (north, west) = (GridPosition::new(1, 2), GridPosition::new(0, 1));
+        field.set_tile(north, TileType::BlockedRock);
+        field.set_tile(west, TileType::BlockedRock);
+        let (mut world, entities) = chain_world(
+            field,
+            &[(1, 1, MushroomType::Unblocker, MushroomDirection::Up)],
+        );
+
+        run_chain(&mut world, entities[0], 1);
+
+        // Cardinal targets are checked north first, and only one rock is cleared
+        let play_field = &world.resource::<GameState>().play_field;
+        assert_eq!(play_field.get_tile(north), Some(TileType::Empty));
+        assert_eq!(play_field.get_tile(west), Some(TileType::BlockedRock));
+        // 8 spores of its own and 15 for the cleared rock
+        let chain_manager = world.resource::<ChainManager>();
+        assert_eq!(chain_manager.chains[0].total_spores, 23.0);
+    }
+
+    #[test]
+    fn unblocker_leaves_water_and_clears_moss() {
+        let mut field = PlayField::new(3, 3);
+        let (north, west) = (GridPosition::new(1, 2), GridPosition::new(0, 1));
+        field.set_tile(north, TileType::BlockedWater);
+        field.set_tile(west, TileType::BlockedMoss);
+        let (mut world, entities) = chain_world(
+            field,
+            &[(1, 1, MushroomType::Unblocker, MushroomDirection::Up)],
+        );
+
+        run_chain(&mut world, entities[0], 1);
+
+        let play_field = &world.resource::<GameState>().play_field;
+        assert_eq!(play_field.get_tile(north), Some(TileType::BlockedWater));
+        assert_eq!(play_field.get_tile(west), Some(TileType::Empty));
+        // The freed cell can take part in connections from the next frame
+        assert!(world.resource::<ConnectionBuilder>().dirty);
+    }
 
-    chain_manager.reset_turn();
+    #[test]
+    fn hub_scales_with_the_connections_on_the_field() {
+        let (mut world, entities) = chain_world(
+            PlayField::new(3, 3),
+            &[
+                (1, 1, MushroomType::Hub, MushroomDirection::Up),
+                (1, 2, MushroomType::Basic, MushroomDirection::Up),
+            ],
+        );
+        world.resource_mut::<GameState>().play_field.add_connection(
+            GridPosition::new(1, 1),
+            GridPosition::new(1, 2),
+            entities[0],
+            entities[1],
+            1.0,
+            vec![],
+        );
+
+        run_chain(&mut world, entities[0], 10);
+
+        // Hub makes 2 * (1 + 0.1 per connection), the Basic it feeds makes 10,
+        // the same as the dry run gives for this board
+        let total = world.resource::<ChainManager>().chains[0].total_spores;
+        assert!((total - 12.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn capacitor_at_depth_five_scores_by_chain_length() {
+        // Five pulses in a column lead up to the capacitor
+        let mut mushrooms: Vec<_> = (0..5)
+            .map(|y| (0, y, MushroomType::Pulse, MushroomDirection::Up))
+            .collect();
+        mushrooms.push((0, 5, MushroomType::Capacitor, MushroomDirection::Up));
+        let (mut world, entities) = chain_world(PlayField::new(1, 6), &mushrooms);
+
+        run_chain(&mut world, entities[0], 40);
+
+        let chain_manager = world.resource::<ChainManager>();
+        let capacitor = chain_manager.chains[0]
+            .activations
+            .iter()
+            .find(|activation| activation.entity == entities[5])
+            .unwrap();
+        assert_eq!(capacitor.depth, 5);
+        // 2 spores base and 3 for each of the five mushrooms before it
+        assert_eq!(capacitor.production, 17.0);
+    }
+
+    #[test]
+    fn energy_halves_on_each_of_three_hops() {
+        let (mut world, entities) = chain_world(
+            PlayField::new(1, 4),
+            &[
+                (0, 0, MushroomType::Pulse, MushroomDirection::Up),
+                (0, 1, MushroomType::Pulse, MushroomDirection::Up),
+                (0, 2, MushroomType::Pulse, MushroomDirection::Up),
+                (0, 3, MushroomType::Basic, MushroomDirection::Up),
+            ],
+        );
+        world.insert_resource(ChainConfig {
+            decay_per_hop: 0.5,
+            ..default()
+        });
+
+        run_chain(&mut world, entities[0], 40);
+
+        let chain_manager = world.resource::<ChainManager>();
+        let energies: Vec<(u32, f32)> = entities
+            .iter()
+            .map(|entity| {
+                let activation = chain_manager.chains[0]
+                    .activations
+                    .iter()
+                    .find(|activation| activation.entity == *entity)
+                    .unwrap();
+                (activation.depth, activation.energy)
+            })
+            .collect();
+        assert_eq!(energies, [(0, 1.0), (1, 0.5), (2, 0.25), (3, 0.125)]);
+    }
+
+    #[test]
+    fn amplifiers_feeding_each_other_stop_at_the_energy_cap() {
+        let (mut world, entities) = chain_world(
+            PlayField::new(2, 1),
+            &[
+                (0, 0, MushroomType::Sideways, MushroomDirection::Up),
+                (1, 0, MushroomType::Sideways, MushroomDirection::Up),
+            ],
+        );
+        // Enough uses for the pair to double the energy past the cap
+        world
+            .resource_mut::<MushroomUpgrades>()
+            .load(&[(MushroomType::Sideways, 30)]);
+        world.insert_resource(ChainConfig {
+            max_chain_depth: 30,
+            ..default()
+        });
+
+        run_chain(&mut world, entities[0], 400);
+
+        let chain = &world.resource::<ChainManager>().chains[0];
+        let deepest = chain.activations.iter().map(|a| a.depth).max();
+        let strongest = chain
+            .activations
+            .iter()
+            .map(|a| a.energy)
+            .fold(0.0, f32::max);
+        assert_eq!(deepest, Some(30));
+        assert_eq!(strongest, MAX_ENERGY);
+        assert!(chain.total_spores.is_finite());
+        assert!(chain.total_spores <= 31.0 * 2.0 * MAX_ENERGY as f64);
+    }
+
+    #[test]
+    fn simulation_scores_a_board_the_same_as_the_chain() {
+        use crate::game::mushrooms::simulation::{SimulatedBoard, simulate_chain};
+
+        let placed = [
+            (1, 0, MushroomType::Pulse, MushroomDirection::Up),
+            (1, 1, MushroomType::Spawner, MushroomDirection::Up),
+            (0, 2, MushroomType::Basic, MushroomDirection::Up),
+        ];
+        let (mut world, entities) = chain_world(PlayField::new(3, 3), &placed);
+        let config = ChainConfig {
+            decay_per_hop: 0.5,
+            max_chain_depth: 3,
+        };
+        world.insert_resource(config);
+        // Stand-in for the mushroom spawning observer, so sprouts can activate
+        world.add_observer(
+            |trigger: Trigger<SpawnMushroomEvent>, mut commands: Commands| {
+                if let Some(entity) = trigger.entity {
+                    commands.entity(entity).insert((
+                        Mushroom(trigger.mushroom_type),
+                        trigger.position,
+                        trigger.direction.unwrap_or_default(),
+                        Transform::default(),
+                    ));
+                }
+            },
+        );
+
+        run_chain(&mut world, entities[0], 60);
+
+        let board: SimulatedBoard = placed
+            .iter()
+            .map(|(x, y, mushroom_type, direction)| {
+                (GridPosition::new(*x, *y), (*mushroom_type, *direction))
+            })
+            .collect();
+        let simulated = simulate_chain(
+            world.resource::<MushroomDefinitions>(),
+            world.resource::<MushroomUpgrades>(),
+            &config,
+            &PlayField::new(3, 3),
+            &board,
+            GridPosition::new(1, 0),
+        );
+        let chain = &world.resource::<ChainManager>().chains[0];
+        // The sprouts took part too
+        assert!(chain.activations.len() > placed.len());
+        assert!((chain.total_spores - simulated).abs() < 1e-9);
+    }
+
+    #[test]
+    fn estimate_after_an_earlier_chain_matches_the_second_chain() {
+        use crate::game::mushrooms::simulation::{SimulatedBoard, simulate_chain_after};
+        use bevy::platform::collections::HashMap;
+
+        let placed = [
+            (0, 0, MushroomType::Pulse, MushroomDirection::Up),
+            (0, 1, MushroomType::Pulse, MushroomDirection::Up),
+            (0, 2, MushroomType::Basic, MushroomDirection::Up),
+        ];
+        let (mut world, entities) = chain_world(PlayField::new(1, 3), &placed);
+        world.resource_mut::<ChainManager>().chains_remaining = 2;
+        run_chain(&mut world, entities[0], 40);
+
+        // Uses spent by the first chain, as the hover estimate reads them
+        let uses_so_far: HashMap<GridPosition, u32> = placed
+            .iter()
+            .zip(&entities)
+            .map(|((x, y, ..), entity)| (GridPosition::new(*x, *y), uses(&world, *entity)))
+            .collect();
+        let board: SimulatedBoard = placed
+            .iter()
+            .map(|(x, y, mushroom_type, direction)| {
+                (GridPosition::new(*x, *y), (*mushroom_type, *direction))
+            })
+            .collect();
+        let estimate = simulate_chain_after(
+            world.resource::<MushroomDefinitions>(),
+            world.resource::<MushroomUpgrades>(),
+            world.resource::<ChainConfig>(),
+            &PlayField::new(1, 3),
+            &board,
+            GridPosition::new(0, 1),
+            &uses_so_far,
+        );
+
+        run_chain(&mut world, entities[1], 40);
+
+        let second = &world.resource::<ChainManager>().chains[1];
+        assert!(estimate > 0.0);
+        assert!((second.total_spores - estimate).abs() < 1e-9);
+    }
 }
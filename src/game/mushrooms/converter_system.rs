@@ -1,13 +1,12 @@
 //! End-of-turn converter system for mushrooms
 
 use bevy::prelude::*;
-use bevy_hanabi::{EffectAsset, ParticleEffect};
-use rand::{prelude::*, rng};
+use rand::prelude::*;
 
 use crate::game::{
-    DespawnTimer,
-    game_flow::{LevelState, TurnPhase},
+    game_flow::{CurrentLevel, LevelState, TurnData, TurnPhase, practice::PracticeMode},
     mushrooms::{Mushroom, MushroomActivationState, MushroomDefinitions},
+    particles::{ParticleEffectPool, ParticleEffects},
     play_field::{
         GridPosition, TileType,
         field_renderer::{FieldGround, TilesDirty},
@@ -17,6 +16,9 @@ use crate::game::{
 
 use super::definitions::ActivationBehavior;
 
+/// Stream of the level's random numbers used for picking converted tiles
+const CONVERSION_RNG_STREAM: u64 = 4;
+
 pub(super) fn plugin(app: &mut App) {
     // Process conversions when entering the Score phase (end of turn)
     app.add_systems(
@@ -32,12 +34,28 @@ fn process_end_of_turn_conversions(
     mushrooms: Query<(&Mushroom, &GridPosition, &MushroomActivationState)>,
     definitions: Res<MushroomDefinitions>,
     field_ground_query: Query<Entity, With<FieldGround>>,
-    mut effects: ResMut<Assets<EffectAsset>>,
+    particle_effects: Res<ParticleEffects>,
+    mut effect_pool: ResMut<ParticleEffectPool>,
+    current_level: Res<CurrentLevel>,
+    turn_data: Res<TurnData>,
+    practice: Res<PracticeMode>,
 ) {
+    // Practice runs put the board back afterwards, their conversions never happen
+    if practice.active {
+        return;
+    }
+
+    // Each turn has its own stream of the level's seed, and converters pick in board
+    // order, so a retry with the same plays converts the same tiles
+    let stream = CONVERSION_RNG_STREAM ^ (u64::from(turn_data.current_turn) << 32);
+    let mut rng = current_level.rng(stream);
+    let mut converters: Vec<_> = mushrooms.iter().collect();
+    converters.sort_by_key(|(_, position, _)| (position.y, position.x));
+
     let mut conversions_to_apply = Vec::new();
 
     // First, collect all conversions to apply
-    for (mushroom, position, state) in mushrooms.iter() {
+    for (mushroom, position, state) in converters {
         // Skip if mushroom didn't activate this turn
         if state.activations_this_turn == 0 {
             continue;
@@ -65,7 +83,6 @@ fn process_end_of_turn_conversions(
             );
 
             // Select random tiles up to convert_count
-            let mut rng = rng();
             let tiles_to_convert: Vec<_> = convertible_tiles
                 .choose_multiple(&mut rng, *convert_count as usize)
                 .cloned()
@@ -89,19 +106,17 @@ fn process_end_of_turn_conversions(
 
             // Spawn conversion effect at the tile position
             let world_pos = pos.to_world_in(&game_state.play_field);
-            let conversion_effect =
-                effects.add(crate::game::particles::assets::tile_conversion_effect());
 
-            commands.spawn((
-                Name::new("Tile Conversion Effect"),
-                ParticleEffect::new(conversion_effect),
-                Transform::from_translation(Vec3::new(
+            effect_pool.spawn(
+                &mut commands,
+                "Tile Conversion Effect",
+                particle_effects.tile_conversion.clone(),
+                Vec3::new(
                     world_pos.x,
                     0.1, // Just above ground level
                     -world_pos.z,
-                )),
-                DespawnTimer::new(1.0),
-            ));
+                ),
+            );
         }
 
         // Mark the field ground as needing texture update
@@ -1,9 +1,11 @@
 //! Mmushroom definitions
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::game::play_field::TileType;
+use super::MushroomDirection;
+use crate::game::{play_field::TileType, resources::format_spores};
 
 /// Relative position offset for connections
 #[derive(Debug, Clone, Copy)]
@@ -16,6 +18,23 @@ impl GridOffset {
     pub const fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
+
+    /// This offset for a mushroom facing `direction` on a square grid, offsets are written facing up
+    ///
+    /// Most code should go through the field's
+    /// [`GridTopology`](crate::game::play_field::topology::GridTopology) instead.
+    pub fn rotated(self, direction: MushroomDirection) -> GridOffset {
+        match direction {
+            // No rotation needed, this is the default
+            MushroomDirection::Up => self,
+            // Rotate 90 degrees clockwise: (x, y) -> (y, -x)
+            MushroomDirection::Right => GridOffset::new(self.y, -self.x),
+            // Rotate 180 degrees: (x, y) -> (-x, -y)
+            MushroomDirection::Down => GridOffset::new(-self.x, -self.y),
+            // Rotate 270 degrees clockwise: (x, y) -> (-y, x)
+            MushroomDirection::Left => GridOffset::new(-self.y, self.x),
+        }
+    }
 }
 
 /// Common connection patterns
@@ -23,7 +42,7 @@ pub mod connection_patterns {
     use super::GridOffset;
 
     /// Cardinal directions (N, E, S, W)
-    pub const _CARDINAL: &[GridOffset] = &[
+    pub const CARDINAL: &[GridOffset] = &[
         GridOffset::new(0, 1),  // North
         GridOffset::new(1, 0),  // East
         GridOffset::new(0, -1), // South
@@ -108,12 +127,104 @@ pub struct MushroomDefinition {
     pub sprite_row: usize,
     /// Activation behavior - systems perform action based on this
     pub activation_behavior: ActivationBehavior,
+    /// Categories for synergies and filters, every definition has at least one
+    pub tags: Vec<MushroomTag>,
     /// Unlock requirements
     pub unlock_requirement: UnlockRequirement,
     /// Connection points relative to this mushroom
     pub connection_points: Vec<GridOffset>,
+    /// `connection_points` rotated for each facing, filled in by `cache_rotations`
+    pub rotated_connection_points: [Vec<GridOffset>; 4],
+    /// Activation particle color, None uses the behavior's default
+    pub particle_color: Option<Color>,
 }
 
+impl MushroomDefinition {
+    /// Precompute the connection points for every facing, so lookups don't rotate them
+    pub fn cache_rotations(&mut self) {
+        self.rotated_connection_points = MushroomDirection::ALL.map(|direction| {
+            self.connection_points
+                .iter()
+                .map(|point| point.rotated(direction))
+                .collect()
+        });
+    }
+
+    pub fn has_tag(&self, tag: MushroomTag) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    /// Connection points for a mushroom facing `direction`
+    pub fn connection_points_facing(&self, direction: MushroomDirection) -> &[GridOffset] {
+        &self.rotated_connection_points[direction.index()]
+    }
+
+    /// Whether turning the mushroom changes which cells it connects to
+    pub fn is_directional(&self) -> bool {
+        let sorted = |offsets: &[GridOffset]| {
+            let mut cells: Vec<(i32, i32)> = offsets.iter().map(|o| (o.x, o.y)).collect();
+            cells.sort_unstable();
+            cells
+        };
+        let up = sorted(self.connection_points_facing(MushroomDirection::Up));
+        MushroomDirection::ALL[1..]
+            .iter()
+            .any(|direction| sorted(self.connection_points_facing(*direction)) != up)
+    }
+
+    /// Color of the particles spawned when this mushroom activates
+    pub fn particle_color(&self) -> Color {
+        self.particle_color
+            .unwrap_or_else(|| self.activation_behavior.default_particle_color())
+    }
+
+    /// Whether this is an amplifier that makes little on its own and relies on
+    /// the mushrooms it feeds, so cards and previews can say so
+    pub fn needs_downstream(&self) -> bool {
+        match self.activation_behavior {
+            ActivationBehavior::Amplifier { boost_factor } => {
+                let outputs = self.connection_points.len().max(1) as f64;
+                self.base_production < boost_factor as f64 * outputs
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Broad category of a mushroom, for queries that don't care about the exact type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum MushroomTag {
+    /// Makes spores worth planting on its own
+    Producer,
+    /// Passes energy on to other mushrooms
+    Connector,
+    /// Boosts the energy it passes on
+    Amplifier,
+    /// Removes mushrooms
+    Destroyer,
+    /// Changes tiles
+    Terraform,
+    /// Creates mushrooms
+    Summoner,
+}
+
+impl MushroomTag {
+    /// Display name for the UI
+    pub fn label(&self) -> &'static str {
+        match self {
+            MushroomTag::Producer => "Producer",
+            MushroomTag::Connector => "Connector",
+            MushroomTag::Amplifier => "Amplifier",
+            MushroomTag::Destroyer => "Destroyer",
+            MushroomTag::Terraform => "Terraform",
+            MushroomTag::Summoner => "Summoner",
+        }
+    }
+}
+
+/// Hint shown for mushrooms where [`MushroomDefinition::needs_downstream`] holds
+pub const NEEDS_DOWNSTREAM_HINT: &str = "Needs downstream mushrooms to shine";
+
 /// Defines how a mushroom behaves when activated
 #[derive(Debug, Clone, Reflect)]
 pub enum ActivationBehavior {
@@ -137,6 +248,50 @@ pub enum ActivationBehavior {
     },
     /// Deletes a mushroom in the connected square
     Deleter,
+    /// Sprouts short-lived Button mushrooms on empty neighbouring tiles, which join the chain
+    Spawner {
+        /// Most mushrooms sprouted per activation
+        spawn_count: u32,
+    },
+    /// Clears blocked tiles in the connected squares, scoring a bonus for each
+    Unblocker {
+        /// Most tiles cleared per activation
+        clear_count: u32,
+        /// Spores awarded per tile cleared
+        bonus_per_tile: f64,
+    },
+    /// Produces more the more mycelium connections there are on the whole field
+    Networked {
+        /// Extra share of production per connection, 0.1 adds 10% for each
+        bonus_per_connection: f64,
+    },
+    /// Produces by how deep in the chain it was reached instead of by its energy
+    Capacitor {
+        /// Spores added for each mushroom the chain passed through to get here
+        per_depth: f64,
+    },
+    /// Sends the full incoming energy to each target instead of dividing it
+    Splitter {
+        /// Most targets that get a copy
+        copies: u32,
+    },
+}
+
+impl ActivationBehavior {
+    /// Activation particle color for mushrooms that don't set their own
+    pub fn default_particle_color(&self) -> Color {
+        match self {
+            ActivationBehavior::Basic => Color::srgb(1.0, 1.0, 0.0),
+            ActivationBehavior::Amplifier { .. } => Color::srgb(1.0, 0.4, 1.0),
+            ActivationBehavior::Converter { .. } => Color::srgb(0.0, 0.9, 0.8),
+            ActivationBehavior::Deleter => Color::srgb(1.0, 0.3, 0.1),
+            ActivationBehavior::Spawner { .. } => Color::srgb(0.6, 1.0, 0.3),
+            ActivationBehavior::Splitter { .. } => Color::srgb(0.3, 0.6, 1.0),
+            ActivationBehavior::Unblocker { .. } => Color::srgb(0.9, 0.7, 0.3),
+            ActivationBehavior::Networked { .. } => Color::srgb(0.8, 0.9, 1.0),
+            ActivationBehavior::Capacitor { .. } => Color::srgb(1.0, 0.9, 0.5),
+        }
+    }
 }
 
 /// Requirements to unlock a mushroom type
@@ -181,10 +336,96 @@ impl UnlockRequirement {
     }
 }
 
+/// Progress towards an unmet unlock requirement
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnlockProgress {
+    /// What has to be reached, e.g. "500 total spores"
+    pub goal: String,
+    pub current: f64,
+    pub required: f64,
+}
+
+impl UnlockProgress {
+    /// How close the requirement is to being met, from 0 to 1
+    pub fn fraction(&self) -> f64 {
+        if self.required <= 0.0 {
+            1.0
+        } else {
+            (self.current / self.required).clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl UnlockRequirement {
+    /// Progress towards the closest unmet part of this requirement
+    /// Returns None if the requirement is already met
+    pub fn progress(
+        &self,
+        game_state: &crate::game::resources::GameState,
+        current_level: usize,
+    ) -> Option<UnlockProgress> {
+        if self.is_met(game_state, current_level) {
+            return None;
+        }
+
+        match self {
+            UnlockRequirement::None => None,
+            UnlockRequirement::TotalSpores(required) => Some(UnlockProgress {
+                goal: format!("{} total spores", format_spores(*required)),
+                current: game_state.total_spores_earned,
+                required: *required,
+            }),
+            UnlockRequirement::ReachLevel(level) => Some(UnlockProgress {
+                goal: format!("level {}", level + 1),
+                current: (current_level + 1) as f64,
+                required: (level + 1) as f64,
+            }),
+            UnlockRequirement::ChainActivations(required) => Some(UnlockProgress {
+                goal: format!("{required} chain activations"),
+                current: game_state.chain_activations as f64,
+                required: *required as f64,
+            }),
+            // The first unmet requirement is the one holding the unlock back
+            UnlockRequirement::All(reqs) => reqs
+                .iter()
+                .find_map(|req| req.progress(game_state, current_level)),
+            // Any will do, so show the closest
+            UnlockRequirement::Any(reqs) => reqs
+                .iter()
+                .filter_map(|req| req.progress(game_state, current_level))
+                .max_by(|a, b| a.fraction().total_cmp(&b.fraction())),
+        }
+    }
+}
+
 /// Resource containing all mushroom definitions
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct MushroomDefinitions {
     definitions: HashMap<MushroomType, MushroomDefinition>,
+    /// Stand-in for types without a definition
+    fallback: MushroomDefinition,
+}
+
+impl Default for MushroomDefinitions {
+    fn default() -> Self {
+        Self {
+            definitions: HashMap::new(),
+            fallback: MushroomDefinition {
+                name: "Unknown".to_string(),
+                description: "1 Spore.".to_string(),
+                base_production: 1.0,
+                cooldown_time: 0.1,
+                max_uses_per_turn: 1,
+                sprite_row: 8,
+                activation_behavior: ActivationBehavior::Basic,
+                tags: vec![MushroomTag::Producer],
+                unlock_requirement: UnlockRequirement::None,
+                connection_points: vec![],
+                particle_color: None,
+                rotated_connection_points: default(),
+            },
+        }
+    }
 }
 
 impl MushroomDefinitions {
@@ -193,12 +434,53 @@ impl MushroomDefinitions {
         self.definitions.get(&mushroom_type)
     }
 
-    /// Get all mushroom types
+    /// Get a mushroom definition, or a harmless Basic stand-in if the type has none
+    ///
+    /// For places where a mushroom already exists and skipping it would leave it broken,
+    /// such as on the field or mid-chain.
+    pub fn get_or_default(&self, mushroom_type: MushroomType) -> &MushroomDefinition {
+        self.definitions.get(&mushroom_type).unwrap_or_else(|| {
+            warn_once!(
+                "No definition for mushroom type {:?}, treating it as a basic mushroom",
+                mushroom_type
+            );
+            &self.fallback
+        })
+    }
+
+    /// Every mushroom type with the given tag
     #[allow(dead_code)]
+    pub fn types_with_tag(&self, tag: MushroomTag) -> Vec<MushroomType> {
+        self.definitions
+            .iter()
+            .filter(|(_, definition)| definition.has_tag(tag))
+            .map(|(mushroom_type, _)| *mushroom_type)
+            .collect()
+    }
+
+    /// Get all mushroom types
     pub fn all_types(&self) -> Vec<MushroomType> {
         self.definitions.keys().copied().collect()
     }
 
+    /// The locked mushroom closest to being unlocked, with its progress
+    /// Returns None once everything is unlocked
+    pub fn next_unlock(
+        &self,
+        game_state: &crate::game::resources::GameState,
+        current_level: usize,
+    ) -> Option<(MushroomType, UnlockProgress)> {
+        self.definitions
+            .iter()
+            .filter(|(mushroom_type, _)| **mushroom_type != MushroomType::Test)
+            .filter_map(|(mushroom_type, def)| {
+                def.unlock_requirement
+                    .progress(game_state, current_level)
+                    .map(|progress| (*mushroom_type, progress))
+            })
+            .max_by(|a, b| a.1.fraction().total_cmp(&b.1.fraction()))
+    }
+
     /// Check if a mushroom type is unlocked
     pub fn is_unlocked(
         &self,
@@ -214,7 +496,21 @@ impl MushroomDefinitions {
 }
 
 /// All mushroom types in the game
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+#[derive(
+    Component,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    Reflect,
+    Serialize,
+    Deserialize,
+)]
 #[reflect(Component)]
 pub enum MushroomType {
     #[default]
@@ -236,6 +532,10 @@ pub enum MushroomType {
     Converter,
     Knight,
     Unblocker,
+    Spawner,
+    Splitter,
+    Hub,
+    Capacitor,
     Test,
 }
 
@@ -247,6 +547,32 @@ pub(super) fn plugin(app: &mut App) {
 
 /// Initialize all mushroom definitions at startup
 fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
+    *definitions = MushroomDefinitions::standard();
+    info!(
+        "Initialized {} mushroom definitions",
+        definitions.definitions.len()
+    );
+}
+
+impl MushroomDefinitions {
+    /// Every mushroom in the game
+    pub fn standard() -> Self {
+        Self {
+            definitions: standard_definitions(),
+            ..default()
+        }
+    }
+}
+
+#[cfg(test)]
+impl MushroomDefinitions {
+    /// Replace a type's definition, for tests that need one the standard set doesn't have
+    pub fn insert(&mut self, mushroom_type: MushroomType, definition: MushroomDefinition) {
+        self.definitions.insert(mushroom_type, definition);
+    }
+}
+
+fn standard_definitions() -> HashMap<MushroomType, MushroomDefinition> {
     let mut defs = HashMap::new();
 
     // Basic Mushroom - no connections
@@ -260,8 +586,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 5,
             sprite_row: 8,
             activation_behavior: ActivationBehavior::Basic,
+            tags: vec![MushroomTag::Producer],
             unlock_requirement: UnlockRequirement::None,
             connection_points: vec![],
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -276,8 +605,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 3,
             sprite_row: 6,
             activation_behavior: ActivationBehavior::Basic,
+            tags: vec![MushroomTag::Producer, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -292,8 +624,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 3,
             sprite_row: 19,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 2.0 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::SIDEWAYS.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -308,8 +643,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 2,
             sprite_row: 9,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 2.0 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::FORK.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -324,8 +662,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 2,
             sprite_row: 0,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 2.0 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::DIAGONALLINE.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -340,8 +681,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 2,
             sprite_row: 7,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 3.0 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::THREEWAY.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -356,8 +700,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 2,
             sprite_row: 1,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 8.0 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::ALL_DIRECTIONS.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -372,8 +719,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 2,
             sprite_row: 2,
             activation_behavior: ActivationBehavior::Basic,
+            tags: vec![MushroomTag::Producer, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::SKIP_FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -388,8 +738,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 1,
             sprite_row: 3,
             activation_behavior: ActivationBehavior::Deleter,
+            tags: vec![MushroomTag::Destroyer, MushroomTag::Producer],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -404,8 +757,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 1,
             sprite_row: 4,
             activation_behavior: ActivationBehavior::Deleter,
+            tags: vec![MushroomTag::Destroyer, MushroomTag::Producer],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::DIAGONAL.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -420,8 +776,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 3,
             sprite_row: 5,
             activation_behavior: ActivationBehavior::Basic,
+            tags: vec![MushroomTag::Producer],
             unlock_requirement: UnlockRequirement::None,
             connection_points: vec![], // No connections
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -436,8 +795,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 1,
             sprite_row: 10,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 1.5 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -452,8 +814,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 1,
             sprite_row: 16,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 2.5 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::SIDEWAYS.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -468,8 +833,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 1,
             sprite_row: 17,
             activation_behavior: ActivationBehavior::Amplifier { boost_factor: 3.75 },
+            tags: vec![MushroomTag::Amplifier, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::THREEWAY.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -484,8 +852,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 10,
             sprite_row: 14,
             activation_behavior: ActivationBehavior::Basic,
+            tags: vec![MushroomTag::Producer, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -505,8 +876,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
                 can_convert_from: Some(vec![TileType::Empty]), // Only converts empty soil
                 search_radius: 2,                              // Searches within 2 tiles
             },
+            tags: vec![MushroomTag::Terraform, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -521,8 +895,11 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
             max_uses_per_turn: 3,
             sprite_row: 12,
             activation_behavior: ActivationBehavior::Basic,
+            tags: vec![MushroomTag::Producer, MushroomTag::Connector],
             unlock_requirement: UnlockRequirement::None,
             connection_points: connection_patterns::KNIGHT_FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
@@ -531,29 +908,179 @@ fn initialize_definitions(mut definitions: ResMut<MushroomDefinitions>) {
         MushroomType::Unblocker,
         MushroomDefinition {
             name: "Wizard's Stave".to_string(),
-            description: "15 Spores. Unblock 1.".to_string(),
+            description: "8 Spores.\nClear 1 blocker, +15 each.".to_string(),
             base_production: 8.0,
             cooldown_time: 2.5,
             max_uses_per_turn: 3,
             sprite_row: 21,
-            activation_behavior: ActivationBehavior::Converter {
-                convert_to: TileType::Empty,
-                convert_count: 1,
-                can_convert_from: Some(vec![
-                    TileType::BlockedMoss,
-                    TileType::BlockedRock,
-                    TileType::BlockedWater,
-                ]), // converts blockers
-                search_radius: 2, // Searches within 2 tiles
+            activation_behavior: ActivationBehavior::Unblocker {
+                clear_count: 1,
+                bonus_per_tile: 15.0,
             },
+            tags: vec![MushroomTag::Terraform],
+            unlock_requirement: UnlockRequirement::None,
+            connection_points: connection_patterns::CARDINAL.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
+        },
+    );
+
+    // Spawner Mushroom - sprouts temporary Buttons around itself
+    defs.insert(
+        MushroomType::Spawner,
+        MushroomDefinition {
+            name: "Fairy Ring".to_string(),
+            description: "3 Spores.\nSprout 2 Buttons.".to_string(),
+            base_production: 3.0,
+            cooldown_time: 0.5,
+            max_uses_per_turn: 1,
+            sprite_row: 20,
+            activation_behavior: ActivationBehavior::Spawner { spawn_count: 2 },
+            tags: vec![MushroomTag::Summoner],
             unlock_requirement: UnlockRequirement::None,
             connection_points: vec![],
+            particle_color: None,
+            rotated_connection_points: default(),
         },
     );
 
-    definitions.definitions = defs;
-    info!(
-        "Initialized {} mushroom definitions",
-        definitions.definitions.len()
+    // Splitter Mushroom - copies its energy to up to three targets
+    defs.insert(
+        MushroomType::Splitter,
+        MushroomDefinition {
+            name: "Mirror Morel".to_string(),
+            description: "2 Spores.\nCopy energy to 3.".to_string(),
+            base_production: 2.0,
+            cooldown_time: 1.0,
+            max_uses_per_turn: 1,
+            sprite_row: 22,
+            activation_behavior: ActivationBehavior::Splitter { copies: 3 },
+            tags: vec![MushroomTag::Connector, MushroomTag::Amplifier],
+            unlock_requirement: UnlockRequirement::None,
+            connection_points: connection_patterns::THREEWAY.to_vec(),
+            rotated_connection_points: default(),
+            particle_color: None,
+        },
+    );
+
+    // Hub Mushroom - rewards dense networks anywhere on the field
+    defs.insert(
+        MushroomType::Hub,
+        MushroomDefinition {
+            name: "Mycelial Hub".to_string(),
+            description: "2 Spores.\n+10% per connection.".to_string(),
+            base_production: 2.0,
+            cooldown_time: 1.0,
+            max_uses_per_turn: 2,
+            sprite_row: 11,
+            activation_behavior: ActivationBehavior::Networked {
+                bonus_per_connection: 0.1,
+            },
+            tags: vec![MushroomTag::Producer, MushroomTag::Connector],
+            unlock_requirement: UnlockRequirement::None,
+            connection_points: connection_patterns::CARDINAL.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
+        },
     );
+
+    // Capacitor Mushroom - pays off at the end of long chains
+    defs.insert(
+        MushroomType::Capacitor,
+        MushroomDefinition {
+            name: "Stormcap".to_string(),
+            description: "2 Spores.\n+3 per chain depth.".to_string(),
+            base_production: 2.0,
+            cooldown_time: 1.0,
+            max_uses_per_turn: 1,
+            sprite_row: 13,
+            activation_behavior: ActivationBehavior::Capacitor { per_depth: 3.0 },
+            tags: vec![MushroomTag::Producer, MushroomTag::Connector],
+            unlock_requirement: UnlockRequirement::None,
+            connection_points: connection_patterns::FORWARD.to_vec(),
+            particle_color: None,
+            rotated_connection_points: default(),
+        },
+    );
+
+    for (mushroom_type, definition) in defs.iter_mut() {
+        definition.cache_rotations();
+        if definition.tags.is_empty() {
+            warn!("Mushroom definition {:?} has no tags", mushroom_type);
+        }
+    }
+
+    defs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undefined_type_falls_back_to_a_harmless_basic() {
+        let definitions = MushroomDefinitions::standard();
+        assert!(definitions.get(MushroomType::Test).is_none());
+
+        let fallback = definitions.get_or_default(MushroomType::Test);
+        assert!(matches!(
+            fallback.activation_behavior,
+            ActivationBehavior::Basic
+        ));
+        assert!(fallback.connection_points.is_empty());
+        assert_eq!(fallback.base_production, 1.0);
+        assert_eq!(fallback.name, "Unknown");
+    }
+
+    #[test]
+    fn cached_rotations_match_rotating_on_the_fly() {
+        let definitions = MushroomDefinitions::standard();
+        for mushroom_type in definitions.all_types() {
+            let definition = definitions.get(mushroom_type).unwrap();
+            for direction in MushroomDirection::ALL {
+                let cached: Vec<(i32, i32)> = definition
+                    .connection_points_facing(direction)
+                    .iter()
+                    .map(|offset| (offset.x, offset.y))
+                    .collect();
+                let rotated: Vec<(i32, i32)> = definition
+                    .connection_points
+                    .iter()
+                    .map(|offset| offset.rotated(direction))
+                    .map(|offset| (offset.x, offset.y))
+                    .collect();
+                assert_eq!(cached, rotated, "{mushroom_type:?} facing {direction:?}");
+            }
+        }
+    }
+
+    fn rotations(offset: GridOffset) -> [(i32, i32); 4] {
+        MushroomDirection::ALL.map(|direction| {
+            let rotated = offset.rotated(direction);
+            (rotated.x, rotated.y)
+        })
+    }
+
+    #[test]
+    fn knight_and_skip_offsets_turn_clockwise() {
+        // Up, Right, Down, Left
+        assert_eq!(
+            rotations(connection_patterns::KNIGHT_FORWARD[0]),
+            [(2, 1), (1, -2), (-2, -1), (-1, 2)]
+        );
+        assert_eq!(
+            rotations(connection_patterns::SKIP_FORWARD[0]),
+            [(0, 2), (2, 0), (0, -2), (-2, 0)]
+        );
+    }
+
+    #[test]
+    fn every_definition_has_a_tag() {
+        let definitions = MushroomDefinitions::standard();
+        for mushroom_type in definitions.all_types() {
+            let definition = definitions.get(mushroom_type).unwrap();
+            assert!(!definition.tags.is_empty(), "{mushroom_type:?} has no tags");
+        }
+        assert!(!MushroomDefinitions::default().fallback.tags.is_empty());
+    }
 }
@@ -15,6 +15,22 @@ pub struct SpawnMushroomEvent {
     pub position: GridPosition,
     pub mushroom_type: MushroomType,
     pub direction: Option<MushroomDirection>,
+    /// Entity to build the mushroom on, for when it has to be known before it spawns
+    pub entity: Option<Entity>,
+    pub source: SpawnSource,
+}
+
+/// Where a spawned mushroom comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnSource {
+    /// Planted by the player, using up the card in play
+    Placed,
+    /// Part of the level's starting layout
+    Level,
+    /// Put back by a board restore or an undo, no card or sound goes with it
+    Restored,
+    /// Sprouted by a spawner during a chain
+    Sprouted,
 }
 
 /// Event to remove a mushroom
@@ -29,4 +45,17 @@ pub struct RemoveMushroomEvent {
 pub struct SporeScoreEvent {
     pub position: GridPosition,
     pub production: f64,
+    /// Hops from the chain's starter to this activation
+    pub depth: u32,
+}
+
+/// A chain has finished, every activation it queued has been processed
+#[derive(Event)]
+pub struct ChainCompleteEvent;
+
+/// Energy a chain activation arrived with, shown as a tag when energy tags are on
+#[derive(Event)]
+pub struct ActivationEnergyEvent {
+    pub position: GridPosition,
+    pub energy: f32,
 }
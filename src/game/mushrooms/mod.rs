@@ -1,30 +1,42 @@
 use bevy::{pbr::NotShadowReceiver, prelude::*};
 use bevy_sprite3d::{Sprite3dBuilder, Sprite3dParams};
+use serde::{Deserialize, Serialize};
 
 use crate::game::{
-    carddeck::{card::Card, hand::Hand, markers::Dragged},
-    game_flow::{LevelState, TurnPhase},
-    level::assets::LevelAssets,
+    carddeck::{
+        card::Card, deck::DiscardPile, hand::Hand, markers::Dragged, wildcard::OpenWildcardPicker,
+    },
+    game_flow::{CurrentLevel, LevelState, TurnPhase},
+    level::{
+        assets::LevelAssets,
+        recording::{RecordActionEvent, RunActionKind},
+    },
     mushrooms::chain_activation::reset_mushroom_states,
     play_field::{
-        GridClickEvent, GridPosition, observers::find_entity_at, placement_preview::PreviewState,
+        GridClickEvent, GridPosition, mycelium::MyceliumConnectionsEnabled,
+        observers::find_entity_at, placement_preview::PreviewState,
     },
     resources::GameState,
     visual_effects::FaceCamera,
 };
+use crate::{PausableSystems, settings::KeyBindings};
 
 pub use chain_activation::{ChainManager, MushroomActivationState};
 pub use definitions::{MushroomDefinitions, MushroomType};
-pub use events::SpawnMushroomEvent;
+pub use events::{SpawnMushroomEvent, SpawnSource};
 pub use resources::SelectedMushroomType;
 
 pub mod chain_activation;
 pub mod converter_system;
 pub mod definitions;
 pub mod events;
+#[cfg(feature = "dev")]
+pub mod replay;
 pub mod resources;
+pub mod simulation;
 pub mod sounds;
 pub mod ui;
+pub mod upgrades;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
@@ -34,7 +46,10 @@ pub(super) fn plugin(app: &mut App) {
         events::plugin,
         ui::plugin,
         sounds::plugin,
+        upgrades::plugin,
     ));
+    #[cfg(feature = "dev")]
+    app.add_plugins(replay::plugin);
 
     // Initialize resources
     app.init_resource::<SelectedMushroomType>();
@@ -48,6 +63,97 @@ pub(super) fn plugin(app: &mut App) {
 
     // Reset mushroom states when entering Draw phase (when new cards are drawn)
     app.add_systems(OnEnter(TurnPhase::Draw), reset_mushroom_states);
+
+    // Mushrooms sprouted during a chain only last for that turn's chain phase
+    app.add_systems(OnExit(TurnPhase::Chain), despawn_temporary_mushrooms);
+
+    // A selection carried over from another level may not be unlocked here
+    app.add_systems(OnEnter(LevelState::Playing), validate_selected_mushroom);
+
+    // Number keys play cards from the hand, dialogue states never reach the planting phase
+    app.add_systems(
+        Update,
+        select_card_with_number_keys
+            .run_if(in_state(TurnPhase::Planting))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Play the hand card matching the pressed choice key, as if it was dragged onto the board
+fn select_card_with_number_keys(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    hand: Res<Hand>,
+    mut cards: Query<(Entity, &Card, &mut Dragged)>,
+    mut selected_type: ResMut<SelectedMushroomType>,
+) {
+    let Some(index) = bindings
+        .choice_keys
+        .keys()
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+    else {
+        return;
+    };
+    let Some(card_entity) = hand.card_entity(index) else {
+        return;
+    };
+
+    // Leave a card that's being dragged alone
+    if cards
+        .iter()
+        .any(|(_, _, dragged)| *dragged == Dragged::Active)
+    {
+        return;
+    }
+
+    // Put any other played card back in the hand, so only the selected one is raised
+    for (entity, card, mut dragged) in cards.iter_mut() {
+        if entity == card_entity {
+            if !dragged.set_if_neq(Dragged::Played) {
+                // Already selected
+                continue;
+            }
+            if card.wildcard {
+                // The mushroom type is chosen from the picker
+                selected_type.mushroom_type = None;
+                commands.trigger(OpenWildcardPicker);
+            } else {
+                selected_type.mushroom_type = Some(card.mushroom_type);
+            }
+            info!(
+                "Selected card {} ({}) from the keyboard",
+                index + 1,
+                card.name
+            );
+        } else if *dragged == Dragged::Played {
+            *dragged = Dragged::Released;
+        }
+    }
+}
+
+/// Drop a selection that isn't unlocked on this level, a card has to be played to pick again
+fn validate_selected_mushroom(
+    mut selected_type: ResMut<SelectedMushroomType>,
+    definitions: Res<MushroomDefinitions>,
+    game_state: Res<GameState>,
+    current_level: Res<CurrentLevel>,
+) {
+    let Some(selected) = selected_type.mushroom_type else {
+        return;
+    };
+    let level = current_level.level_index;
+    if definitions.is_unlocked(selected, &game_state, level) {
+        return;
+    }
+
+    info!(
+        "{:?} is locked on level {}, clearing the selection",
+        selected,
+        level + 1
+    );
+    selected_type.mushroom_type = None;
 }
 
 /// Marker component for mushroom entities
@@ -58,8 +164,12 @@ pub struct Mushroom(pub MushroomType);
 #[derive(Component)]
 pub struct MushroomSprite;
 
+/// Marker for mushrooms sprouted by a spawner, removed when the chain phase ends
+#[derive(Component)]
+pub struct TemporaryMushroom;
+
 /// Direction component for mushrooms
-#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum MushroomDirection {
     #[default]
     Up,
@@ -69,6 +179,19 @@ pub enum MushroomDirection {
 }
 
 impl MushroomDirection {
+    /// Every facing, in clockwise order from `Up`
+    pub const ALL: [MushroomDirection; 4] = [
+        MushroomDirection::Up,
+        MushroomDirection::Right,
+        MushroomDirection::Down,
+        MushroomDirection::Left,
+    ];
+
+    /// Position of this facing in [`MushroomDirection::ALL`]
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
     pub fn rotate_clockwise(&self) -> MushroomDirection {
         match self {
             MushroomDirection::Up => MushroomDirection::Right,
@@ -77,6 +200,17 @@ impl MushroomDirection {
             MushroomDirection::Left => MushroomDirection::Up,
         }
     }
+
+    /// Rotation about the up axis that turns world forward (-Z, grid up) to this facing
+    pub fn to_quat(self) -> Quat {
+        let angle = match self {
+            MushroomDirection::Up => 0.0,
+            MushroomDirection::Right => -std::f32::consts::FRAC_PI_2,
+            MushroomDirection::Down => std::f32::consts::PI,
+            MushroomDirection::Left => std::f32::consts::FRAC_PI_2,
+        };
+        Quat::from_rotation_y(angle)
+    }
 }
 
 /// Handle grid clicks based on game phase
@@ -91,9 +225,10 @@ fn handle_grid_clicks(
     definitions: Res<MushroomDefinitions>,
     game_state: ResMut<GameState>,
     current_phase: Option<Res<State<TurnPhase>>>,
-    current_level: Res<crate::game::game_flow::CurrentLevel>,
+    current_level: Res<CurrentLevel>,
     preview_state: Res<PreviewState>,
     hovered_cell: Res<crate::game::play_field::placement_preview::HoveredCell>,
+    connections_enabled: ResMut<MyceliumConnectionsEnabled>,
 ) {
     info!("Grid click at {:?}", trigger.position);
 
@@ -118,6 +253,7 @@ fn handle_grid_clicks(
             current_level.level_index,
             preview_state,
             hovered_cell,
+            connections_enabled,
         ),
         TurnPhase::Chain => {
             handle_chain_click(trigger.event(), commands, chain_manager, game_state)
@@ -144,6 +280,7 @@ fn handle_planting_click(
     current_level: usize,
     preview_state: Res<PreviewState>,
     hovered_cell: Res<crate::game::play_field::placement_preview::HoveredCell>,
+    mut connections_enabled: ResMut<MyceliumConnectionsEnabled>,
 ) {
     // Right-click to delete
     if event.button == bevy::picking::pointer::PointerButton::Secondary {
@@ -151,6 +288,9 @@ fn handle_planting_click(
             info!("Deleting mushroom at {:?}", event.position);
             commands.entity(entity).despawn();
             game_state.play_field.remove(event.position);
+            commands.trigger(RecordActionEvent(RunActionKind::Remove {
+                position: event.position,
+            }));
         }
         return;
     }
@@ -218,7 +358,19 @@ fn handle_planting_click(
         position: event.position,
         mushroom_type: selected_type.mushroom_type.unwrap(),
         direction: Some(preview_state.direction),
+        entity: None,
+        source: SpawnSource::Placed,
     });
+    commands.trigger(RecordActionEvent(RunActionKind::Place {
+        mushroom_type: selected_type.mushroom_type.unwrap(),
+        position: event.position,
+        direction: Some(preview_state.direction),
+    }));
+
+    // The first player placement wakes up any suppressed connections
+    if !connections_enabled.0 {
+        connections_enabled.0 = true;
+    }
 
     // Set selected mushroom back to none
     selected_type.mushroom_type = None;
@@ -228,7 +380,7 @@ fn handle_planting_click(
 #[tracing::instrument(name = "Handle chain click", skip_all)]
 fn handle_chain_click(
     event: &GridClickEvent,
-    _commands: Commands,
+    mut commands: Commands,
     mut chain_manager: ResMut<ChainManager>,
     game_state: ResMut<GameState>,
 ) {
@@ -244,7 +396,9 @@ fn handle_chain_click(
                     "Started chain {} from mushroom at {:?}",
                     chain_id, event.position
                 );
-                // Could trigger visual effects here
+                commands.trigger(RecordActionEvent(RunActionKind::StartChain {
+                    position: event.position,
+                }));
             }
             None => {
                 info!("Cannot start another chain this turn");
@@ -267,14 +421,9 @@ fn spawn_mushroom(
     preview_state: Res<PreviewState>,
     cards_query: Query<(Entity, &Dragged), With<Card>>,
     mut hand: ResMut<Hand>,
+    mut discard: ResMut<DiscardPile>,
 ) -> Result {
-    let Some(definition) = definitions.get(trigger.mushroom_type) else {
-        warn!(
-            "No definition for mushroom type {:?}",
-            trigger.mushroom_type
-        );
-        return Ok(());
-    };
+    let definition = definitions.get_or_default(trigger.mushroom_type);
 
     let world_pos = trigger.position.to_world_in(&game_state.play_field);
 
@@ -297,8 +446,12 @@ fn spawn_mushroom(
     let direction = trigger.event().direction.unwrap_or(preview_state.direction);
 
     // Spawn mushroom entity
-    let entity = commands
-        .spawn((
+    let mut entity_commands = match trigger.entity {
+        Some(entity) => commands.entity(entity),
+        None => commands.spawn_empty(),
+    };
+    let entity = entity_commands
+        .insert((
             Name::new(format!(
                 "{} at ({}, {})",
                 definition.name, trigger.position.x, trigger.position.y
@@ -341,25 +494,49 @@ fn spawn_mushroom(
         definition.name, entity, direction
     );
 
-    // Despawn active card
+    // Only the player's placements are paid for with a card
+    if trigger.source != SpawnSource::Placed {
+        return Ok(());
+    }
+
+    sounds::place_mushroom_sfx(&mut commands, &level_assets, world_pos);
+
+    // Despawn active card, it goes to the discard pile
     for (entity, dragged_component) in cards_query {
         if dragged_component == &Dragged::Played {
-            hand.despawn_card(commands.reborrow(), entity)?;
+            if let Some(card) = hand.despawn_card(commands.reborrow(), entity)? {
+                discard.add(card);
+            }
         }
     }
 
     Ok(())
 }
 
+/// Clear away the mushrooms sprouted by spawners this turn
+fn despawn_temporary_mushrooms(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    temporary: Query<(Entity, &GridPosition), With<TemporaryMushroom>>,
+) {
+    for (entity, position) in &temporary {
+        if game_state.play_field.get(*position) == Some(entity) {
+            game_state.play_field.remove(*position);
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
 /// Reset systems when entering chain phase
 fn reset_chain_phase(
     mut chain_manager: ResMut<ChainManager>,
     mut mushroom_states: Query<&mut MushroomActivationState>,
+    current_level: Res<CurrentLevel>,
 ) {
     info!("=== CHAIN PHASE STARTED ===");
 
     // Reset chain manager for new chain phase
-    chain_manager.reset_turn();
+    chain_manager.reset_turn(current_level.max_chains_per_turn);
 
     // Reset mushroom activation counts
     for mut state in mushroom_states.iter_mut() {
@@ -369,3 +546,61 @@ fn reset_chain_phase(
 
     info!("Click a mushroom to begin a chain reaction!");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn temporary_mushrooms_are_removed_at_the_end_of_the_turn() {
+        let mut world = World::new();
+        let mut game_state = GameState::default();
+
+        let sprout_position = GridPosition::new(1, 1);
+        let sprout = world
+            .spawn((
+                TemporaryMushroom,
+                Mushroom(MushroomType::Basic),
+                sprout_position,
+            ))
+            .id();
+        game_state.play_field.insert(sprout_position, sprout);
+
+        let placed_position = GridPosition::new(2, 1);
+        let placed = world
+            .spawn((Mushroom(MushroomType::Spawner), placed_position))
+            .id();
+        game_state.play_field.insert(placed_position, placed);
+        world.insert_resource(game_state);
+
+        world.run_system_once(despawn_temporary_mushrooms).unwrap();
+
+        assert!(world.get_entity(sprout).is_err());
+        assert!(world.get_entity(placed).is_ok());
+        let play_field = &world.resource::<GameState>().play_field;
+        assert_eq!(play_field.get(sprout_position), None);
+        assert_eq!(play_field.get(placed_position), Some(placed));
+    }
+
+    #[test]
+    fn later_unlock_selection_is_cleared_on_the_first_level() {
+        let mut definitions = MushroomDefinitions::standard();
+        let mut knight = definitions.get(MushroomType::Knight).unwrap().clone();
+        knight.unlock_requirement = definitions::UnlockRequirement::ReachLevel(3);
+        definitions.insert(MushroomType::Knight, knight);
+
+        let mut world = World::new();
+        world.insert_resource(definitions);
+        world.insert_resource(GameState::default());
+        world.insert_resource(CurrentLevel::default());
+        world.insert_resource(SelectedMushroomType {
+            mushroom_type: Some(MushroomType::Knight),
+        });
+
+        world.run_system_once(validate_selected_mushroom).unwrap();
+
+        // No card pays for a fallback, so nothing is selected until one is played
+        assert_eq!(world.resource::<SelectedMushroomType>().mushroom_type, None);
+    }
+}
@@ -0,0 +1,146 @@
+//! Chain replay
+//!
+//! Plays the last finished chain back from its recorded activations, firing the
+//! activation animations and spore popups in the order they happened. Nothing is
+//! re-simulated and no spores are scored, so a replay can't change the board.
+//!
+//! Dev builds only. Press F11 or use the dev tools window to replay, at the speed
+//! picked in that window.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use std::collections::VecDeque;
+
+use super::{ChainManager, Mushroom, chain_activation::ChainActivation, events::SporeScoreEvent};
+use crate::{
+    PausableSystems,
+    game::{game_flow::LevelState, visual_effects::ActivationAnimation},
+};
+
+/// Seconds between replayed activations at 1x speed
+const REPLAY_STEP_SECS: f32 = 0.2;
+
+const REPLAY_KEY: KeyCode = KeyCode::F11;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ChainReplay>();
+    app.add_observer(start_replay);
+    app.add_systems(
+        Update,
+        (
+            replay_with_key.run_if(input_just_pressed(REPLAY_KEY)),
+            play_replay,
+        )
+            .chain()
+            .run_if(in_state(LevelState::Playing))
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnExit(LevelState::Playing), stop_replay);
+}
+
+/// Replay the last finished chain at `speed` times the normal pace
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ReplayChainEvent {
+    pub speed: f32,
+}
+
+/// The replay in progress, if any
+#[derive(Resource, Debug)]
+pub struct ChainReplay {
+    /// Speed F11 replays at, set from the dev tools window
+    pub speed: f32,
+    /// Activations still to play, in recorded order
+    remaining: VecDeque<ChainActivation>,
+    timer: Timer,
+}
+
+impl Default for ChainReplay {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            remaining: VecDeque::new(),
+            timer: Timer::default(),
+        }
+    }
+}
+
+impl ChainReplay {
+    pub fn is_playing(&self) -> bool {
+        !self.remaining.is_empty()
+    }
+}
+
+fn replay_with_key(mut commands: Commands, replay: Res<ChainReplay>) {
+    commands.trigger(ReplayChainEvent {
+        speed: replay.speed,
+    });
+}
+
+fn start_replay(
+    trigger: Trigger<ReplayChainEvent>,
+    chain_manager: Res<ChainManager>,
+    mut replay: ResMut<ChainReplay>,
+) {
+    if chain_manager.has_active_chains() {
+        info!("Can't replay while a chain is running");
+        return;
+    }
+    let Some(chain) = chain_manager
+        .chains
+        .iter()
+        .rev()
+        .find(|chain| !chain.active && !chain.activations.is_empty())
+    else {
+        info!("No finished chain to replay");
+        return;
+    };
+
+    let speed = trigger.speed.max(0.1);
+    replay.remaining = chain.activations.iter().cloned().collect();
+    // Repeating, so the first activation plays straight away
+    replay.timer = Timer::from_seconds(REPLAY_STEP_SECS / speed, TimerMode::Repeating);
+    let step = replay.timer.duration();
+    replay.timer.set_elapsed(step);
+    info!(
+        "Replaying chain {} ({} activations) at {}x",
+        chain.id,
+        replay.remaining.len(),
+        speed
+    );
+}
+
+fn play_replay(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut replay: ResMut<ChainReplay>,
+    mushrooms: Query<(&Transform, Option<&ActivationAnimation>), With<Mushroom>>,
+) {
+    if !replay.is_playing() {
+        return;
+    }
+    replay.timer.tick(time.delta());
+
+    for _ in 0..replay.timer.times_finished_this_tick() {
+        let Some(activation) = replay.remaining.pop_front() else {
+            break;
+        };
+
+        commands.trigger(SporeScoreEvent {
+            position: activation.position,
+            production: activation.production,
+            depth: activation.depth,
+        });
+
+        // Deleted and temporary mushrooms may be gone by now, the popup still plays
+        if let Ok((transform, animation)) = mushrooms.get(activation.entity) {
+            // Keep the resting scale if the mushroom is still mid-animation
+            let original_scale = animation.map_or(transform.scale, |a| a.original_scale);
+            commands
+                .entity(activation.entity)
+                .insert(ActivationAnimation::new(0.4, 1.3, original_scale));
+        }
+    }
+}
+
+fn stop_replay(mut replay: ResMut<ChainReplay>) {
+    replay.remaining.clear();
+}
@@ -0,0 +1,377 @@
+//! Dry-run chain simulation
+//!
+//! Plays a chain out on a plain copy of the board, without entities, timers or effects,
+//! so analysis features can score boards the player hasn't built.
+//! Follows the same rules as `chain_activation`: uses per turn, cooldown waits, tile
+//! multipliers, energy splitting, energy decay, amplifiers, splitters, deleters,
+//! unblockers, spawners, hubs and capacitors. Sprouts join the simulated board for the
+//! rest of the chain, as the real ones stay until the turn ends.
+//! Hubs count the connections on the simulated board the same way the play field
+//! builds them, so blocked mycelium has none and a pair linked both ways counts once.
+
+use bevy::platform::collections::{HashMap, HashSet};
+
+use super::{
+    MushroomDirection, MushroomType,
+    chain_activation::{
+        ChainConfig, MAX_COOLDOWN_WAIT, MAX_ENERGY, capacitor_production, networked_production,
+        sprout_positions,
+    },
+    definitions::{ActivationBehavior, MushroomDefinitions},
+    upgrades::MushroomUpgrades,
+};
+use crate::game::{
+    play_field::{GridPosition, PlayField, mycelium},
+    resources::clamp_spores,
+};
+
+/// Upper bound on activations in one simulated chain, so runaway loops stay cheap
+const MAX_SIMULATED_ACTIVATIONS: usize = 2_000;
+
+/// Mushrooms on a simulated board, by position
+pub type SimulatedBoard = HashMap<GridPosition, (MushroomType, MushroomDirection)>;
+
+/// Simulate a chain started at `start` and return the spores it would produce
+/// `field` only provides bounds and tiles, its entities are ignored
+pub fn simulate_chain(
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+    config: &ChainConfig,
+    field: &PlayField,
+    board: &SimulatedBoard,
+    start: GridPosition,
+) -> f64 {
+    simulate_chain_after(
+        definitions,
+        upgrades,
+        config,
+        field,
+        board,
+        start,
+        &HashMap::default(),
+    )
+}
+
+/// Like [`simulate_chain`], for a turn where mushrooms have already been used
+/// `uses_so_far` counts each position's activations earlier in the turn
+pub fn simulate_chain_after(
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+    config: &ChainConfig,
+    field: &PlayField,
+    board: &SimulatedBoard,
+    start: GridPosition,
+    uses_so_far: &HashMap<GridPosition, u32>,
+) -> f64 {
+    // Deleters remove mushrooms, so work on a copy of the board
+    let mut board = board.clone();
+    let mut uses = uses_so_far.clone();
+    let mut cooldown_until: HashMap<GridPosition, f32> = HashMap::default();
+    // Blockers cleared by Unblockers, the field itself is left alone
+    let mut cleared_tiles: HashSet<GridPosition> = HashSet::default();
+
+    // Counted when a hub first needs it, and again after the board changes
+    let mut connections: Option<usize> = None;
+
+    // (time, position, energy, depth)
+    let mut pending: Vec<(f32, GridPosition, f32, u32)> = vec![(0.0, start, 1.0, 0)];
+    let mut total = 0.0;
+    let mut processed = 0;
+
+    while processed < MAX_SIMULATED_ACTIVATIONS {
+        // Take the earliest pending activation
+        let Some(next) = pending
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.0.total_cmp(&b.1.0))
+            .map(|(i, _)| i)
+        else {
+            break;
+        };
+        let (time, position, energy, depth) = pending.swap_remove(next);
+        processed += 1;
+
+        let Some(&(mushroom_type, direction)) = board.get(&position) else {
+            continue;
+        };
+        // Same stand-in as the real chain for a type without a definition
+        let definition = definitions.get_or_default(mushroom_type);
+
+        let used = uses.entry(position).or_default();
+        if *used >= upgrades.max_uses(mushroom_type, definition) {
+            continue;
+        }
+        if let Some(&until) = cooldown_until.get(&position).filter(|until| time < **until) {
+            // Wait for the cooldown like the real chain does
+            if until - time <= MAX_COOLDOWN_WAIT {
+                pending.push((until, position, energy, depth));
+            }
+            continue;
+        }
+        *used += 1;
+        cooldown_until.insert(position, time + definition.cooldown_time);
+
+        let tile_modifier = field
+            .get_tile(position)
+            .map(|t| t.production_multiplier())
+            .unwrap_or(1.0);
+        let mut production =
+            clamp_spores(definition.base_production * energy as f64 * tile_modifier as f64);
+
+        let mut targets: Vec<GridPosition> = field
+            .topology
+            .targets(position, definition, direction)
+            .into_iter()
+            .filter(|target| board.contains_key(target))
+            .collect();
+
+        let mut outgoing = energy * tile_modifier;
+        match &definition.activation_behavior {
+            ActivationBehavior::Deleter => {
+                for target in &targets {
+                    board.remove(target);
+                }
+                if !targets.is_empty() {
+                    connections = None;
+                }
+                production = clamp_spores(production * targets.len() as f64);
+                total = clamp_spores(total + production);
+                // Deleter does not propagate
+                continue;
+            }
+            ActivationBehavior::Unblocker {
+                clear_count,
+                bonus_per_tile,
+            } => {
+                let cleared = field
+                    .topology
+                    .targets(position, definition, direction)
+                    .into_iter()
+                    .filter(|target| {
+                        !cleared_tiles.contains(target)
+                            && field
+                                .get_tile(*target)
+                                .is_some_and(|tile| tile.can_be_cleared())
+                    })
+                    .take(*clear_count as usize)
+                    .collect::<Vec<_>>();
+                production = clamp_spores(production + bonus_per_tile * cleared.len() as f64);
+                cleared_tiles.extend(cleared);
+                total = clamp_spores(total + production);
+                // Unblocker does not propagate
+                continue;
+            }
+            ActivationBehavior::Spawner { spawn_count } => {
+                let sprouts = sprout_positions(field, position, *spawn_count, |cell| {
+                    !board.contains_key(&cell)
+                        && (cleared_tiles.contains(&cell)
+                            || field
+                                .get_tile(cell)
+                                .is_some_and(|tile| tile.allows_mushroom()))
+                });
+                // Sprouts are Basic mushrooms that share the spawner's energy
+                let sprout_energy = config.sprout_energy(energy, tile_modifier, sprouts.len());
+                for (i, cell) in sprouts.into_iter().enumerate() {
+                    board.insert(cell, (MushroomType::Basic, MushroomDirection::default()));
+                    connections = None;
+                    if depth < config.max_chain_depth {
+                        pending.push((
+                            time + 0.2 + i as f32 * 0.05,
+                            cell,
+                            sprout_energy,
+                            depth + 1,
+                        ));
+                    }
+                }
+            }
+            ActivationBehavior::Amplifier { boost_factor } => {
+                outgoing = (outgoing * boost_factor).min(MAX_ENERGY)
+            }
+            ActivationBehavior::Networked {
+                bonus_per_connection,
+            } => {
+                let connections = *connections
+                    .get_or_insert_with(|| count_connections(definitions, field, &board));
+                production = networked_production(production, *bonus_per_connection, connections)
+            }
+            ActivationBehavior::Capacitor { per_depth } => {
+                production =
+                    capacitor_production(definition, *per_depth, depth as usize, tile_modifier)
+            }
+            _ => {}
+        }
+        outgoing *= config.decay_per_hop;
+        total = clamp_spores(total + production);
+
+        if targets.is_empty() {
+            continue;
+        }
+        let split_energy = match definition.activation_behavior {
+            ActivationBehavior::Splitter { copies } => {
+                targets.truncate(copies as usize);
+                outgoing
+            }
+            _ => outgoing / targets.len() as f32,
+        };
+        // Same depth cap as the real chain
+        if depth >= config.max_chain_depth {
+            continue;
+        }
+        for (i, target) in targets.into_iter().enumerate() {
+            pending.push((
+                time + 0.2 + i as f32 * 0.05,
+                target,
+                split_energy,
+                depth + 1,
+            ));
+        }
+    }
+
+    total
+}
+
+/// Connections on the simulated board, counted the same way as on the play field
+fn count_connections(
+    definitions: &MushroomDefinitions,
+    field: &PlayField,
+    board: &SimulatedBoard,
+) -> usize {
+    let mushrooms: Vec<_> = board
+        .iter()
+        .map(|(position, (mushroom_type, direction))| (*position, *mushroom_type, *direction))
+        .collect();
+    mycelium::count_connections(definitions, field, &mushrooms)
+}
+
+/// Best chain score on the board and the position to start it from
+pub fn best_chain(
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+    config: &ChainConfig,
+    field: &PlayField,
+    board: &SimulatedBoard,
+) -> Option<(GridPosition, f64)> {
+    board
+        .keys()
+        .map(|start| {
+            (
+                *start,
+                simulate_chain(definitions, upgrades, config, field, board, *start),
+            )
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::play_field::TileType;
+
+    fn board(mushrooms: &[(i32, i32, MushroomType, MushroomDirection)]) -> SimulatedBoard {
+        mushrooms
+            .iter()
+            .map(|(x, y, mushroom_type, direction)| {
+                (GridPosition::new(*x, *y), (*mushroom_type, *direction))
+            })
+            .collect()
+    }
+
+    fn run(field: &PlayField, board: &SimulatedBoard, start: GridPosition) -> f64 {
+        simulate_chain(
+            &MushroomDefinitions::standard(),
+            &MushroomUpgrades::default(),
+            &ChainConfig::default(),
+            field,
+            board,
+            start,
+        )
+    }
+
+    #[test]
+    fn hub_production_grows_with_connections_on_the_board() {
+        let field = PlayField::new(5, 5);
+        let hub = GridPosition::new(2, 2);
+        let sparse = board(&[
+            (2, 2, MushroomType::Hub, MushroomDirection::Up),
+            (2, 3, MushroomType::Basic, MushroomDirection::Up),
+        ]);
+        let mut dense = sparse.clone();
+        // A separate pair, only the hub's connection count sees it
+        dense.extend(board(&[
+            (0, 0, MushroomType::Pulse, MushroomDirection::Up),
+            (0, 1, MushroomType::Basic, MushroomDirection::Up),
+        ]));
+
+        // Hub makes 2 * (1 + 0.1 per connection), the Basic it feeds makes 10
+        assert!((run(&field, &sparse, hub) - 12.2).abs() < 1e-9);
+        assert!((run(&field, &dense, hub) - 12.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn spawner_sprouts_join_the_chain() {
+        let field = PlayField::new(3, 3);
+        let spawner = GridPosition::new(1, 1);
+        let board = board(&[(1, 1, MushroomType::Spawner, MushroomDirection::Up)]);
+
+        // Spawner makes 3, its two sprouts get half the energy each and make 5
+        assert!((run(&field, &board, spawner) - 13.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn boxed_in_spawner_only_scores_itself() {
+        let mut field = PlayField::new(3, 3);
+        for cell in GridPosition::new(1, 1).adjacent_cardinal() {
+            field.set_tile(cell, TileType::BlockedRock);
+        }
+        let spawner = GridPosition::new(1, 1);
+        let board = board(&[(1, 1, MushroomType::Spawner, MushroomDirection::Up)]);
+
+        assert!((run(&field, &board, spawner) - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn undefined_type_scores_as_the_basic_stand_in() {
+        let field = PlayField::new(2, 1);
+        let board = board(&[(0, 0, MushroomType::Pulse, MushroomDirection::Up)]);
+
+        let total = simulate_chain(
+            &MushroomDefinitions::default(),
+            &MushroomUpgrades::default(),
+            &ChainConfig::default(),
+            &field,
+            &board,
+            GridPosition::new(0, 0),
+        );
+
+        assert_eq!(total, 1.0);
+    }
+
+    #[test]
+    fn amplifiers_feeding_each_other_stay_finite_and_capped() {
+        let field = PlayField::new(2, 1);
+        let board = board(&[
+            (0, 0, MushroomType::Sideways, MushroomDirection::Up),
+            (1, 0, MushroomType::Sideways, MushroomDirection::Up),
+        ]);
+        // Enough uses for the pair to bounce energy back and forth to the depth cap,
+        // doubling it every hop
+        let mut upgrades = MushroomUpgrades::default();
+        upgrades.load(&[(MushroomType::Sideways, 64)]);
+
+        let config = ChainConfig::default();
+        let total = simulate_chain(
+            &MushroomDefinitions::standard(),
+            &upgrades,
+            &config,
+            &field,
+            &board,
+            GridPosition::new(0, 0),
+        );
+
+        // Each activation makes at most 2 spores per unit of capped energy
+        let activations = (config.max_chain_depth + 1) as f64;
+        assert!(total.is_finite());
+        assert!(total >= 2.0 * MAX_ENERGY as f64);
+        assert!(total <= activations * 2.0 * MAX_ENERGY as f64);
+    }
+}
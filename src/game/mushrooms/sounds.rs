@@ -1,18 +1,81 @@
 use bevy::prelude::*;
 
 use crate::{
-    audio::sound_effect,
-    game::{level::assets::LevelAssets, mushrooms::events::SporeScoreEvent},
+    audio::{pitched_spatial_sound_effect, sound_effect, spatial_sound_effect},
+    game::{
+        level::assets::LevelAssets,
+        mushrooms::events::{ChainCompleteEvent, SporeScoreEvent},
+        resources::GameState,
+    },
+    screens::Screen,
 };
 
+/// Minimum time between activation sounds, so long chains don't stack dozens at once
+const ACTIVATION_SOUND_INTERVAL: f32 = 0.05;
+/// Playback speed added per hop of the chain, so deep chains climb in pitch
+const ACTIVATION_PITCH_PER_DEPTH: f32 = 0.04;
+/// Highest playback speed for the activation sound
+const MAX_ACTIVATION_PITCH: f32 = 1.6;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(chain_activate_sfx);
+    app.add_observer(chain_complete_sfx);
 }
 
+/// Play the activation sound from the mushroom's position on the board
 pub fn chain_activate_sfx(
-    _trigger: Trigger<SporeScoreEvent>,
+    trigger: Trigger<SporeScoreEvent>,
+    mut commands: Commands,
+    level_assets: Res<LevelAssets>,
+    game_state: Res<GameState>,
+    time: Res<Time>,
+    mut last_played: Local<Option<f32>>,
+) {
+    let now = time.elapsed_secs();
+    if last_played.is_some_and(|last| now - last < ACTIVATION_SOUND_INTERVAL) {
+        return;
+    }
+    *last_played = Some(now);
+
+    let world_pos = trigger.position.to_world_in(&game_state.play_field);
+    let pitch = (1.0 + trigger.depth as f32 * ACTIVATION_PITCH_PER_DEPTH).min(MAX_ACTIVATION_PITCH);
+
+    commands.spawn((
+        Name::new("Activation Sound"),
+        pitched_spatial_sound_effect(
+            level_assets.sfx_activate.clone(),
+            Vec3::new(world_pos.x, 0.5, -world_pos.z),
+            pitch,
+        ),
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+/// Play the placement sound where the player just planted a mushroom
+/// Called once the mushroom has spawned, `world_pos` is its grid world position
+pub(super) fn place_mushroom_sfx(
+    commands: &mut Commands,
+    level_assets: &LevelAssets,
+    world_pos: Vec3,
+) {
+    commands.spawn((
+        Name::new("Placement Sound"),
+        spatial_sound_effect(
+            level_assets.sfx_place.clone(),
+            Vec3::new(world_pos.x, 0.5, -world_pos.z),
+        ),
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn chain_complete_sfx(
+    _: Trigger<ChainCompleteEvent>,
     mut commands: Commands,
     level_assets: Res<LevelAssets>,
 ) {
-    commands.spawn(sound_effect(level_assets.sfx_activate.clone()));
+    commands.spawn((
+        Name::new("Chain Complete Sound"),
+        sound_effect(level_assets.sfx_chain_complete.clone()),
+        StateScoped(Screen::Gameplay),
+    ));
 }
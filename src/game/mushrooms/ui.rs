@@ -1,14 +1,21 @@
 //! Visual information display for mushrooms
 //! Add this as a new file: src/game/mushrooms/visual_info.rs
 
-use bevy::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
 use bevy_rich_text3d::{Text3d, TextAtlas};
 
-use crate::game::{
-    game_flow::LevelState,
-    mushrooms::{Mushroom, MushroomActivationState, MushroomDefinitions, events::SporeScoreEvent},
-    resources::GameState,
-    visual_effects::FaceCamera,
+use crate::{
+    game::{
+        game_flow::{LevelState, TurnPhase},
+        mushrooms::{
+            ChainManager, Mushroom, MushroomActivationState, MushroomDefinitions,
+            events::{ActivationEnergyEvent, SporeScoreEvent},
+            upgrades::MushroomUpgrades,
+        },
+        resources::{GameState, format_spores},
+        visual_effects::FaceCamera,
+    },
+    settings::ShowEnergyTags,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -17,10 +24,24 @@ pub(super) fn plugin(app: &mut App) {
         (animate_spore_popups, update_uses_display).run_if(in_state(LevelState::Playing)),
     );
 
+    app.add_systems(
+        Update,
+        update_pending_pips.run_if(in_state(TurnPhase::Chain)),
+    );
+    app.add_systems(OnExit(TurnPhase::Chain), clear_pending_pips);
+
     app.add_observer(spawn_spore_popup);
+    app.add_observer(spawn_energy_tag);
     app.add_observer(spawn_uses_display);
 }
 
+/// Badge over a mushroom with activations waiting in the chain queue
+#[derive(Component)]
+pub struct PendingActivationPip {
+    pub mushroom: Entity,
+    pub count: usize,
+}
+
 /// Component for the uses remaining display
 #[derive(Component)]
 pub struct UsesDisplay;
@@ -49,6 +70,7 @@ fn update_uses_display(
         Changed<MushroomActivationState>,
     >,
     definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
     mut uses_displays: Query<(Entity, &ChildOf), With<UsesDisplay>>,
     mut commands: Commands,
     mut materials: ResMut<Assets<StandardMaterial>>,
@@ -61,8 +83,8 @@ fn update_uses_display(
         };
 
         // Calculate remaining uses
-        let remaining_uses = definition
-            .max_uses_per_turn
+        let remaining_uses = upgrades
+            .max_uses(mushroom.0, definition)
             .saturating_sub(state.activations_this_turn);
 
         // Find uses displays that are children of this mushroom
@@ -95,11 +117,78 @@ fn update_uses_display(
     }
 }
 
+fn pip_text(count: usize) -> Text3d {
+    if count > 1 {
+        Text3d::new(format!("!{count}"))
+    } else {
+        Text3d::new("!")
+    }
+}
+
+/// Keep a pip over every mushroom that has a pending activation
+fn update_pending_pips(
+    chain_manager: Res<ChainManager>,
+    mut pips: Query<(Entity, &mut PendingActivationPip)>,
+    mushrooms: Query<(), With<Mushroom>>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut pending: HashMap<Entity, usize> = HashMap::default();
+    for activation in &chain_manager.activation_queue {
+        *pending.entry(activation.entity).or_default() += 1;
+    }
+
+    for (pip_entity, mut pip) in &mut pips {
+        match pending.remove(&pip.mushroom) {
+            Some(count) if count != pip.count => {
+                pip.count = count;
+                commands.entity(pip_entity).insert(pip_text(count));
+            }
+            Some(_) => {}
+            None => commands.entity(pip_entity).despawn(),
+        }
+    }
+
+    // Whatever is left has no pip yet
+    for (mushroom, count) in pending {
+        if !mushrooms.contains(mushroom) {
+            continue;
+        }
+
+        let mat = materials.add(StandardMaterial {
+            base_color_texture: Some(TextAtlas::DEFAULT_IMAGE.clone()),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            base_color: Color::srgb(1.0, 0.85, 0.2),
+            ..Default::default()
+        });
+
+        commands.spawn((
+            Name::new("Pending Activation Pip"),
+            pip_text(count),
+            Mesh3d::default(),
+            Transform::from_xyz(0.25, 0.5, 0.0).with_scale(Vec3::splat(0.019)),
+            MeshMaterial3d(mat),
+            StateScoped(LevelState::Playing),
+            PendingActivationPip { mushroom, count },
+            FaceCamera,
+            ChildOf(mushroom),
+        ));
+    }
+}
+
+fn clear_pending_pips(mut commands: Commands, pips: Query<Entity, With<PendingActivationPip>>) {
+    for entity in &pips {
+        commands.entity(entity).despawn();
+    }
+}
+
 /// Spawn uses display for mushrooms
 fn spawn_uses_display(
     trigger: Trigger<OnAdd, Mushroom>,
     mut commands: Commands,
     definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mushrooms: Query<(&MushroomActivationState, &Mushroom)>,
 ) {
@@ -111,8 +200,8 @@ fn spawn_uses_display(
 
     let definition = definitions.get(mushroom.0).unwrap();
 
-    let remaining_uses = definition
-        .max_uses_per_turn
+    let remaining_uses = upgrades
+        .max_uses(mushroom.0, definition)
         .saturating_sub(state.activations_this_turn);
 
     let mat = materials.add(StandardMaterial {
@@ -154,7 +243,7 @@ pub fn spawn_spore_popup(
 
     commands.spawn((
         Name::new("Spore Popup"),
-        Text3d::new(format!("+{:.0}", trigger.event().production)),
+        Text3d::new(format!("+{}", format_spores(trigger.event().production))),
         Mesh3d::default(),
         MeshMaterial3d(mat.clone()),
         Transform::from_xyz(world_pos.x, 1.0, -world_pos.z).with_scale(Vec3::splat(0.022)),
@@ -167,6 +256,44 @@ pub fn spawn_spore_popup(
     ));
 }
 
+/// Spawn a tag with the energy an activation arrived with, if energy tags are on
+fn spawn_energy_tag(
+    trigger: Trigger<ActivationEnergyEvent>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_state: Res<GameState>,
+    energy_tags: Res<ShowEnergyTags>,
+) {
+    if !energy_tags.0 {
+        return;
+    }
+
+    let mat = materials.add(StandardMaterial {
+        base_color_texture: Some(TextAtlas::DEFAULT_IMAGE.clone()),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        base_color: Color::srgb(0.4, 0.9, 1.0),
+        ..Default::default()
+    });
+
+    let world_pos = trigger.event().position.to_world_in(&game_state.play_field);
+
+    // Starts below the spore popup and floats up with it
+    commands.spawn((
+        Name::new("Energy Tag"),
+        Text3d::new(format!("x{:.2}", trigger.event().energy)),
+        Mesh3d::default(),
+        MeshMaterial3d(mat),
+        Transform::from_xyz(world_pos.x, 0.7, -world_pos.z).with_scale(Vec3::splat(0.016)),
+        FaceCamera,
+        StateScoped(LevelState::Playing),
+        SporePopup {
+            timer: Timer::from_seconds(1.5, TimerMode::Once),
+            start_y: 0.7,
+        },
+    ));
+}
+
 /// Animate spore popups - float up
 fn animate_spore_popups(
     mut commands: Commands,
@@ -0,0 +1,117 @@
+//! Mushroom upgrades earned between levels
+//!
+//! Completing a level lets the player level up one mushroom type, giving it an extra
+//! use per turn for the rest of the session. Definitions keep their base values, so
+//! anything that reads `max_uses_per_turn` during play goes through [`MushroomUpgrades`].
+//!
+//! Upgrades are saved with the rest of the run and restored when it's continued.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use super::{MushroomType, definitions::MushroomDefinition};
+
+/// How many mushroom types the level complete screen offers to level up
+pub const UPGRADE_CHOICES: usize = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MushroomUpgrades>();
+    app.add_observer(apply_upgrade);
+}
+
+/// Extra uses per turn bought for each mushroom type
+#[derive(Resource, Default, Debug)]
+pub struct MushroomUpgrades {
+    extra_uses: HashMap<MushroomType, u32>,
+}
+
+impl MushroomUpgrades {
+    /// Uses per turn for a mushroom type, with its upgrades applied
+    pub fn max_uses(&self, mushroom_type: MushroomType, definition: &MushroomDefinition) -> u32 {
+        definition.max_uses_per_turn + self.level(mushroom_type)
+    }
+
+    /// Number of times a mushroom type has been levelled up
+    pub fn level(&self, mushroom_type: MushroomType) -> u32 {
+        self.extra_uses.get(&mushroom_type).copied().unwrap_or(0)
+    }
+
+    /// Levels to write to the save, by type
+    pub fn to_saved(&self) -> Vec<(MushroomType, u32)> {
+        let mut levels: Vec<_> = self
+            .extra_uses
+            .iter()
+            .filter(|(_, level)| **level > 0)
+            .map(|(mushroom_type, level)| (*mushroom_type, *level))
+            .collect();
+        levels.sort();
+        levels
+    }
+
+    /// Replace the upgrades with the levels read from a save
+    pub fn load(&mut self, levels: &[(MushroomType, u32)]) {
+        self.extra_uses = levels.iter().copied().collect();
+    }
+}
+
+/// Level up a mushroom type by one use per turn
+#[derive(Event, Debug, Clone, Copy)]
+pub struct UpgradeMushroom(pub MushroomType);
+
+fn apply_upgrade(trigger: Trigger<UpgradeMushroom>, mut upgrades: ResMut<MushroomUpgrades>) {
+    let mushroom_type = trigger.event().0;
+    *upgrades.extra_uses.entry(mushroom_type).or_default() += 1;
+    info!(
+        "Upgraded {:?} to level {}",
+        mushroom_type,
+        upgrades.level(mushroom_type)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::mushrooms::MushroomDefinitions;
+
+    #[test]
+    fn upgrades_add_to_the_definition_uses() {
+        let definitions = MushroomDefinitions::standard();
+        let pulse = definitions.get(MushroomType::Pulse).unwrap();
+        let basic = definitions.get(MushroomType::Basic).unwrap();
+
+        let mut world = World::new();
+        world.init_resource::<MushroomUpgrades>();
+        world.add_observer(apply_upgrade);
+        world.trigger(UpgradeMushroom(MushroomType::Pulse));
+        world.trigger(UpgradeMushroom(MushroomType::Pulse));
+
+        let upgrades = world.resource::<MushroomUpgrades>();
+        assert_eq!(upgrades.level(MushroomType::Pulse), 2);
+        assert_eq!(
+            upgrades.max_uses(MushroomType::Pulse, pulse),
+            pulse.max_uses_per_turn + 2
+        );
+        // Other types and the definitions themselves are left alone
+        assert_eq!(
+            upgrades.max_uses(MushroomType::Basic, basic),
+            basic.max_uses_per_turn
+        );
+        assert_eq!(pulse.max_uses_per_turn, 3);
+    }
+
+    #[test]
+    fn saved_upgrades_are_restored() {
+        let mut upgrades = MushroomUpgrades::default();
+        upgrades.load(&[(MushroomType::Splitter, 1), (MushroomType::Basic, 3)]);
+
+        let mut restored = MushroomUpgrades::default();
+        restored.load(&upgrades.to_saved());
+
+        assert_eq!(restored.level(MushroomType::Basic), 3);
+        assert_eq!(restored.level(MushroomType::Splitter), 1);
+        assert_eq!(restored.level(MushroomType::Pulse), 0);
+        assert_eq!(
+            upgrades.to_saved(),
+            vec![(MushroomType::Basic, 3), (MushroomType::Splitter, 1)]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Ambient spores falling over the field
+//!
+//! The closer the level is to its spore target, the more spores drift down,
+//! so the field comes alive as the player nears the goal.
+
+use bevy::prelude::*;
+use bevy_hanabi::{EffectSpawner, ParticleEffect, SpawnerSettings};
+
+use super::ParticleEffects;
+use crate::{
+    game::game_flow::{CurrentLevel, LevelState},
+    settings::{ParticleQuality, ReducedMotion},
+};
+
+/// Spores per second once the target is reached
+pub const MAX_AMBIENT_SPORE_RATE: f32 = 40.0;
+
+/// How often the spawn rate follows the score, in seconds
+const RATE_UPDATE_INTERVAL: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(LevelState::Playing), spawn_ambient_spores);
+    app.add_systems(
+        Update,
+        update_ambient_spore_rate.run_if(in_state(LevelState::Playing)),
+    );
+}
+
+/// Marker for the ambient spore emitter
+#[derive(Component)]
+struct AmbientSpores;
+
+fn spawn_ambient_spores(mut commands: Commands, particle_effects: Res<ParticleEffects>) {
+    commands.spawn((
+        Name::new("Ambient Spores"),
+        AmbientSpores,
+        ParticleEffect::new(particle_effects.ambient_spores.clone()),
+        Transform::default(),
+        StateScoped(LevelState::Playing),
+    ));
+}
+
+fn update_ambient_spore_rate(
+    time: Res<Time>,
+    current_level: Res<CurrentLevel>,
+    reduced_motion: Res<ReducedMotion>,
+    particle_quality: Res<ParticleQuality>,
+    mut spawners: Query<&mut EffectSpawner, With<AmbientSpores>>,
+    mut since_update: Local<f32>,
+) {
+    *since_update += time.delta_secs();
+    let settings_changed = reduced_motion.is_changed() || particle_quality.is_changed();
+    if *since_update < RATE_UPDATE_INTERVAL && !settings_changed {
+        return;
+    }
+    *since_update = 0.0;
+
+    let particles_off = *particle_quality == ParticleQuality::Off;
+    let density = if reduced_motion.0 || particles_off || current_level.target_score <= 0.0 {
+        0.0
+    } else {
+        let progress = current_level.total_spores_earned / current_level.target_score;
+        progress.clamp(0.0, 1.0) as f32 * particle_quality.particle_scale()
+    };
+
+    for mut spawner in &mut spawners {
+        spawner.settings = SpawnerSettings::rate((MAX_AMBIENT_SPORE_RATE * density).into());
+    }
+}
@@ -1,11 +1,18 @@
 use bevy::prelude::*;
 use bevy_hanabi::prelude::*;
 
-pub fn activate_effect() -> EffectAsset {
-    // Define a color gradient from red to transparent black
+/// Particle capacity for an effect at the given quality scale, never empty
+fn scaled_capacity(capacity: u32, particle_scale: f32) -> u32 {
+    ((capacity as f32 * particle_scale) as u32).max(1)
+}
+
+pub fn activate_effect(color: Color, particle_scale: f32) -> EffectAsset {
+    // Define a color gradient from the mushroom's color, fading out towards white
+    let start = color.to_linear().to_vec3();
+    let end = start.lerp(Vec3::ONE, 0.5);
     let mut gradient = Gradient::new();
-    gradient.add_key(0.0, Vec4::new(1., 1., 0., 0.7));
-    gradient.add_key(1.0, Vec4::new(1., 1., 0.5, 0.0));
+    gradient.add_key(0.0, start.extend(0.7));
+    gradient.add_key(1.0, end.extend(0.0));
 
     // Create a new expression module
     let mut module = Module::default();
@@ -38,9 +45,9 @@ pub fn activate_effect() -> EffectAsset {
     // Create the effect asset
     EffectAsset::new(
         // Maximum number of particles alive at a time
-        600,
+        scaled_capacity(600, particle_scale),
         // Spawn at a rate of 5 particles per second
-        SpawnerSettings::rate(600.0.into()),
+        SpawnerSettings::rate((600.0 * particle_scale).into()),
         // Move the expression module into the asset
         module,
     )
@@ -62,7 +69,7 @@ pub fn activate_effect() -> EffectAsset {
     })
 }
 
-pub fn delete_effect() -> EffectAsset {
+pub fn delete_effect(particle_scale: f32) -> EffectAsset {
     // Define a color gradient from red to transparent black
     let mut gradient = Gradient::new();
     gradient.add_key(0.0, Vec4::new(1., 0.2, 0., 0.7));
@@ -99,9 +106,9 @@ pub fn delete_effect() -> EffectAsset {
     // Create the effect asset
     EffectAsset::new(
         // Maximum number of particles alive at a time
-        600,
+        scaled_capacity(600, particle_scale),
         // Spawn at a rate of 5 particles per second
-        SpawnerSettings::rate(600.0.into()),
+        SpawnerSettings::rate((600.0 * particle_scale).into()),
         // Move the expression module into the asset
         module,
     )
@@ -123,7 +130,7 @@ pub fn delete_effect() -> EffectAsset {
     })
 }
 
-pub fn tile_conversion_effect() -> EffectAsset {
+pub fn tile_conversion_effect(particle_scale: f32) -> EffectAsset {
     // Define a color gradient from red to transparent black
     let mut gradient = Gradient::new();
     gradient.add_key(1.0, Vec4::new(0.0, 0.6, 0.8, 0.7));
@@ -160,9 +167,9 @@ pub fn tile_conversion_effect() -> EffectAsset {
     // Create the effect asset
     EffectAsset::new(
         // Maximum number of particles alive at a time
-        600,
+        scaled_capacity(600, particle_scale),
         // Spawn at a rate of 5 particles per second
-        SpawnerSettings::rate(300.0.into()),
+        SpawnerSettings::rate((300.0 * particle_scale).into()),
         // Move the expression module into the asset
         module,
     )
@@ -183,3 +190,52 @@ pub fn tile_conversion_effect() -> EffectAsset {
         ..default()
     })
 }
+
+/// Slow falling spores drifting down over the whole field
+pub fn ambient_spores_effect(max_rate: f32) -> EffectAsset {
+    // Faint warm yellow, fading in and out so particles don't pop
+    let mut gradient = Gradient::new();
+    gradient.add_key(0.0, Vec4::new(1.0, 0.95, 0.6, 0.0));
+    gradient.add_key(0.2, Vec4::new(1.0, 0.95, 0.6, 0.5));
+    gradient.add_key(1.0, Vec4::new(1.0, 0.95, 0.6, 0.0));
+
+    let writer = ExprWriter::new();
+
+    // Anywhere in a flat box above the field
+    let init_pos = SetAttributeModifier::new(
+        Attribute::POSITION,
+        (writer.rand(VectorType::VEC3F) * writer.lit(Vec3::new(12.0, 1.0, 12.0))
+            + writer.lit(Vec3::new(-6.0, 4.0, -6.0)))
+        .expr(),
+    );
+
+    // Drift slowly down with a little sideways wander
+    let init_vel = SetAttributeModifier::new(
+        Attribute::VELOCITY,
+        ((writer.rand(VectorType::VEC3F) - writer.lit(Vec3::splat(0.5)))
+            * writer.lit(Vec3::new(0.3, 0.0, 0.3))
+            + writer.lit(Vec3::new(0.0, -0.5, 0.0)))
+        .expr(),
+    );
+
+    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, writer.lit(8.0).expr());
+
+    EffectAsset::new(
+        // Enough for the full rate over a whole lifetime
+        (max_rate * 8.0) as u32,
+        SpawnerSettings::rate(max_rate.into()),
+        writer.finish(),
+    )
+    .with_name("AmbientSpores")
+    .init(init_pos)
+    .init(init_vel)
+    .init(init_lifetime)
+    .render(SizeOverLifetimeModifier {
+        gradient: Gradient::constant(Vec3::ONE * 0.04),
+        screen_space_size: false,
+    })
+    .render(ColorOverLifetimeModifier {
+        gradient,
+        ..default()
+    })
+}
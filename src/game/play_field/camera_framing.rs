@@ -0,0 +1,71 @@
+//! Frame the placed mushrooms with the camera
+//!
+//! Press F to move the camera focus to the middle of the placed mushrooms and zoom
+//! so that all of them fit on screen. With nothing placed it frames the whole grid.
+//! The camera eases there on its own, since only its targets are changed.
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use super::{CELL_SIZE, GridPosition};
+use crate::{
+    MainCamera,
+    game::{game_flow::LevelState, mushrooms::Mushroom, resources::GameState},
+};
+
+const FRAME_KEY: KeyCode = KeyCode::KeyF;
+/// Extra room around the framed area, as a multiple of its size
+const FRAMING_MARGIN: f32 = 1.4;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        frame_mushrooms.run_if(in_state(LevelState::Playing)),
+    );
+}
+
+fn frame_mushrooms(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    game_state: Res<GameState>,
+    mushrooms: Query<&GridPosition, With<Mushroom>>,
+    mut cameras: Query<(&mut PanOrbitCamera, &Projection), With<MainCamera>>,
+) {
+    if !keyboard.just_pressed(FRAME_KEY) {
+        return;
+    }
+
+    let field = &game_state.play_field;
+    let (min, max) = mushrooms
+        .iter()
+        .map(|position| position.to_world_in(field))
+        .fold(None, |bounds: Option<(Vec3, Vec3)>, point| {
+            Some(match bounds {
+                Some((min, max)) => (min.min(point), max.max(point)),
+                None => (point, point),
+            })
+        })
+        // Nothing placed, frame the grid around its centre
+        .unwrap_or_else(|| {
+            let (width, height) = field.world_size();
+            let half = Vec3::new(width, 0.0, height) * 0.5;
+            (-half, half)
+        });
+
+    let focus = (min + max) * 0.5;
+    // Half the diagonal, padded so edge mushrooms aren't right at the border
+    let extent = ((max - min).length() * 0.5 + CELL_SIZE) * FRAMING_MARGIN;
+
+    for (mut camera, projection) in &mut cameras {
+        let fov = match projection {
+            Projection::Perspective(perspective) => perspective.fov,
+            _ => std::f32::consts::FRAC_PI_4,
+        };
+        let radius = (extent / (fov * 0.5).tan()).max(camera.zoom_lower_limit);
+        let radius = camera
+            .zoom_upper_limit
+            .map_or(radius, |upper| radius.min(upper));
+
+        camera.target_focus = focus;
+        camera.target_radius = radius;
+    }
+}
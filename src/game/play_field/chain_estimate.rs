@@ -0,0 +1,139 @@
+//! Chain output estimate
+//!
+//! During the chain phase, hovering a mushroom shows how many spores a chain started
+//! from it would produce, next to the cursor. The estimate comes from the dry-run
+//! simulation, counting uses already spent by earlier chains this turn.
+
+use bevy::{
+    platform::collections::HashMap, prelude::*, text::FontSmoothing, window::PrimaryWindow,
+};
+
+use super::{GridPosition, placement_preview::HoveredCell};
+use crate::{
+    game::{
+        game_flow::TurnPhase,
+        mushrooms::{
+            ChainManager, Mushroom, MushroomActivationState, MushroomDefinitions,
+            MushroomDirection,
+            chain_activation::ChainConfig,
+            simulation::{SimulatedBoard, simulate_chain_after},
+            upgrades::MushroomUpgrades,
+        },
+        resources::{GameState, format_spores},
+        ui::GameplayUI,
+    },
+    theme::palette as ui_palette,
+};
+
+/// Gap between the cursor and the estimate, in pixels
+const CURSOR_OFFSET: Vec2 = Vec2::new(18.0, 18.0);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_chain_estimate.run_if(in_state(TurnPhase::Chain)),
+    );
+}
+
+/// Marker for the estimate text
+#[derive(Component)]
+struct ChainEstimate;
+
+fn update_chain_estimate(
+    mut commands: Commands,
+    hovered_cell: Res<HoveredCell>,
+    chain_manager: Res<ChainManager>,
+    game_state: Res<GameState>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    chain_config: Res<ChainConfig>,
+    mushrooms: Query<(
+        &GridPosition,
+        &Mushroom,
+        Option<&MushroomDirection>,
+        &MushroomActivationState,
+    )>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut estimates: Query<(Entity, &mut Text, &mut Node), With<ChainEstimate>>,
+    asset_server: Res<AssetServer>,
+    mut cached: Local<Option<(GridPosition, f64)>>,
+) {
+    // Only while the player can still start a chain
+    let can_start = chain_manager.chains_remaining > 0 && !chain_manager.has_active_chains();
+    let start = hovered_cell
+        .position
+        .filter(|position| can_start && game_state.play_field.get(*position).is_some());
+    let (Some(start), Some(cursor)) = (start, window.cursor_position()) else {
+        for (entity, ..) in &estimates {
+            commands.entity(entity).despawn();
+        }
+        *cached = None;
+        return;
+    };
+
+    let stale = chain_manager.is_changed() || game_state.is_changed();
+    let spores = match *cached {
+        Some((position, spores)) if position == start && !stale => spores,
+        _ => {
+            let mut board = SimulatedBoard::default();
+            let mut uses_so_far = HashMap::default();
+            for (position, mushroom, direction, state) in &mushrooms {
+                board.insert(
+                    *position,
+                    (mushroom.0, direction.copied().unwrap_or_default()),
+                );
+                uses_so_far.insert(*position, state.activations_this_turn);
+            }
+            let spores = simulate_chain_after(
+                &definitions,
+                &upgrades,
+                &chain_config,
+                &game_state.play_field,
+                &board,
+                start,
+                &uses_so_far,
+            );
+            *cached = Some((start, spores));
+            spores
+        }
+    };
+
+    let text = format!("~{} spores", format_spores(spores));
+    // The cursor is in logical pixels, UI pixels are scaled on top of that
+    let offset = (cursor + CURSOR_OFFSET) / ui_scale.0.max(f32::EPSILON);
+
+    if let Ok((_, mut label, mut node)) = estimates.single_mut() {
+        if label.0 != text {
+            label.0 = text;
+        }
+        if node.left != Val::Px(offset.x) || node.top != Val::Px(offset.y) {
+            node.left = Val::Px(offset.x);
+            node.top = Val::Px(offset.y);
+        }
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Chain Estimate"),
+        ChainEstimate,
+        Text::new(text),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 18.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(ui_palette::LABEL_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(offset.x),
+            top: Val::Px(offset.y),
+            ..default()
+        },
+        GlobalZIndex(5),
+        Pickable::IGNORE,
+        GameplayUI,
+        StateScoped(TurnPhase::Chain),
+    ));
+}
@@ -0,0 +1,102 @@
+//! Cinematic camera for chain resolution
+//!
+//! Press V to toggle. While on, the camera slowly orbits the field centre during the
+//! chain phase, with player camera control disabled. Control comes back as soon as the
+//! chain phase ends or the mode is toggled off, with the focus and zoom the player had
+//! before the orbit started.
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    MainCamera,
+    game::game_flow::{LevelState, TurnPhase},
+};
+
+const TOGGLE_KEY: KeyCode = KeyCode::KeyV;
+/// Orbit speed in radians per second
+const ORBIT_SPEED: f32 = 0.25;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CinematicCamera>();
+    app.add_systems(
+        Update,
+        (toggle_cinematic, orbit_camera)
+            .chain()
+            .run_if(in_state(LevelState::Playing)),
+    );
+    app.add_systems(OnExit(TurnPhase::Chain), restore_camera_control);
+    app.add_systems(OnExit(LevelState::Playing), restore_camera_control);
+}
+
+/// Whether the camera orbits on its own during the chain phase
+#[derive(Resource, Default, Debug)]
+pub struct CinematicCamera {
+    pub enabled: bool,
+    /// Focus and radius the camera had before orbiting, put back when control returns
+    saved_view: Option<(Vec3, f32)>,
+}
+
+fn toggle_cinematic(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut cinematic: ResMut<CinematicCamera>,
+    mut cameras: Query<&mut PanOrbitCamera, With<MainCamera>>,
+) {
+    if !keyboard.just_pressed(TOGGLE_KEY) {
+        return;
+    }
+
+    cinematic.enabled = !cinematic.enabled;
+    info!(
+        "Cinematic camera {}",
+        if cinematic.enabled { "on" } else { "off" }
+    );
+    if !cinematic.enabled {
+        hand_back_control(&mut cinematic, &mut cameras);
+    }
+}
+
+fn orbit_camera(
+    time: Res<Time>,
+    mut cinematic: ResMut<CinematicCamera>,
+    current_phase: Option<Res<State<TurnPhase>>>,
+    mut cameras: Query<&mut PanOrbitCamera, With<MainCamera>>,
+) {
+    let in_chain = current_phase.is_some_and(|phase| *phase.get() == TurnPhase::Chain);
+    if !cinematic.enabled || !in_chain {
+        return;
+    }
+
+    for mut camera in &mut cameras {
+        cinematic
+            .saved_view
+            .get_or_insert((camera.target_focus, camera.target_radius));
+        // Turning the camera off stops player input, targets still apply
+        camera.enabled = false;
+        camera.target_focus = Vec3::ZERO;
+        camera.target_yaw += ORBIT_SPEED * time.delta_secs();
+        // Pitch and zoom are left alone, so they stay within the camera's limits
+    }
+}
+
+fn restore_camera_control(
+    mut cinematic: ResMut<CinematicCamera>,
+    mut cameras: Query<&mut PanOrbitCamera, With<MainCamera>>,
+) {
+    hand_back_control(&mut cinematic, &mut cameras);
+}
+
+/// Give the camera back to the player, looking where it did before the orbit
+fn hand_back_control(
+    cinematic: &mut CinematicCamera,
+    cameras: &mut Query<&mut PanOrbitCamera, With<MainCamera>>,
+) {
+    let saved_view = cinematic.saved_view.take();
+    for mut camera in cameras.iter_mut() {
+        camera.enabled = true;
+        if let Some((focus, radius)) = saved_view {
+            camera.target_focus = focus;
+            camera.target_radius = radius;
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Connection graph dump
+//!
+//! Dev builds only. Press F12 while playing to log the play field's connection graph:
+//! every node with its index, then every edge with its strength.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::game::{game_flow::LevelState, resources::GameState};
+
+const DUMP_KEY: KeyCode = KeyCode::F12;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        dump_connection_graph
+            .run_if(input_just_pressed(DUMP_KEY).and(in_state(LevelState::Playing))),
+    );
+}
+
+fn dump_connection_graph(game_state: Res<GameState>) {
+    let (nodes, edges) = game_state.play_field.export_graph();
+    info!(
+        "Connection graph: {} nodes, {} edges",
+        nodes.len(),
+        edges.len()
+    );
+    for (index, position) in nodes.iter().enumerate() {
+        info!("  node {index}: ({}, {})", position.x, position.y);
+    }
+    for (from, to, strength) in edges {
+        info!("  edge {from} -> {to}: strength {strength:.2}");
+    }
+}
@@ -1,9 +1,14 @@
 //! The structure that represents our play field
 //! It contains a spatial index mapping grid positions to entities
 
-use bevy::{platform::collections::HashMap, prelude::*};
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use std::hash::{DefaultHasher, Hash, Hasher};
 
-use super::{GridPosition, TileType};
+use super::{GridPosition, TileType, topology::GridTopology};
+use crate::game::mushrooms::{MushroomDirection, MushroomType};
 
 /// The size of each cell in the grid
 pub const CELL_SIZE: f32 = 1.0;
@@ -20,6 +25,8 @@ pub struct Connection {
     pub strength: f32,
     pub active: bool,            // Currently pulsing with energy
     pub path: Vec<GridPosition>, // Path the mycelium takes
+    pub bidirectional: bool,     // Both mushrooms connect to each other
+    pub last_energy: f32,        // Energy of the latest activation along this edge
 }
 
 /// The play field containing the spatial index and bounds
@@ -35,6 +42,12 @@ pub struct PlayField {
     pub width: i32,
     /// Height of the field
     pub height: i32,
+    /// Cells cut out of the field, they have no tile and can't hold anything
+    holes: HashSet<GridPosition>,
+    /// Seed mixed into tile sprite variants, so layouts look different between runs
+    pub render_seed: u32,
+    /// How cells neighbour each other
+    pub topology: GridTopology,
 }
 
 impl Default for PlayField {
@@ -52,12 +65,29 @@ impl PlayField {
             tiles: vec![TileType::Empty; (width * height) as usize],
             width,
             height,
+            holes: HashSet::default(),
+            render_seed: 0,
+            topology: GridTopology::default(),
         }
     }
 
-    /// Check if a position is within bounds
+    /// Check if a position is within bounds and not a hole
     pub fn contains(&self, position: GridPosition) -> bool {
-        position.x >= 0 && position.x < self.width && position.y >= 0 && position.y < self.height
+        position.x >= 0
+            && position.x < self.width
+            && position.y >= 0
+            && position.y < self.height
+            && !self.holes.contains(&position)
+    }
+
+    /// Whether a position inside the bounds is cut out of the field
+    pub fn is_hole(&self, position: GridPosition) -> bool {
+        self.holes.contains(&position)
+    }
+
+    /// Cut cells out of the field, replacing any earlier holes
+    pub fn set_holes(&mut self, holes: &[GridPosition]) {
+        self.holes = holes.iter().copied().collect();
     }
 
     /// Get the entity at a position
@@ -127,6 +157,8 @@ impl PlayField {
         self.tiles = new_tiles;
         self.width = new_width;
         self.height = new_height;
+        self.holes
+            .retain(|position| position.x < new_width && position.y < new_height);
     }
 
     /// Get total cell size including spacing
@@ -154,7 +186,33 @@ impl PlayField {
         &self.connections
     }
 
+    /// Hash of the field size, tiles, holes and the given mushrooms, equal for identical boards
+    ///
+    /// Mushrooms can come in any order. Entities and connections are left out, so a
+    /// rebuilt copy of a board matches the original.
+    pub fn fingerprint(
+        &self,
+        mushrooms: impl IntoIterator<Item = (GridPosition, MushroomType, MushroomDirection)>,
+    ) -> u64 {
+        let mut mushrooms: Vec<_> = mushrooms.into_iter().collect();
+        mushrooms.sort_by_key(|(position, _, _)| (position.y, position.x));
+
+        // Fixed keys, so fingerprints stay the same between runs
+        let mut hasher = DefaultHasher::new();
+        self.width.hash(&mut hasher);
+        self.height.hash(&mut hasher);
+        self.tiles.hash(&mut hasher);
+        let mut holes: Vec<_> = self.holes.iter().collect();
+        holes.sort_by_key(|position| (position.y, position.x));
+        holes.hash(&mut hasher);
+        mushrooms.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Add a mycelium connection between two mushrooms
+    /// If the pair is already connected the existing connection is kept, and a reverse
+    /// connection marks it as bidirectional instead of adding a second line
+    /// Returns true if a new connection was added
     pub fn add_connection(
         &mut self,
         from_pos: GridPosition,
@@ -163,7 +221,21 @@ impl PlayField {
         to_entity: Entity,
         strength: f32,
         path: Vec<GridPosition>,
-    ) {
+    ) -> bool {
+        if let Some(existing) = self.connections.iter_mut().find(|c| {
+            (c.from_pos == from_pos && c.to_pos == to_pos)
+                || (c.from_pos == to_pos && c.to_pos == from_pos)
+        }) {
+            if existing.from_pos == to_pos {
+                existing.bidirectional = true;
+                info!(
+                    "Merged reverse connection from {:?} to {:?} into a bidirectional link",
+                    from_pos, to_pos
+                );
+            }
+            return false;
+        }
+
         self.connections.push(Connection {
             from_pos,
             to_pos,
@@ -172,10 +244,188 @@ impl PlayField {
             strength,
             active: false,
             path,
+            bidirectional: false,
+            last_energy: 0.0,
         });
         info!(
             "Added mycelium connection from {:?} to {:?} with strength {}",
             from_pos, to_pos, strength
         );
+        true
+    }
+
+    /// Remember the energy that just travelled between two mushrooms, in either direction
+    pub fn record_edge_energy(&mut self, from_entity: Entity, to_entity: Entity, energy: f32) {
+        if let Some(connection) = self.connections.iter_mut().find(|c| {
+            (c.from_entity == from_entity && c.to_entity == to_entity)
+                || (c.from_entity == to_entity && c.to_entity == from_entity)
+        }) {
+            connection.last_energy = energy;
+        }
+    }
+
+    /// Forget the energy recorded on every edge, so lines go back to their resting width
+    pub fn reset_edge_energy(&mut self) {
+        for connection in &mut self.connections {
+            connection.last_energy = 0.0;
+        }
+    }
+
+    /// Export the connection graph as nodes and weighted edges
+    /// Nodes are every occupied position plus any connection endpoint, sorted by (y, x)
+    /// Edges are `(from_index, to_index, strength)` into the node list, a bidirectional
+    /// connection exports one edge each way
+    // Release builds have no caller, only tests and the dev F12 dump
+    #[cfg_attr(not(feature = "dev"), allow(dead_code))]
+    pub fn export_graph(&self) -> (Vec<GridPosition>, Vec<(usize, usize, f32)>) {
+        let mut nodes: Vec<GridPosition> = self
+            .entities
+            .keys()
+            .copied()
+            .chain(self.connections.iter().flat_map(|c| [c.from_pos, c.to_pos]))
+            .collect();
+        nodes.sort_by_key(|pos| (pos.y, pos.x));
+        nodes.dedup();
+
+        let index_of = |pos: GridPosition| {
+            nodes
+                .binary_search_by_key(&(pos.y, pos.x), |p| (p.y, p.x))
+                .ok()
+        };
+
+        let mut edges = Vec::new();
+        for c in &self.connections {
+            let (Some(from), Some(to)) = (index_of(c.from_pos), index_of(c.to_pos)) else {
+                continue;
+            };
+            edges.push((from, to, c.strength));
+            if c.bidirectional {
+                edges.push((to, from, c.strength));
+            }
+        }
+
+        (nodes, edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::play_field::test_support::{at, connected_field};
+
+    #[test]
+    fn mutually_connecting_mushrooms_merge_into_one_connection() {
+        let field = connected_field(
+            PlayField::new(3, 3),
+            &[
+                at(1, 0, MushroomType::Pulse),
+                (
+                    GridPosition::new(1, 1),
+                    MushroomType::Pulse,
+                    MushroomDirection::Down,
+                ),
+            ],
+        );
+
+        assert_eq!(field.connections.len(), 1);
+        let connection = &field.connections[0];
+        assert_eq!(connection.from_pos, GridPosition::new(1, 0));
+        assert_eq!(connection.to_pos, GridPosition::new(1, 1));
+        assert!(connection.bidirectional);
+    }
+
+    #[test]
+    fn pulse_into_basic_exports_one_edge() {
+        let field = connected_field(
+            PlayField::new(3, 3),
+            &[
+                at(1, 0, MushroomType::Pulse),
+                at(1, 1, MushroomType::Basic),
+                // Nothing targets it, it's still a node
+                at(0, 2, MushroomType::Basic),
+            ],
+        );
+
+        let (nodes, edges) = field.export_graph();
+        assert_eq!(
+            nodes,
+            vec![
+                GridPosition::new(1, 0),
+                GridPosition::new(1, 1),
+                GridPosition::new(0, 2),
+            ]
+        );
+        assert_eq!(edges, vec![(0, 1, 1.0)]);
+    }
+
+    #[test]
+    fn mushrooms_facing_each_other_export_an_edge_each_way() {
+        let field = connected_field(
+            PlayField::new(3, 3),
+            &[
+                at(1, 0, MushroomType::Pulse),
+                (
+                    GridPosition::new(1, 1),
+                    MushroomType::Pulse,
+                    MushroomDirection::Down,
+                ),
+            ],
+        );
+
+        let (nodes, edges) = field.export_graph();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(edges, vec![(0, 1, 1.0), (1, 0, 1.0)]);
+    }
+
+    #[test]
+    fn detour_around_rock_exports_weaker_edge() {
+        let mut field = PlayField::new(3, 3);
+        field.set_tile(GridPosition::new(1, 1), TileType::BlockedRock);
+        let field = connected_field(
+            field,
+            &[
+                at(1, 0, MushroomType::Skipper),
+                at(1, 2, MushroomType::Basic),
+            ],
+        );
+
+        let (nodes, edges) = field.export_graph();
+        assert_eq!(
+            nodes,
+            vec![GridPosition::new(1, 0), GridPosition::new(1, 2)]
+        );
+        assert_eq!(edges.len(), 1);
+        let (from, to, strength) = edges[0];
+        assert_eq!((from, to), (0, 1));
+        assert!(strength > 0.0 && strength < 1.0);
+    }
+
+    #[test]
+    fn fingerprint_matches_identical_boards_only() {
+        let board = || {
+            let mut field = PlayField::new(4, 4);
+            field.set_tile(GridPosition::new(2, 2), TileType::Fertile);
+            field.set_holes(&[GridPosition::new(3, 3), GridPosition::new(0, 3)]);
+            field
+        };
+        let mushrooms = [at(0, 0, MushroomType::Pulse), at(1, 0, MushroomType::Basic)];
+
+        // A rebuilt copy with the mushrooms listed the other way round still matches
+        let mut reversed = mushrooms;
+        reversed.reverse();
+        let original = board().fingerprint(mushrooms);
+        assert_eq!(original, board().fingerprint(reversed));
+
+        let mut retiled = board();
+        retiled.set_tile(GridPosition::new(2, 2), TileType::Nutrient);
+        assert_ne!(original, retiled.fingerprint(mushrooms));
+
+        let mut turned = mushrooms;
+        turned[0].2 = MushroomDirection::Right;
+        assert_ne!(original, board().fingerprint(turned));
+
+        let mut moved_hole = board();
+        moved_hole.set_holes(&[GridPosition::new(3, 3), GridPosition::new(1, 3)]);
+        assert_ne!(original, moved_hole.fingerprint(mushrooms));
     }
 }
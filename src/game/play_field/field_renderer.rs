@@ -4,19 +4,30 @@
 //! - Grid coordinates: Y=0 at TOP, Y increases DOWNWARD
 //! - Texture coordinates: Y=0 at BOTTOM, Y increases UPWARD  
 
+use super::field::Connection;
+use super::mycelium::ConnectionsFormed;
 use super::tile_atlas::TileSprite;
 use super::{GridPosition, PlayField, TileType};
 use crate::game::game_flow::LevelLifecycle;
 use crate::game::level::assets::LevelAssets;
 use crate::game::resources::GameState;
+use crate::settings::{ColorblindMode, ConnectionWidthMode, ReducedMotion};
 use bevy::{
     pbr::{ExtendedMaterial, MaterialExtension},
+    platform::collections::HashSet,
     prelude::*,
     render::render_asset::RenderAssetUsages,
     render::render_resource::{AsBindGroup, ShaderRef, ShaderType},
     render::storage::ShaderStorageBuffer,
 };
 
+/// Width scale for connections no energy has travelled along yet
+const MIN_DYNAMIC_WIDTH_SCALE: f32 = 0.75;
+/// Cap on the width scale, so amplified chains don't flood the field
+const MAX_DYNAMIC_WIDTH_SCALE: f32 = 3.0;
+/// How long newly formed connections pulse for, in seconds
+const FORMED_PULSE_DURATION: f32 = 0.6;
+
 /// Component to track if tiles have been modified
 #[derive(Component)]
 pub struct TilesDirty;
@@ -42,8 +53,17 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(MaterialPlugin::<
         ExtendedMaterial<StandardMaterial, FieldGroundExtension>,
     >::default())
-        .add_systems(Update, update_connection_data)
-        .add_systems(Update, update_shader_highlights)
+        .init_resource::<FormedConnectionPulse>()
+        .add_systems(
+            Update,
+            (
+                start_formed_pulse,
+                tick_formed_pulse,
+                update_connection_data,
+            )
+                .chain(),
+        )
+        .add_systems(Update, (update_shader_highlights, update_highlight_palette))
         .add_systems(Update, update_material_time)
         .add_systems(Update, update_tile_texture);
 }
@@ -85,17 +105,43 @@ pub struct FieldUniforms {
     pub pulse_speed: f32,
     pub glow_intensity: f32,
     pub line_width: f32,
-    pub _padding: Vec3,
+    /// Non-zero to draw patterns over the preview highlights, set by [`ColorblindMode`]
+    pub highlight_patterns: u32,
+    /// Preview position, connected, empty connection point and existing target colours
+    pub highlight_colors: [Vec4; 4],
 }
 
 /// Connection data for storage buffer
+///
+/// One entry per step of a connection's path, so the line follows the cells energy
+/// actually travels through instead of cutting across them
 #[derive(Debug, Clone, Copy, ShaderType)]
 pub struct ConnectionBufferData {
     pub start_pos: Vec2,
     pub end_pos: Vec2,
     pub strength: f32,
+    /// Length of the whole path, in UV units
     pub distance: f32,
-    pub _padding: Vec2,
+    pub width: f32,
+    /// Brightness of the "connection formed" pulse, 0 when not pulsing
+    pub formed_pulse: f32,
+    /// Length of the path before this step, so the pulse flows along the whole path
+    pub path_offset: f32,
+    pub _padding: f32,
+}
+
+impl ConnectionBufferData {
+    /// Placeholder entry, as the storage buffer can't be empty
+    const EMPTY: Self = Self {
+        start_pos: Vec2::ZERO,
+        end_pos: Vec2::ZERO,
+        strength: 0.0,
+        distance: 0.0,
+        width: 0.0,
+        formed_pulse: 0.0,
+        path_offset: 0.0,
+        _padding: 0.0,
+    };
 }
 
 /// Preview highlight data for storage buffer
@@ -167,13 +213,7 @@ pub fn spawn_field_ground(
     let tile_texture_handle = level_assets.tile_texture.clone();
 
     // Create empty storage buffers with initial capacity
-    let empty_connections = vec![ConnectionBufferData {
-        start_pos: Vec2::ZERO,
-        end_pos: Vec2::ZERO,
-        strength: 0.0,
-        distance: 0.0,
-        _padding: Vec2::ZERO,
-    }];
+    let empty_connections = vec![ConnectionBufferData::EMPTY];
     let connections_buffer = buffers.add(ShaderStorageBuffer::from(empty_connections));
 
     let empty_previews = vec![PreviewBufferData {
@@ -203,7 +243,9 @@ pub fn spawn_field_ground(
                 pulse_speed: 2.0,
                 glow_intensity: 0.8,
                 line_width: 0.005,
-                _padding: Vec3::ZERO,
+                highlight_patterns: 0,
+                highlight_colors: highlight_palette(ColorblindMode::Off)
+                    .map(|color| color.to_vec4()),
             },
             connections: connections_buffer,
             preview_highlights: preview_buffer,
@@ -249,7 +291,7 @@ fn create_tile_indices_texture(play_field: &PlayField) -> Image {
             }
 
             // Select sprite based on tile type
-            let sprite = select_tile_sprite(tile_type, pos);
+            let sprite = select_tile_sprite(tile_type, pos, play_field.render_seed);
             let sprite_index = sprite.index();
 
             // Store tile index in R channel
@@ -258,7 +300,8 @@ fn create_tile_indices_texture(play_field: &PlayField) -> Image {
             data[pixel_index] = tile_value;
             data[pixel_index + 1] = 0;
             data[pixel_index + 2] = 0;
-            data[pixel_index + 3] = 255;
+            // Zero alpha marks a hole, the shader leaves it empty
+            data[pixel_index + 3] = if play_field.is_hole(pos) { 0 } else { 255 };
         }
     }
 
@@ -288,14 +331,70 @@ fn update_material_time(
     }
 }
 
+/// Line width for a connection, scaled from the base `line_width` uniform
+fn connection_line_width(connection: &Connection, mode: ConnectionWidthMode, base: f32) -> f32 {
+    let scale = match mode {
+        ConnectionWidthMode::Static => connection.strength,
+        ConnectionWidthMode::Dynamic => {
+            (MIN_DYNAMIC_WIDTH_SCALE + connection.last_energy).min(MAX_DYNAMIC_WIDTH_SCALE)
+        }
+    };
+    base * scale
+}
+
+/// Connections that were just formed, lit up briefly in the shader
+#[derive(Resource, Debug, Default)]
+struct FormedConnectionPulse {
+    connections: Vec<(GridPosition, GridPosition)>,
+    timer: Timer,
+}
+
+impl FormedConnectionPulse {
+    /// Pulse brightness for a connection, fading out over the pulse duration
+    fn strength_for(&self, connection: &Connection) -> f32 {
+        let endpoints = (connection.from_pos, connection.to_pos);
+        if self.timer.finished() || !self.connections.contains(&endpoints) {
+            return 0.0;
+        }
+        1.0 - self.timer.fraction()
+    }
+}
+
+fn start_formed_pulse(
+    mut formed_events: EventReader<ConnectionsFormed>,
+    mut pulse: ResMut<FormedConnectionPulse>,
+    reduced_motion: Res<ReducedMotion>,
+) {
+    for event in formed_events.read() {
+        if reduced_motion.0 {
+            continue;
+        }
+        pulse.connections = event.connections.clone();
+        pulse.timer = Timer::from_seconds(FORMED_PULSE_DURATION, TimerMode::Once);
+    }
+}
+
+fn tick_formed_pulse(time: Res<Time>, mut pulse: ResMut<FormedConnectionPulse>) {
+    if pulse.connections.is_empty() {
+        return;
+    }
+    pulse.timer.tick(time.delta());
+    if pulse.timer.finished() {
+        // Clearing counts as a change, so the buffer gets one last write without the pulse
+        pulse.connections.clear();
+    }
+}
+
 /// Update connection data in storage buffers
 fn update_connection_data(
     field_grounds: Query<&FieldGround>,
     mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, FieldGroundExtension>>>,
     mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
     game_state: Res<GameState>,
+    width_mode: Res<ConnectionWidthMode>,
+    formed_pulse: Res<FormedConnectionPulse>,
 ) {
-    if !game_state.is_changed() {
+    if !game_state.is_changed() && !width_mode.is_changed() && !formed_pulse.is_changed() {
         return;
     }
 
@@ -307,38 +406,59 @@ fn update_connection_data(
             // Create buffer data
             let mut connection_data = Vec::with_capacity(connections.len().max(1));
 
+            let grid_size = material.extension.field_uniforms.grid_size;
+            // Convert grid positions to normalized UV coordinates
+            let to_uv = |position: GridPosition| {
+                Vec2::new(
+                    (position.x as f32 + 0.5) / grid_size.x,
+                    1.0 - ((position.y as f32 + 0.5) / grid_size.y), // Flip Y coordinate
+                )
+            };
+
             for connection in connections {
-                // Convert grid positions to normalized UV coordinates
-                let grid_size = material.extension.field_uniforms.grid_size;
-                let start_uv = Vec2::new(
-                    (connection.from_pos.x as f32 + 0.5) / grid_size.x,
-                    1.0 - ((connection.from_pos.y as f32 + 0.5) / grid_size.y), // Flip Y coordinate
+                // Connections without a path get a straight line
+                let points: Vec<Vec2> = if connection.path.len() >= 2 {
+                    connection.path.iter().copied().map(to_uv).collect()
+                } else {
+                    vec![to_uv(connection.from_pos), to_uv(connection.to_pos)]
+                };
+                let distance: f32 = points
+                    .windows(2)
+                    .map(|step| step[0].distance(step[1]))
+                    .sum();
+                let width = connection_line_width(
+                    connection,
+                    *width_mode,
+                    material.extension.field_uniforms.line_width,
                 );
-                let end_uv = Vec2::new(
-                    (connection.to_pos.x as f32 + 0.5) / grid_size.x,
-                    1.0 - ((connection.to_pos.y as f32 + 0.5) / grid_size.y), // Flip Y coordinate
-                );
-
-                let distance = start_uv.distance(end_uv);
-
-                connection_data.push(ConnectionBufferData {
-                    start_pos: start_uv,
-                    end_pos: end_uv,
-                    strength: connection.strength,
-                    distance,
-                    _padding: Vec2::ZERO,
-                });
+                let formed_pulse = formed_pulse.strength_for(connection);
+
+                let mut path_offset = 0.0;
+                for step in points.windows(2) {
+                    let length = step[0].distance(step[1]);
+                    // Zero-length steps would divide by zero in the shader
+                    if length <= 0.0 {
+                        continue;
+                    }
+                    connection_data.push(ConnectionBufferData {
+                        start_pos: step[0],
+                        end_pos: step[1],
+                        strength: connection.strength,
+                        distance,
+                        width,
+                        formed_pulse,
+                        path_offset,
+                        _padding: 0.0,
+                    });
+                    path_offset += length;
+                }
             }
 
+            let segment_count = connection_data.len();
+
             // Ensure we have at least one element to avoid zero-sized buffer
             if connection_data.is_empty() {
-                connection_data.push(ConnectionBufferData {
-                    start_pos: Vec2::ZERO,
-                    end_pos: Vec2::ZERO,
-                    strength: 0.0,
-                    distance: 0.0,
-                    _padding: Vec2::ZERO,
-                });
+                connection_data.push(ConnectionBufferData::EMPTY);
             }
 
             // Update storage buffer
@@ -346,16 +466,97 @@ fn update_connection_data(
                 buffer.set_data(connection_data.as_slice());
             }
 
-            material.extension.field_uniforms.connection_count = connections.len() as u32;
+            material.extension.field_uniforms.connection_count = segment_count as u32;
 
             info!(
-                "Updated {} mycelium connections in shader",
-                connections.len()
+                "Updated {} mycelium connections ({} steps) in shader",
+                connections.len(),
+                segment_count
             );
         }
     }
 }
 
+/// Collect highlighted cells with their shader highlight type, one entry per cell
+/// When a cell falls into several categories the first one wins, in this order:
+/// preview position, preview connection (green), empty connection point (red),
+/// existing mushroom connection target (blue)
+fn resolve_highlights(
+    preview_connections: &crate::game::play_field::placement_preview::PreviewConnections,
+) -> Vec<(GridPosition, f32)> {
+    let categories = [
+        (preview_connections.preview_position.as_slice(), -1.0),
+        (preview_connections.connected_positions.as_slice(), -2.0),
+        (preview_connections.empty_connection_points.as_slice(), -3.0),
+        (
+            preview_connections.existing_connection_targets.as_slice(),
+            -4.0,
+        ),
+    ];
+
+    let mut seen = HashSet::new();
+    let mut highlights = Vec::new();
+    for (positions, highlight_type) in categories {
+        for pos in positions {
+            if seen.insert(*pos) {
+                highlights.push((*pos, highlight_type));
+            }
+        }
+    }
+    highlights
+}
+
+/// Highlight colours for each [`ColorblindMode`], in shader highlight type order
+/// The colourblind palettes are picked from the Okabe-Ito set
+fn highlight_palette(mode: ColorblindMode) -> [LinearRgba; 4] {
+    match mode {
+        ColorblindMode::Off => [
+            LinearRgba::rgb(0.2, 0.8, 1.0),
+            LinearRgba::rgb(0.2, 1.0, 0.4),
+            LinearRgba::rgb(1.0, 0.3, 0.2),
+            LinearRgba::rgb(0.3, 0.6, 1.0),
+        ],
+        ColorblindMode::Deuteranopia => [
+            LinearRgba::rgb(0.94, 0.89, 0.26),
+            LinearRgba::rgb(0.0, 0.45, 0.7),
+            LinearRgba::rgb(0.9, 0.6, 0.0),
+            LinearRgba::rgb(0.8, 0.47, 0.65),
+        ],
+        ColorblindMode::Protanopia => [
+            LinearRgba::rgb(0.94, 0.89, 0.26),
+            LinearRgba::rgb(0.34, 0.71, 0.91),
+            LinearRgba::rgb(0.9, 0.6, 0.0),
+            LinearRgba::rgb(0.0, 0.45, 0.7),
+        ],
+        ColorblindMode::Tritanopia => [
+            LinearRgba::rgb(0.9, 0.9, 0.9),
+            LinearRgba::rgb(0.0, 0.75, 0.85),
+            LinearRgba::rgb(0.84, 0.37, 0.0),
+            LinearRgba::rgb(0.8, 0.47, 0.65),
+        ],
+    }
+}
+
+/// Apply the colourblind palette when it changes, and to each newly spawned field
+fn update_highlight_palette(
+    mode: Res<ColorblindMode>,
+    field_grounds: Query<&FieldGround>,
+    added_grounds: Query<(), Added<FieldGround>>,
+    mut materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, FieldGroundExtension>>>,
+) {
+    if !mode.is_changed() && added_grounds.is_empty() {
+        return;
+    }
+
+    for field_ground in field_grounds.iter() {
+        if let Some(material) = materials.get_mut(&field_ground.material_handle) {
+            let uniforms = &mut material.extension.field_uniforms;
+            uniforms.highlight_colors = highlight_palette(*mode).map(|color| color.to_vec4());
+            uniforms.highlight_patterns = (*mode != ColorblindMode::Off) as u32;
+        }
+    }
+}
+
 /// Update preview highlights in storage buffer
 fn update_shader_highlights(
     preview_connections: Res<crate::game::play_field::placement_preview::PreviewConnections>,
@@ -379,54 +580,14 @@ fn update_shader_highlights(
                 || !preview_connections.existing_connection_targets.is_empty();
 
             if has_preview_data {
-                // Add preview position
-                if let Some(preview_pos) = preview_connections.preview_position {
-                    let preview_uv = Vec2::new(
-                        (preview_pos.x as f32 + 0.5) / grid_size.x,
-                        1.0 - ((preview_pos.y as f32 + 0.5) / grid_size.y),
-                    );
-                    preview_data.push(PreviewBufferData {
-                        position: preview_uv,
-                        highlight_type: -1.0,
-                        _padding: 0.0,
-                    });
-                }
-
-                // Add all connected positions
-                for connected_pos in &preview_connections.connected_positions {
-                    let connected_uv = Vec2::new(
-                        (connected_pos.x as f32 + 0.5) / grid_size.x,
-                        1.0 - ((connected_pos.y as f32 + 0.5) / grid_size.y),
+                for (pos, highlight_type) in resolve_highlights(&preview_connections) {
+                    let uv = Vec2::new(
+                        (pos.x as f32 + 0.5) / grid_size.x,
+                        1.0 - ((pos.y as f32 + 0.5) / grid_size.y),
                     );
                     preview_data.push(PreviewBufferData {
-                        position: connected_uv,
-                        highlight_type: -2.0,
-                        _padding: 0.0,
-                    });
-                }
-
-                // Add all empty connection points
-                for empty_pos in &preview_connections.empty_connection_points {
-                    let empty_uv = Vec2::new(
-                        (empty_pos.x as f32 + 0.5) / grid_size.x,
-                        1.0 - ((empty_pos.y as f32 + 0.5) / grid_size.y),
-                    );
-                    preview_data.push(PreviewBufferData {
-                        position: empty_uv,
-                        highlight_type: -3.0,
-                        _padding: 0.0,
-                    });
-                }
-
-                // Add all existing mushroom connection targets
-                for existing_target in &preview_connections.existing_connection_targets {
-                    let target_uv = Vec2::new(
-                        (existing_target.x as f32 + 0.5) / grid_size.x,
-                        1.0 - ((existing_target.y as f32 + 0.5) / grid_size.y),
-                    );
-                    preview_data.push(PreviewBufferData {
-                        position: target_uv,
-                        highlight_type: -4.0,
+                        position: uv,
+                        highlight_type,
                         _padding: 0.0,
                     });
                 }
@@ -456,11 +617,25 @@ fn update_shader_highlights(
     }
 }
 
+/// Pick one of `count` sprite variants for a position, mixing in the level's render seed
+fn tile_variant(
+    position: GridPosition,
+    seed: u32,
+    x_factor: i32,
+    y_factor: i32,
+    count: u32,
+) -> u32 {
+    let base = (position.x * x_factor + position.y * y_factor) as u32;
+    // Scramble so neighbouring seeds don't just shift the pattern
+    let mixed = (base ^ seed).wrapping_mul(0x045d_9f3b);
+    (mixed ^ (mixed >> 16)) % count
+}
+
 /// Select tile sprite
-fn select_tile_sprite(tile_type: TileType, position: GridPosition) -> TileSprite {
+fn select_tile_sprite(tile_type: TileType, position: GridPosition, seed: u32) -> TileSprite {
     match tile_type {
         TileType::Empty => {
-            let variant = (position.x * 11 + position.y * 17) % 3;
+            let variant = tile_variant(position, seed, 11, 17, 3);
             match variant {
                 0 => TileSprite::Soil1,
                 1 => TileSprite::Soil2,
@@ -468,28 +643,30 @@ fn select_tile_sprite(tile_type: TileType, position: GridPosition) -> TileSprite
             }
         }
         TileType::Fertile => {
-            let variant = (position.x * 13 + position.y * 19) % 2;
+            let variant = tile_variant(position, seed, 13, 19, 2);
             match variant {
                 0 => TileSprite::RichSoil1,
                 _ => TileSprite::RichSoil2,
             }
         }
+        // No art of its own yet, always the richer of the two soil sprites
+        TileType::Nutrient => TileSprite::RichSoil2,
         TileType::BlockedRock => {
-            let variant = (position.x * 23 + position.y * 29) % 2;
+            let variant = tile_variant(position, seed, 23, 29, 2);
             match variant {
                 0 => TileSprite::BlockerRock1,
                 _ => TileSprite::BlockerRock2,
             }
         }
         TileType::BlockedWater => {
-            let variant = (position.x * 31 + position.y * 37) % 2;
+            let variant = tile_variant(position, seed, 31, 37, 2);
             match variant {
                 0 => TileSprite::BlockerWater1,
                 _ => TileSprite::BlockerWater2,
             }
         }
         TileType::BlockedMoss => {
-            let variant = (position.x * 17 + position.y * 41) % 2;
+            let variant = tile_variant(position, seed, 17, 41, 2);
             match variant {
                 0 => TileSprite::BlockerMoss1,
                 _ => TileSprite::BlockerMoss2,
@@ -497,3 +674,51 @@ fn select_tile_sprite(tile_type: TileType, position: GridPosition) -> TileSprite
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::play_field::placement_preview::PreviewConnections;
+
+    #[test]
+    fn overlapping_highlights_keep_the_highest_priority() {
+        let shared = GridPosition::new(2, 2);
+        let preview = PreviewConnections {
+            connected_positions: vec![shared, GridPosition::new(3, 2)],
+            empty_connection_points: vec![GridPosition::new(1, 2), shared],
+            existing_connection_targets: vec![shared, GridPosition::new(3, 2)],
+            preview_position: Some(GridPosition::new(2, 3)),
+            ..default()
+        };
+
+        let highlights = resolve_highlights(&preview);
+
+        assert_eq!(
+            highlights,
+            vec![
+                (GridPosition::new(2, 3), -1.0),
+                (shared, -2.0),
+                (GridPosition::new(3, 2), -2.0),
+                (GridPosition::new(1, 2), -3.0),
+            ]
+        );
+    }
+
+    fn field_with_seed(render_seed: u32) -> PlayField {
+        let mut field = PlayField::new(6, 6);
+        field.set_tile(GridPosition::new(2, 3), TileType::BlockedRock);
+        field.set_tile(GridPosition::new(4, 1), TileType::Fertile);
+        field.render_seed = render_seed;
+        field
+    }
+
+    #[test]
+    fn same_seed_builds_the_same_tile_texture() {
+        let first = create_tile_indices_texture(&field_with_seed(1));
+        let again = create_tile_indices_texture(&field_with_seed(1));
+        let other = create_tile_indices_texture(&field_with_seed(2));
+
+        assert_eq!(first.data, again.data);
+        assert_ne!(first.data, other.data);
+    }
+}
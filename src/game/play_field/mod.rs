@@ -2,15 +2,27 @@
 
 use bevy::prelude::*;
 
+pub mod camera_framing;
+mod chain_estimate;
+pub mod cinematic;
+#[cfg(feature = "dev")]
+mod connection_graph;
 pub mod events;
 mod field;
 pub mod field_renderer;
 pub mod mycelium;
 pub mod observers;
+pub mod placement_comparison;
 pub mod placement_preview;
 mod position;
+pub mod relocation;
+pub mod snapshot;
+mod sweep_delete;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod tile_atlas;
 pub mod tiles;
+pub mod topology;
 
 pub use events::GridClickEvent;
 pub use field::{CELL_SIZE, CELL_SPACING, PlayField};
@@ -22,13 +34,21 @@ use crate::game::resources::GameState;
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         position::plugin,
+        camera_framing::plugin,
+        chain_estimate::plugin,
+        cinematic::plugin,
         events::plugin,
         observers::plugin,
         tiles::plugin,
         mycelium::plugin,
         field_renderer::plugin,
+        placement_comparison::plugin,
         placement_preview::plugin,
+        relocation::plugin,
+        sweep_delete::plugin,
     ));
+    #[cfg(feature = "dev")]
+    app.add_plugins(connection_graph::plugin);
 
     app.add_systems(
         OnEnter(crate::game::game_flow::LevelState::Playing),
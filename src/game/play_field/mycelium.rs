@@ -1,18 +1,43 @@
 //! Mycelium connection system (PlayField connections)
 
 use super::GridPosition;
-use crate::game::mushrooms::{Mushroom, MushroomDefinitions, MushroomDirection};
+use crate::game::mushrooms::{Mushroom, MushroomDefinitions, MushroomDirection, MushroomType};
 use crate::game::play_field::PlayField;
 use crate::game::resources::GameState;
-use bevy::prelude::*;
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MyceliumConnectionsEnabled>();
+    app.init_resource::<ConnectionBuilder>();
+    app.add_event::<ConnectionsFormed>();
     app.add_systems(Update, build_playfield_connections);
 }
 
+/// Whether mycelium connections are currently being built
+/// Levels can start with this off so pre-placed mushrooms don't give away the solution
+#[derive(Resource, Debug)]
+pub struct MyceliumConnectionsEnabled(pub bool);
+
+impl Default for MyceliumConnectionsEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Sent when newly placed mushrooms form connections, with the endpoints of each new connection
+#[derive(Event, Debug, Clone)]
+pub struct ConnectionsFormed {
+    pub connections: Vec<(GridPosition, GridPosition)>,
+}
+
 /// Connection building state to avoid rebuilding every frame
 #[derive(Resource, Default)]
 pub struct ConnectionBuilder {
+    /// Set when the field changes under the mushrooms, such as a cleared rock, to
+    /// rebuild the connections next frame
     pub dirty: bool,
 }
 
@@ -31,18 +56,28 @@ pub fn build_playfield_connections(
     all_mushrooms: Query<(Entity, &GridPosition, &Mushroom, Option<&MushroomDirection>)>,
     mut game_state: ResMut<GameState>,
     definitions: Res<MushroomDefinitions>,
-    mut builder: Local<ConnectionBuilder>,
+    connections_enabled: Res<MyceliumConnectionsEnabled>,
+    mut builder: ResMut<ConnectionBuilder>,
+    mut formed_events: EventWriter<ConnectionsFormed>,
 ) {
     // Check if we need to rebuild connections
     let needs_rebuild = !new_mushrooms.is_empty()
         || !changed_mushrooms.is_empty()
         || deleted_mushrooms.read().next().is_some()
+        || connections_enabled.is_changed()
         || builder.dirty;
 
     if !needs_rebuild {
         return;
     }
 
+    // Connections are suppressed until the player places their first mushroom
+    if !connections_enabled.0 {
+        game_state.play_field.clear_connections();
+        builder.dirty = false;
+        return;
+    }
+
     info!(
         "Building PlayField connections - {} new, {} changed mushrooms",
         new_mushrooms.iter().count(),
@@ -53,63 +88,37 @@ pub fn build_playfield_connections(
     game_state.play_field.clear_connections();
 
     // Get all mushrooms for connection building
-    let mushroom_list: Vec<_> = all_mushrooms.iter().collect();
+    let mushroom_list: Vec<_> = all_mushrooms
+        .iter()
+        .map(|(_, pos, mushroom, direction)| {
+            (*pos, mushroom.0, direction.copied().unwrap_or_default())
+        })
+        .collect();
 
     let mut connection_count = 0;
+    let placed: Vec<Entity> = new_mushrooms.iter().map(|(entity, ..)| entity).collect();
+    let mut formed = Vec::new();
 
-    // Build connections for each mushroom based on its connection points
-    for (entity, pos, mushroom, direction) in mushroom_list.iter() {
-        // Get the mushroom definition
-        let Some(definition) = definitions.get(mushroom.0) else {
-            warn!("No definition found for mushroom type {:?}", mushroom.0);
+    for link in mycelium_links(&definitions, &game_state.play_field, &mushroom_list) {
+        let (Some(entity), Some(target_entity)) = (
+            game_state.play_field.get(link.from),
+            game_state.play_field.get(link.to),
+        ) else {
             continue;
         };
-
-        // Skip if this mushroom has no connections
-        if definition.connection_points.is_empty() {
-            continue;
-        }
-
-        info!(
-            "Building connections for {:?} at {:?} with {} connection points",
-            mushroom.0,
-            pos,
-            definition.connection_points.len()
-        );
-
-        // Process each connection point
-        for connection_point in &definition.connection_points {
-            // Calculate the actual target position
-            // If the mushroom has a direction component, rotate the connection point
-            let target_pos = if let Some(dir) = direction {
-                let rotated_offset = rotate_connection_point(connection_point, dir);
-                GridPosition::new(pos.x + rotated_offset.x, pos.y + rotated_offset.y)
-            } else {
-                // No direction component, use the connection point as-is
-                GridPosition::new(pos.x + connection_point.x, pos.y + connection_point.y)
-            };
-
-            // Check if there's a mushroom at the target position
-            if let Some(target_entity) = game_state.play_field.get(target_pos) {
-                // Verify it's actually a mushroom
-                if mushroom_list.iter().any(|(e, _, _, _)| *e == target_entity) {
-                    // Create connection with pathfinding
-                    if let Some((path, strength)) =
-                        find_mycelium_path(**pos, target_pos, &game_state.play_field)
-                    {
-                        game_state.play_field.add_connection(
-                            **pos,
-                            target_pos,
-                            *entity,
-                            target_entity,
-                            strength,
-                            path,
-                        );
-                        connection_count += 1;
-                        info!("Created connection from {:?} to {:?}", pos, target_pos);
-                    }
-                }
+        if game_state.play_field.add_connection(
+            link.from,
+            link.to,
+            entity,
+            target_entity,
+            link.strength,
+            link.path,
+        ) {
+            connection_count += 1;
+            if placed.contains(&entity) || placed.contains(&target_entity) {
+                formed.push((link.from, link.to));
             }
+            info!("Created connection from {:?} to {:?}", link.from, link.to);
         }
     }
 
@@ -117,63 +126,208 @@ pub fn build_playfield_connections(
         "PlayField connection build complete: {} connections created",
         connection_count
     );
+    if !formed.is_empty() {
+        formed_events.write(ConnectionsFormed {
+            connections: formed,
+        });
+    }
     builder.dirty = false;
 }
 
-/// Rotate a connection point based on mushroom direction
-fn rotate_connection_point(
-    point: &crate::game::mushrooms::definitions::GridOffset,
-    direction: &MushroomDirection,
-) -> crate::game::mushrooms::definitions::GridOffset {
-    use crate::game::mushrooms::definitions::GridOffset;
+/// Mycelium grown from one mushroom to another it targets
+#[derive(Debug, Clone)]
+pub struct MyceliumLink {
+    pub from: GridPosition,
+    pub to: GridPosition,
+    pub path: Vec<GridPosition>,
+    pub strength: f32,
+}
 
-    match direction {
-        MushroomDirection::Up => {
-            // No rotation needed, this is the default
-            GridOffset::new(point.x, point.y)
-        }
-        MushroomDirection::Right => {
-            // Rotate 90 degrees clockwise: (x, y) -> (y, -x)
-            GridOffset::new(point.y, -point.x)
-        }
-        MushroomDirection::Down => {
-            // Rotate 180 degrees: (x, y) -> (-x, -y)
-            GridOffset::new(-point.x, -point.y)
-        }
-        MushroomDirection::Left => {
-            // Rotate 270 degrees clockwise: (x, y) -> (-y, x)
-            GridOffset::new(-point.y, point.x)
+/// Every link the mushrooms grow, in the order they're given
+/// Each mushroom links to the mushrooms on its connection points that mycelium can reach.
+/// Two mushrooms targeting each other give a link each way.
+pub fn mycelium_links(
+    definitions: &MushroomDefinitions,
+    play_field: &PlayField,
+    mushrooms: &[(GridPosition, MushroomType, MushroomDirection)],
+) -> Vec<MyceliumLink> {
+    let occupied: HashSet<GridPosition> = mushrooms.iter().map(|(pos, ..)| *pos).collect();
+    mushrooms
+        .iter()
+        .flat_map(|mushroom| links_from(definitions, play_field, *mushroom, &occupied))
+        .collect()
+}
+
+/// Connections the mushrooms form, counted like the play field's: a pair linked both
+/// ways is one connection, and pairs mycelium can't reach have none
+pub fn count_connections(
+    definitions: &MushroomDefinitions,
+    play_field: &PlayField,
+    mushrooms: &[(GridPosition, MushroomType, MushroomDirection)],
+) -> usize {
+    mycelium_links(definitions, play_field, mushrooms)
+        .into_iter()
+        .map(|link| unordered_pair(link.from, link.to))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Connections a new mushroom would add to the others, one per mushroom it ends up
+/// linked with in either direction
+pub fn count_new_connections(
+    definitions: &MushroomDefinitions,
+    play_field: &PlayField,
+    mushrooms: &[(GridPosition, MushroomType, MushroomDirection)],
+    new: (GridPosition, MushroomType, MushroomDirection),
+) -> usize {
+    let position = new.0;
+    let mut occupied: HashSet<GridPosition> = mushrooms.iter().map(|(pos, ..)| *pos).collect();
+    occupied.insert(position);
+
+    let mut partners: HashSet<GridPosition> = links_from(definitions, play_field, new, &occupied)
+        .map(|link| link.to)
+        .collect();
+    // Only the links into the new cell matter for the others
+    let new_cell = HashSet::from_iter([position]);
+    for mushroom in mushrooms.iter().filter(|(pos, ..)| *pos != position) {
+        if links_from(definitions, play_field, *mushroom, &new_cell)
+            .next()
+            .is_some()
+        {
+            partners.insert(mushroom.0);
         }
     }
+    partners.len()
 }
 
+/// Links from one mushroom to the occupied cells it targets
+fn links_from<'a>(
+    definitions: &'a MushroomDefinitions,
+    play_field: &'a PlayField,
+    (from, mushroom_type, direction): (GridPosition, MushroomType, MushroomDirection),
+    occupied: &'a HashSet<GridPosition>,
+) -> impl Iterator<Item = MyceliumLink> + 'a {
+    let definition = definitions.get_or_default(mushroom_type);
+    play_field
+        .topology
+        .targets(from, definition, direction)
+        .into_iter()
+        .filter(|to| occupied.contains(to))
+        .filter_map(move |to| {
+            let (path, strength) = find_mycelium_path(from, to, play_field)?;
+            Some(MyceliumLink {
+                from,
+                to,
+                path,
+                strength,
+            })
+        })
+}
+
+/// The pair in a fixed order, so a link and its reverse compare equal
+fn unordered_pair(a: GridPosition, b: GridPosition) -> (GridPosition, GridPosition) {
+    if (a.y, a.x) <= (b.y, b.x) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Extra steps a detour may take over the straight line before the connection gives up
+const MAX_DETOUR_EXTRA_STEPS: usize = 3;
+/// Strength kept by a connection that has to grow around something
+const DETOUR_STRENGTH: f32 = 0.6;
+
 /// Find a valid path for mycelium between two positions
+/// Takes the straight line when it's clear, otherwise a short detour at reduced strength
 fn find_mycelium_path(
     from: GridPosition,
     to: GridPosition,
     play_field: &PlayField,
 ) -> Option<(Vec<GridPosition>, f32)> {
     let path = bresenham_line(from, to);
+    if let Some(strength) = path_strength(&path, play_field) {
+        return Some((path, strength));
+    }
 
+    let max_steps = path.len() - 1 + MAX_DETOUR_EXTRA_STEPS;
+    let detour = find_detour(from, to, play_field, max_steps)?;
+    let strength = path_strength(&detour, play_field)? * DETOUR_STRENGTH;
+    Some((detour, strength))
+}
+
+/// Combined strength of the tiles along a path, `None` if any of them blocks mycelium
+/// Holes have no tile, so mycelium can't cross them
+fn path_strength(path: &[GridPosition], play_field: &PlayField) -> Option<f32> {
     let mut total_strength = 1.0;
-    let mut can_connect = true;
 
     // Check each tile along the path
-    for pos in &path {
-        if let Some(tile) = play_field.get_tile(*pos) {
-            if !tile.allows_mycelium() {
-                can_connect = false;
-                break;
-            }
-            total_strength *= tile.mycelium_strength_modifier();
+    for pos in path {
+        let tile = play_field.get_tile(*pos)?;
+        if !tile.allows_mycelium() {
+            return None;
         }
+        total_strength *= tile.mycelium_strength_modifier();
     }
 
-    if can_connect && total_strength > 0.0 {
-        Some((path, total_strength.min(1.0)))
-    } else {
-        None
+    (total_strength > 0.0).then_some(total_strength.min(1.0))
+}
+
+/// A* search for the shortest route of at most `max_steps` moves through tiles that let
+/// mycelium grow, moving like a king in chess
+fn find_detour(
+    from: GridPosition,
+    to: GridPosition,
+    play_field: &PlayField,
+    max_steps: usize,
+) -> Option<Vec<GridPosition>> {
+    // Diagonal moves cost the same, so the distance is the larger of the two axes
+    let heuristic = |pos: GridPosition| (pos.x - to.x).abs().max((pos.y - to.y).abs()) as usize;
+    let passable = |pos: GridPosition| {
+        play_field
+            .get_tile(pos)
+            .is_some_and(|tile| tile.allows_mycelium())
+    };
+
+    let mut open = vec![from];
+    let mut steps: HashMap<GridPosition, usize> = HashMap::default();
+    let mut came_from: HashMap<GridPosition, GridPosition> = HashMap::default();
+    steps.insert(from, 0);
+
+    while let Some(index) = (0..open.len()).min_by_key(|i| steps[&open[*i]] + heuristic(open[*i])) {
+        let current = open.swap_remove(index);
+        if current == to {
+            let mut path = vec![to];
+            let mut pos = to;
+            while let Some(previous) = came_from.get(&pos) {
+                path.push(*previous);
+                pos = *previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let next_steps = steps[&current] + 1;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let next = GridPosition::new(current.x + dx, current.y + dy);
+                if (dx == 0 && dy == 0)
+                    || !passable(next)
+                    || next_steps + heuristic(next) > max_steps
+                    || steps.get(&next).is_some_and(|known| *known <= next_steps)
+                {
+                    continue;
+                }
+                steps.insert(next, next_steps);
+                came_from.insert(next, current);
+                if !open.contains(&next) {
+                    open.push(next);
+                }
+            }
+        }
     }
+
+    None
 }
 
 /// Bresenham's line algorithm for grid positions
@@ -212,3 +366,129 @@ fn bresenham_line(from: GridPosition, to: GridPosition) -> Vec<GridPosition> {
 
     points
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::play_field::{TileType, test_support::at};
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn pulse(
+        x: i32,
+        y: i32,
+        direction: MushroomDirection,
+    ) -> (GridPosition, MushroomType, MushroomDirection) {
+        (GridPosition::new(x, y), MushroomType::Pulse, direction)
+    }
+
+    #[test]
+    fn mushrooms_targeting_each_other_form_one_connection() {
+        let definitions = MushroomDefinitions::standard();
+        let field = PlayField::new(3, 4);
+        let mushrooms = [
+            pulse(1, 1, MushroomDirection::Up),
+            pulse(1, 2, MushroomDirection::Down),
+        ];
+
+        assert_eq!(mycelium_links(&definitions, &field, &mushrooms).len(), 2);
+        assert_eq!(count_connections(&definitions, &field, &mushrooms), 1);
+    }
+
+    #[test]
+    fn blocked_mycelium_forms_no_connection() {
+        let definitions = MushroomDefinitions::standard();
+        let mut field = PlayField::new(3, 5);
+        for x in 0..3 {
+            field.set_tile(GridPosition::new(x, 2), TileType::BlockedRock);
+        }
+        let mushrooms = [
+            at(1, 1, MushroomType::Skipper),
+            at(1, 3, MushroomType::Basic),
+        ];
+
+        assert_eq!(count_connections(&definitions, &field, &mushrooms), 0);
+    }
+
+    #[test]
+    fn single_rock_is_routed_around() {
+        let mut field = PlayField::new(3, 3);
+        let rock = GridPosition::new(1, 1);
+        field.set_tile(rock, TileType::BlockedRock);
+        let (from, to) = (GridPosition::new(1, 0), GridPosition::new(1, 2));
+
+        let (path, strength) = find_mycelium_path(from, to, &field).unwrap();
+
+        assert_eq!(path.first(), Some(&from));
+        assert_eq!(path.last(), Some(&to));
+        assert!(!path.contains(&rock));
+        // One step to the side of the rock and back is the shortest way round
+        assert_eq!(path.len(), 3);
+        assert_eq!(strength, DETOUR_STRENGTH);
+    }
+
+    #[test]
+    fn new_mushroom_counts_links_in_both_directions() {
+        let definitions = MushroomDefinitions::standard();
+        let field = PlayField::new(3, 4);
+        let mushrooms = [
+            // Points at the new mushroom
+            pulse(1, 0, MushroomDirection::Up),
+            // Pointed at by the new mushroom
+            (
+                GridPosition::new(1, 2),
+                MushroomType::Basic,
+                MushroomDirection::Up,
+            ),
+            // Neither
+            pulse(0, 0, MushroomDirection::Up),
+        ];
+
+        let new = pulse(1, 1, MushroomDirection::Up);
+        assert_eq!(
+            count_new_connections(&definitions, &field, &mushrooms, new),
+            2
+        );
+
+        let mut placed = mushrooms.to_vec();
+        placed.push(new);
+        assert_eq!(count_connections(&definitions, &field, &placed), 2);
+    }
+
+    #[test]
+    fn pre_placed_mushrooms_wait_for_connections_to_be_enabled() {
+        let mut world = World::new();
+        world.insert_resource(GameState::default());
+        world.insert_resource(MushroomDefinitions::standard());
+        world.insert_resource(MyceliumConnectionsEnabled(false));
+        world.init_resource::<Events<ConnectionsFormed>>();
+        world.init_resource::<ConnectionBuilder>();
+        for (position, mushroom_type) in [
+            (GridPosition::new(1, 1), MushroomType::Pulse),
+            (GridPosition::new(1, 2), MushroomType::Basic),
+        ] {
+            let entity = world
+                .spawn((Mushroom(mushroom_type), position, MushroomDirection::Up))
+                .id();
+            world
+                .resource_mut::<GameState>()
+                .play_field
+                .insert(position, entity);
+        }
+
+        world.run_system_once(build_playfield_connections).unwrap();
+        assert!(
+            world
+                .resource::<GameState>()
+                .play_field
+                .connections
+                .is_empty()
+        );
+
+        world.resource_mut::<MyceliumConnectionsEnabled>().0 = true;
+        world.run_system_once(build_playfield_connections).unwrap();
+        assert_eq!(
+            world.resource::<GameState>().play_field.connections.len(),
+            1
+        );
+    }
+}
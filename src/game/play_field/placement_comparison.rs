@@ -0,0 +1,226 @@
+//! Placement comparison panel
+//!
+//! Press C during planting to toggle a side panel that, for the hovered cell, lists every
+//! card type in hand with how many connections it would make there and the best chain
+//! the board would give with it placed. Only recomputed when the hover, rotation or hand changes.
+
+use bevy::{platform::collections::HashSet, prelude::*, text::FontSmoothing};
+
+use super::{
+    GridPosition, mycelium,
+    placement_preview::{HoveredCell, PreviewState},
+};
+use crate::{
+    game::{
+        carddeck::hand::Hand,
+        game_flow::TurnPhase,
+        mushrooms::{
+            Mushroom, MushroomDefinitions, MushroomDirection,
+            chain_activation::ChainConfig,
+            simulation::{SimulatedBoard, best_chain},
+            upgrades::MushroomUpgrades,
+        },
+        resources::{GameState, format_spores},
+        ui::GameplayUI,
+    },
+    theme::palette as ui_palette,
+};
+
+const TOGGLE_KEY: KeyCode = KeyCode::KeyC;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PlacementComparison>();
+    app.add_systems(
+        Update,
+        (toggle_comparison, update_comparison_panel)
+            .chain()
+            .run_if(in_state(TurnPhase::Planting)),
+    );
+}
+
+/// Whether the comparison panel is shown
+#[derive(Resource, Default, Debug)]
+pub struct PlacementComparison {
+    pub enabled: bool,
+}
+
+/// Marker for the comparison panel text
+#[derive(Component)]
+struct ComparisonPanel;
+
+/// Predicted result of placing one mushroom type on a cell
+struct PlacementEstimate {
+    name: String,
+    connections: usize,
+    spores: f64,
+}
+
+fn toggle_comparison(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut comparison: ResMut<PlacementComparison>,
+) {
+    if keyboard.just_pressed(TOGGLE_KEY) {
+        comparison.enabled = !comparison.enabled;
+    }
+}
+
+fn update_comparison_panel(
+    mut commands: Commands,
+    comparison: Res<PlacementComparison>,
+    hovered_cell: Res<HoveredCell>,
+    preview_state: Res<PreviewState>,
+    hand: Res<Hand>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    chain_config: Res<ChainConfig>,
+    game_state: Res<GameState>,
+    mushrooms: Query<(&GridPosition, &Mushroom, Option<&MushroomDirection>)>,
+    mut panel: Query<(Entity, &mut Text), With<ComparisonPanel>>,
+    asset_server: Res<AssetServer>,
+    mut last_hover: Local<Option<(Option<GridPosition>, MushroomDirection)>>,
+) {
+    if !comparison.enabled {
+        for (entity, _) in &panel {
+            commands.entity(entity).despawn();
+        }
+        *last_hover = None;
+        return;
+    }
+
+    let hover = (hovered_cell.position, preview_state.direction);
+    let needs_update = last_hover.as_ref() != Some(&hover)
+        || comparison.is_changed()
+        || hand.is_changed()
+        || game_state.is_changed()
+        || panel.is_empty();
+    if !needs_update {
+        return;
+    }
+    *last_hover = Some(hover);
+
+    let placeable = hovered_cell.position.filter(|position| {
+        game_state.play_field.get(*position).is_none()
+            && game_state
+                .play_field
+                .get_tile(*position)
+                .is_some_and(|tile| tile.allows_mushroom())
+    });
+
+    let text = match placeable {
+        Some(position) => {
+            let board: SimulatedBoard = mushrooms
+                .iter()
+                .map(|(pos, mushroom, direction)| {
+                    (*pos, (mushroom.0, direction.copied().unwrap_or_default()))
+                })
+                .collect();
+
+            let lines: Vec<String> = estimate_hand(
+                &definitions,
+                &upgrades,
+                &chain_config,
+                &game_state,
+                &board,
+                &hand,
+                position,
+                preview_state.direction,
+            )
+            .into_iter()
+            .map(|estimate| {
+                format!(
+                    "{}: {} links, {} spores",
+                    estimate.name,
+                    estimate.connections,
+                    format_spores(estimate.spores)
+                )
+            })
+            .collect();
+
+            if lines.is_empty() {
+                "No mushroom cards in hand".to_string()
+            } else {
+                format!("At ({}, {}):\n{}", position.x, position.y, lines.join("\n"))
+            }
+        }
+        None => "Hover an empty cell".to_string(),
+    };
+
+    if let Ok((_, mut panel_text)) = panel.single_mut() {
+        panel_text.0 = text;
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Placement Comparison"),
+        Text::new(text),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 16.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(ui_palette::LABEL_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(140.0),
+            left: Val::Px(20.0),
+            padding: UiRect::all(Val::Px(10.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ComparisonPanel,
+        GameplayUI,
+        StateScoped(TurnPhase::Planting),
+    ));
+}
+
+/// Estimate each distinct card type in hand placed at `position`
+fn estimate_hand(
+    definitions: &MushroomDefinitions,
+    upgrades: &MushroomUpgrades,
+    config: &ChainConfig,
+    game_state: &GameState,
+    board: &SimulatedBoard,
+    hand: &Hand,
+    position: GridPosition,
+    direction: MushroomDirection,
+) -> Vec<PlacementEstimate> {
+    let mut seen = HashSet::new();
+    let mut board = board.clone();
+    let mushrooms: Vec<_> = board
+        .iter()
+        .map(|(pos, (mushroom_type, direction))| (*pos, *mushroom_type, *direction))
+        .collect();
+
+    hand.iter()
+        .filter(|card| !card.wildcard)
+        .map(|card| card.mushroom_type)
+        .filter(|mushroom_type| seen.insert(*mushroom_type))
+        .filter_map(|mushroom_type| {
+            let definition = definitions.get(mushroom_type)?;
+            let connections = mycelium::count_new_connections(
+                definitions,
+                &game_state.play_field,
+                &mushrooms,
+                (position, mushroom_type, direction),
+            );
+
+            board.insert(position, (mushroom_type, direction));
+            let spores = best_chain(
+                definitions,
+                upgrades,
+                config,
+                &game_state.play_field,
+                &board,
+            )
+            .map_or(0.0, |(_, spores)| spores);
+            board.remove(&position);
+
+            Some(PlacementEstimate {
+                name: definition.name.clone(),
+                connections,
+                spores,
+            })
+        })
+        .collect()
+}
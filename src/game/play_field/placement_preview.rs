@@ -1,18 +1,28 @@
 //! Placement preview system for mushroom placement
 //!
 //! This module handles showing a preview of mushrooms before placement,
-//! including highlighting potential connections in the shader.
+//! including highlighting potential connections in the shader and a badge next to
+//! the previewed cell counting them.
 
-use bevy::{pbr::NotShadowReceiver, prelude::*};
+use bevy::{pbr::NotShadowReceiver, prelude::*, text::FontSmoothing};
 use bevy_sprite3d::{Sprite3dBuilder, Sprite3dParams};
 
-use crate::game::{
-    game_flow::{LevelState, TurnPhase},
-    level::assets::LevelAssets,
-    mushrooms::{Mushroom, MushroomDefinitions, MushroomDirection, SelectedMushroomType},
-    play_field::{GridPosition, PlayField, events::GridCell},
-    resources::GameState,
-    visual_effects::FaceCamera,
+use crate::{
+    MainCamera,
+    game::{
+        game_flow::{CurrentLevel, LevelState, TurnPhase},
+        level::assets::LevelAssets,
+        mushrooms::{
+            Mushroom, MushroomDefinitions, MushroomDirection, SelectedMushroomType,
+            definitions::MushroomDefinition,
+        },
+        play_field::{CELL_SIZE, GridPosition, PlayField, events::GridCell, mycelium},
+        resources::GameState,
+        ui::GameplayUI,
+        visual_effects::FaceCamera,
+    },
+    settings::{AlwaysShowConnections, KeyBindings, PreviewOpacity, SmartRotate},
+    theme::palette as ui_palette,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -38,22 +48,51 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
+            smart_rotate_preview,
             update_placement_preview,
+            update_placement_block,
             handle_preview_rotation,
             update_preview_connections,
             update_existing_mushroom_connections,
             apply_preview_transparency,
+            tint_blocked_preview,
         )
             .chain()
             .run_if(in_state(TurnPhase::Planting)),
     );
 
+    app.add_systems(
+        Update,
+        (
+            update_facing_label,
+            update_incoming_connections_label,
+            draw_hovered_cell_outline,
+        )
+            .run_if(in_state(TurnPhase::Planting)),
+    );
+
+    // Shows the preview's connection count, so it runs once that's up to date
+    app.add_systems(
+        Update,
+        update_connection_count_badge
+            .after(update_preview_connections)
+            .run_if(in_state(TurnPhase::Planting)),
+    );
+
     // Chain phase hover highlight
     app.add_systems(
         Update,
         update_chain_hover.run_if(in_state(TurnPhase::Chain)),
     );
 
+    // Connection targets outside planting, when the player wants them always shown
+    app.add_systems(
+        Update,
+        show_existing_connections_outside_planting
+            .after(update_chain_hover)
+            .run_if(in_state(LevelState::Playing).and(not(in_state(TurnPhase::Planting)))),
+    );
+
     // Cleanup
     app.add_systems(OnExit(TurnPhase::Planting), cleanup_preview);
     app.add_systems(OnExit(TurnPhase::Chain), clear_preview_connections);
@@ -78,6 +117,8 @@ pub struct PreviewConnections {
     pub existing_connection_targets: Vec<GridPosition>,
     /// The preview mushroom's position
     pub preview_position: Option<GridPosition>,
+    /// Connections placing the preview would form, counted the way the play field builds them
+    pub connection_count: usize,
 }
 
 /// State of the placement preview
@@ -87,8 +128,46 @@ pub struct PreviewState {
     pub direction: MushroomDirection,
     /// Entity of the preview mushroom
     pub preview_entity: Option<Entity>,
+    /// Whether `direction` was picked by smart rotate rather than the player
+    pub auto_rotated: bool,
+    /// Why the hovered cell can't take the selected mushroom, if it can't
+    pub blocked: Option<PlacementBlock>,
+}
+
+/// Reasons the preview can't be planted where it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementBlock {
+    Occupied,
+    BlockedTile,
+    Locked,
+}
+
+impl PlacementBlock {
+    /// Text shown next to the preview
+    pub fn description(&self) -> &'static str {
+        match self {
+            PlacementBlock::Occupied => "Cell is occupied",
+            PlacementBlock::BlockedTile => "Tile can't grow mushrooms",
+            PlacementBlock::Locked => "Mushroom is still locked",
+        }
+    }
 }
 
+/// Marker for the text showing which way the preview faces
+#[derive(Component)]
+struct FacingLabel;
+
+/// Marker for the text showing how many mushrooms connect to the hovered cell
+#[derive(Component)]
+struct IncomingConnectionsLabel;
+
+/// Marker for the connection count next to the previewed cell
+#[derive(Component)]
+struct ConnectionCountBadge;
+
+/// Gap between the previewed cell's centre and the connection count badge, in pixels
+const BADGE_CELL_OFFSET: Vec2 = Vec2::new(24.0, -12.0);
+
 /// Event fired when the hovered cell changes
 #[derive(Event, Debug)]
 #[allow(dead_code)]
@@ -105,17 +184,48 @@ pub struct PlacementPreview;
 #[derive(Component)]
 struct PreviewMarker;
 
+/// Height of the hovered cell outline, just above the invisible grid cells
+const HOVER_OUTLINE_HEIGHT: f32 = 0.11;
+/// Size of the hovered cell outline, a little inside the cell so neighbours stay apart
+const HOVER_OUTLINE_SIZE: f32 = CELL_SIZE * 0.94;
+const HOVER_OUTLINE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.5);
+
+/// Outline the hovered cell, drawn fresh every frame so it disappears with the hover
+fn draw_hovered_cell_outline(
+    mut gizmos: Gizmos,
+    hovered_cell: Res<HoveredCell>,
+    game_state: Res<GameState>,
+) {
+    let Some(position) = hovered_cell.position else {
+        return;
+    };
+
+    let world_pos = position.to_world_in(&game_state.play_field);
+    gizmos.rect(
+        Isometry3d::new(
+            Vec3::new(world_pos.x, HOVER_OUTLINE_HEIGHT, -world_pos.z),
+            Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+        ),
+        Vec2::splat(HOVER_OUTLINE_SIZE),
+        HOVER_OUTLINE_COLOR,
+    );
+}
+
 /// Update hover highlight during chain phase
 fn update_chain_hover(
     mut preview_connections: ResMut<PreviewConnections>,
     hovered_cell: Res<HoveredCell>,
     game_state: Res<GameState>,
+    always_show: Res<AlwaysShowConnections>,
 ) {
-    // Clear all highlights
+    // Clear all highlights, connection targets stay if they are always shown
     preview_connections.connected_positions.clear();
     preview_connections.empty_connection_points.clear();
-    preview_connections.existing_connection_targets.clear();
+    if !always_show.0 {
+        preview_connections.existing_connection_targets.clear();
+    }
     preview_connections.preview_position = None;
+    preview_connections.connection_count = 0;
 
     // Only highlight if there's a mushroom at the hovered position
     if let Some(position) = hovered_cell.position {
@@ -137,6 +247,7 @@ fn clear_preview_connections(
     preview_connections.empty_connection_points.clear();
     preview_connections.existing_connection_targets.clear();
     preview_connections.preview_position = None;
+    preview_connections.connection_count = 0;
     hovered_cell.position = None;
 
     // Also clean up any preview entity
@@ -197,9 +308,8 @@ fn update_placement_preview(
     for event in hover_changed.read() {
         match event.new_position {
             Some(position) => {
-                // Check if position is valid for placement
-                if !is_valid_placement_position(&position, &play_field.play_field) {
-                    // Hide preview if position is invalid
+                // Off the grid there is nothing to show, invalid cells keep a tinted preview
+                if !play_field.play_field.contains(position) {
                     if let Some(entity) = preview_state.preview_entity {
                         commands.entity(entity).despawn();
                         preview_state.preview_entity = None;
@@ -284,9 +394,61 @@ fn update_placement_preview(
     }
 }
 
+/// Work out whether the selected mushroom can be planted on the hovered cell
+fn update_placement_block(
+    mut preview_state: ResMut<PreviewState>,
+    hovered_cell: Res<HoveredCell>,
+    selected_type: Res<SelectedMushroomType>,
+    definitions: Res<MushroomDefinitions>,
+    game_state: Res<GameState>,
+    current_level: Res<CurrentLevel>,
+) {
+    let blocked = hovered_cell.position.and_then(|position| {
+        let field = &game_state.play_field;
+        if field.get(position).is_some() {
+            Some(PlacementBlock::Occupied)
+        } else if !is_valid_placement_position(&position, field) {
+            Some(PlacementBlock::BlockedTile)
+        } else if selected_type.mushroom_type.is_some_and(|mushroom_type| {
+            !definitions.is_unlocked(mushroom_type, &game_state, current_level.level_index)
+        }) {
+            Some(PlacementBlock::Locked)
+        } else {
+            None
+        }
+    });
+
+    if preview_state.blocked != blocked {
+        preview_state.blocked = blocked;
+    }
+}
+
+/// Tint the preview red while it sits on a cell it can't be planted on
+fn tint_blocked_preview(
+    preview_state: Res<PreviewState>,
+    opacity: Res<PreviewOpacity>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    preview_query: Query<&MeshMaterial3d<StandardMaterial>, With<PreviewMarker>>,
+) {
+    let color = preview_color(preview_state.blocked.is_some(), opacity.0);
+
+    for material_handle in preview_query.iter() {
+        // Only touch the material when it changes, writing it re-uploads it
+        if materials
+            .get(&material_handle.0)
+            .is_some_and(|material| material.base_color != color)
+        {
+            if let Some(material) = materials.get_mut(&material_handle.0) {
+                material.base_color = color;
+            }
+        }
+    }
+}
+
 /// Handle rotation input for the preview
 fn handle_preview_rotation(
     keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     mut preview_state: ResMut<PreviewState>,
     mut preview_query: Query<&mut MushroomDirection, With<PlacementPreview>>,
     hovered_cell: Res<HoveredCell>,
@@ -296,9 +458,11 @@ fn handle_preview_rotation(
         return;
     }
 
-    // Check for rotation input (R key)
-    if keyboard.just_pressed(KeyCode::KeyR) {
+    // Check for rotation input
+    if keyboard.just_pressed(bindings.rotate) {
         preview_state.direction = preview_state.direction.rotate_clockwise();
+        // Manual rotation wins until the next hover
+        preview_state.auto_rotated = false;
 
         // Update the preview entity
         if let Some(entity) = preview_state.preview_entity {
@@ -317,11 +481,13 @@ fn update_preview_connections(
     selected_type: Res<SelectedMushroomType>,
     definitions: Res<MushroomDefinitions>,
     play_field: Res<GameState>,
+    all_mushrooms: Query<(&GridPosition, &Mushroom, Option<&MushroomDirection>)>,
 ) {
     // Clear previous connections (but keep existing mushroom connections)
     preview_connections.connected_positions.clear();
     preview_connections.empty_connection_points.clear();
     preview_connections.preview_position = None;
+    preview_connections.connection_count = 0;
 
     // Only calculate if we have a valid hover position
     let Some(position) = hovered_cell.position else {
@@ -339,25 +505,53 @@ fn update_preview_connections(
     if selected_type.mushroom_type.is_none() {
         return;
     }
-    let Some(definition) = definitions.get(selected_type.mushroom_type.unwrap()) else {
+    let mushroom_type = selected_type.mushroom_type.unwrap();
+    let Some(definition) = definitions.get(mushroom_type) else {
         return;
     };
 
-    // Calculate connection points and categorize them
-    for connection_point in &definition.connection_points {
-        // Rotate connection point based on preview rotation
-        let rotated_offset = rotate_connection_point(connection_point, &preview_state.direction);
-        let target_pos =
-            GridPosition::new(position.x + rotated_offset.x, position.y + rotated_offset.y);
+    let (connected, empty) = categorize_connection_targets(
+        definition,
+        position,
+        preview_state.direction,
+        &play_field.play_field,
+    );
+    preview_connections.connected_positions = connected;
+    preview_connections.empty_connection_points = empty;
+
+    let mushrooms: Vec<_> = all_mushrooms
+        .iter()
+        .map(|(pos, mushroom, direction)| {
+            (*pos, mushroom.0, direction.copied().unwrap_or_default())
+        })
+        .collect();
+    preview_connections.connection_count = mycelium::count_new_connections(
+        &definitions,
+        &play_field.play_field,
+        &mushrooms,
+        (position, mushroom_type, preview_state.direction),
+    );
+}
 
+/// Split a mushroom's connection targets at `position` into (will connect, empty)
+fn categorize_connection_targets(
+    definition: &MushroomDefinition,
+    position: GridPosition,
+    direction: MushroomDirection,
+    play_field: &PlayField,
+) -> (Vec<GridPosition>, Vec<GridPosition>) {
+    let mut connected = Vec::new();
+    let mut empty = Vec::new();
+
+    // Calculate connection points and categorize them
+    for target_pos in play_field.topology.targets(position, definition, direction) {
         // Check if target position is within bounds
-        if !play_field.play_field.contains(target_pos) {
+        if !play_field.contains(target_pos) {
             continue;
         }
 
         // Check if target tile allows mushroom placement (for visual feedback)
         let target_allows_mushroom = play_field
-            .play_field
             .get_tile(target_pos)
             .map(|tile| tile.allows_mushroom())
             .unwrap_or(false);
@@ -367,76 +561,330 @@ fn update_preview_connections(
         }
 
         // Check if there's a mushroom at the target position
-        if play_field.play_field.get(target_pos).is_some() {
+        if play_field.get(target_pos).is_some() {
             // Will connect (green highlight)
-            preview_connections.connected_positions.push(target_pos);
+            connected.push(target_pos);
         } else {
             // Connection point but no mushroom (red highlight)
-            preview_connections.empty_connection_points.push(target_pos);
+            empty.push(target_pos);
         }
     }
+
+    (connected, empty)
+}
+
+/// Face the preview whichever way makes the most connections at the newly hovered cell
+fn smart_rotate_preview(
+    mut hover_changed: EventReader<CellHoverChanged>,
+    smart_rotate: Res<SmartRotate>,
+    mut preview_state: ResMut<PreviewState>,
+    selected_type: Res<SelectedMushroomType>,
+    definitions: Res<MushroomDefinitions>,
+    game_state: Res<GameState>,
+    mut preview_query: Query<&mut MushroomDirection, With<PlacementPreview>>,
+) {
+    let Some(position) = hover_changed.read().filter_map(|e| e.new_position).last() else {
+        return;
+    };
+    if !smart_rotate.0 {
+        return;
+    }
+    let Some(definition) = selected_type
+        .mushroom_type
+        .and_then(|mushroom_type| definitions.get(mushroom_type))
+    else {
+        return;
+    };
+
+    // Keep the current facing on ties, so the preview doesn't spin needlessly
+    let count = |direction: MushroomDirection| {
+        categorize_connection_targets(definition, position, direction, &game_state.play_field)
+            .0
+            .len()
+    };
+    let mut best = preview_state.direction;
+    let mut best_count = count(best);
+    let mut direction = best;
+    for _ in 0..3 {
+        direction = direction.rotate_clockwise();
+        let connections = count(direction);
+        if connections > best_count {
+            best = direction;
+            best_count = connections;
+        }
+    }
+
+    preview_state.auto_rotated = true;
+    if best != preview_state.direction {
+        preview_state.direction = best;
+        if let Some(entity) = preview_state.preview_entity {
+            if let Ok(mut facing) = preview_query.get_mut(entity) {
+                *facing = best;
+            }
+        }
+    }
+}
+
+/// Show which way the preview faces, and whether smart rotate chose it
+fn update_facing_label(
+    mut commands: Commands,
+    preview_state: Res<PreviewState>,
+    smart_rotate: Res<SmartRotate>,
+    mut labels: Query<(Entity, &mut Text, &mut TextColor), With<FacingLabel>>,
+    asset_server: Res<AssetServer>,
+) {
+    if preview_state.preview_entity.is_none() {
+        for (entity, _, _) in &labels {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let facing = if smart_rotate.0 && preview_state.auto_rotated {
+        format!("Facing: {:?} (auto)", preview_state.direction)
+    } else {
+        format!("Facing: {:?}", preview_state.direction)
+    };
+    let (text, color) = match preview_state.blocked {
+        Some(block) => (
+            format!("{facing}\nCan't plant: {}", block.description()),
+            Color::srgb(1.0, 0.4, 0.4),
+        ),
+        None => (facing, ui_palette::LABEL_TEXT),
+    };
+
+    if let Ok((_, mut label, mut label_color)) = labels.single_mut() {
+        if label.0 != text {
+            label.0 = text;
+        }
+        if label_color.0 != color {
+            label_color.0 = color;
+        }
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Facing Label"),
+        FacingLabel,
+        Text::new(text),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 18.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(color),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(240.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        GameplayUI,
+        StateScoped(TurnPhase::Planting),
+    ));
+}
+
+/// Tell the player how many placed mushrooms would pass energy to the hovered cell
+fn update_incoming_connections_label(
+    mut commands: Commands,
+    hovered_cell: Res<HoveredCell>,
+    preview_connections: Res<PreviewConnections>,
+    mut labels: Query<(Entity, &mut Text), With<IncomingConnectionsLabel>>,
+    asset_server: Res<AssetServer>,
+) {
+    // Targets hold one entry per connecting mushroom, so repeats are the count
+    let count = hovered_cell.position.map_or(0, |position| {
+        preview_connections
+            .existing_connection_targets
+            .iter()
+            .filter(|target| **target == position)
+            .count()
+    });
+
+    if count == 0 {
+        for (entity, _) in &labels {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let text = if count == 1 {
+        "1 mushroom can connect here".to_string()
+    } else {
+        format!("{count} mushrooms can connect here")
+    };
+
+    if let Ok((_, mut label)) = labels.single_mut() {
+        if label.0 != text {
+            label.0 = text;
+        }
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Incoming Connections Label"),
+        IncomingConnectionsLabel,
+        Text::new(text),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 18.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(ui_palette::LABEL_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(300.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        GameplayUI,
+        StateScoped(TurnPhase::Planting),
+    ));
+}
+
+/// Show how many mushrooms the preview would connect to, next to the previewed cell
+fn update_connection_count_badge(
+    mut commands: Commands,
+    hovered_cell: Res<HoveredCell>,
+    preview_state: Res<PreviewState>,
+    preview_connections: Res<PreviewConnections>,
+    game_state: Res<GameState>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    ui_scale: Res<UiScale>,
+    mut badges: Query<(Entity, &mut Text, &mut Node), With<ConnectionCountBadge>>,
+    asset_server: Res<AssetServer>,
+) {
+    let showing = hovered_cell.position.is_some()
+        && preview_state.preview_entity.is_some()
+        && preview_connections.preview_position.is_some();
+    let (camera, camera_transform) = *camera;
+    let anchor = preview_connections
+        .preview_position
+        .filter(|_| showing)
+        .and_then(|position| {
+            position.to_screen(
+                &game_state.play_field,
+                CELL_SIZE * 0.5,
+                camera,
+                camera_transform,
+            )
+        });
+    let Some(anchor) = anchor else {
+        for (entity, ..) in &badges {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let count = preview_connections.connection_count;
+    let text = if count == 1 {
+        "1 connection".to_string()
+    } else {
+        format!("{count} connections")
+    };
+    // The viewport is in logical pixels, UI pixels are scaled on top of that
+    let offset = (anchor + BADGE_CELL_OFFSET) / ui_scale.0.max(f32::EPSILON);
+
+    if let Ok((_, mut label, mut node)) = badges.single_mut() {
+        if label.0 != text {
+            label.0 = text;
+        }
+        if node.left != Val::Px(offset.x) || node.top != Val::Px(offset.y) {
+            node.left = Val::Px(offset.x);
+            node.top = Val::Px(offset.y);
+        }
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Connection Count Badge"),
+        ConnectionCountBadge,
+        Text::new(text),
+        TextFont {
+            font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+            font_size: 18.0,
+            font_smoothing: FontSmoothing::AntiAliased,
+            ..default()
+        },
+        TextColor(ui_palette::LABEL_TEXT),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(offset.x),
+            top: Val::Px(offset.y),
+            ..default()
+        },
+        GlobalZIndex(5),
+        Pickable::IGNORE,
+        GameplayUI,
+        StateScoped(TurnPhase::Planting),
+    ));
 }
 
 /// Show connection targets for all existing mushrooms during placement
 fn update_existing_mushroom_connections(
     mut preview_connections: ResMut<PreviewConnections>,
-    all_mushrooms: Query<(Entity, &GridPosition, &Mushroom, Option<&MushroomDirection>)>,
+    all_mushrooms: Query<(&GridPosition, &Mushroom, Option<&MushroomDirection>)>,
     definitions: Res<MushroomDefinitions>,
     play_field: Res<GameState>,
 ) {
-    // Clear existing connection targets
-    preview_connections.existing_connection_targets.clear();
+    preview_connections.existing_connection_targets =
+        existing_connection_targets(&all_mushrooms, &definitions, &play_field.play_field);
+}
+
+/// Keep showing connection targets outside placement when the setting is on
+/// Only writes when the targets change, so the shader buffers aren't rebuilt every frame
+fn show_existing_connections_outside_planting(
+    mut preview_connections: ResMut<PreviewConnections>,
+    all_mushrooms: Query<(&GridPosition, &Mushroom, Option<&MushroomDirection>)>,
+    definitions: Res<MushroomDefinitions>,
+    play_field: Res<GameState>,
+    always_show: Res<AlwaysShowConnections>,
+) {
+    let targets = if always_show.0 {
+        existing_connection_targets(&all_mushrooms, &definitions, &play_field.play_field)
+    } else {
+        Vec::new()
+    };
+    if preview_connections.existing_connection_targets != targets {
+        preview_connections.existing_connection_targets = targets;
+    }
+}
+
+/// Empty cells that placed mushrooms would connect to
+fn existing_connection_targets(
+    all_mushrooms: &Query<(&GridPosition, &Mushroom, Option<&MushroomDirection>)>,
+    definitions: &MushroomDefinitions,
+    play_field: &PlayField,
+) -> Vec<GridPosition> {
+    let mut targets = Vec::new();
 
     // Calculate connection targets for all placed mushrooms
-    for (_entity, pos, mushroom, direction) in all_mushrooms.iter() {
+    for (pos, mushroom, direction) in all_mushrooms.iter() {
         let Some(definition) = definitions.get(mushroom.0) else {
             continue;
         };
 
-        // Skip mushrooms with no connection points
-        if definition.connection_points.is_empty() {
-            continue;
-        }
-
         // Calculate where this mushroom can connect to
-        for connection_point in &definition.connection_points {
-            // Rotate connection point based on mushroom direction
-            let rotated_offset = if let Some(dir) = direction {
-                rotate_connection_point(connection_point, dir)
-            } else {
-                rotate_connection_point(connection_point, &MushroomDirection::Up)
-            };
-
-            let target_pos = GridPosition::new(pos.x + rotated_offset.x, pos.y + rotated_offset.y);
-
-            // Check if target position is within bounds and allows mushrooms
-            if !play_field.play_field.contains(target_pos) {
-                continue;
-            }
-
-            let target_allows_mushroom = play_field
-                .play_field
-                .get_tile(target_pos)
-                .map(|tile| tile.allows_mushroom())
-                .unwrap_or(false);
-
-            if !target_allows_mushroom {
-                continue;
-            }
-
+        let target_positions =
+            play_field
+                .topology
+                .targets(*pos, definition, direction.copied().unwrap_or_default());
+        for target_pos in target_positions {
             // Only highlight empty positions (where you could place a mushroom)
-            if play_field.play_field.get(target_pos).is_none() {
-                preview_connections
-                    .existing_connection_targets
-                    .push(target_pos);
+            if is_valid_placement_position(&target_pos, play_field) {
+                targets.push(target_pos);
             }
         }
     }
+
+    targets
 }
 
 /// Check if a position is valid for mushroom placement
 fn is_valid_placement_position(position: &GridPosition, play_field: &PlayField) -> bool {
-    // Check bounds
+    // Check bounds, holes count as outside the field
     if !play_field.contains(*position) {
         return false;
     }
@@ -454,21 +902,6 @@ fn is_valid_placement_position(position: &GridPosition, play_field: &PlayField)
     true
 }
 
-/// Rotate a connection point based on direction
-fn rotate_connection_point(
-    point: &crate::game::mushrooms::definitions::GridOffset,
-    direction: &MushroomDirection,
-) -> crate::game::mushrooms::definitions::GridOffset {
-    use crate::game::mushrooms::definitions::GridOffset;
-
-    match direction {
-        MushroomDirection::Up => GridOffset::new(point.x, point.y),
-        MushroomDirection::Right => GridOffset::new(point.y, -point.x),
-        MushroomDirection::Down => GridOffset::new(-point.x, -point.y),
-        MushroomDirection::Left => GridOffset::new(-point.y, point.x),
-    }
-}
-
 /// Cleanup preview when exiting planting phase
 fn cleanup_preview(
     mut commands: Commands,
@@ -487,12 +920,24 @@ fn cleanup_preview(
     preview_connections.empty_connection_points.clear();
     preview_connections.existing_connection_targets.clear();
     preview_connections.preview_position = None;
+    preview_connections.connection_count = 0;
     hovered_cell.position = None;
 }
 
+/// Preview tint, red and a little more solid when the cell can't be planted on
+fn preview_color(blocked: bool, opacity: f32) -> Color {
+    let opacity = opacity.clamp(PreviewOpacity::MIN, PreviewOpacity::MAX);
+    if blocked {
+        Color::srgba(1.0, 0.3, 0.3, (opacity + 0.1).min(1.0))
+    } else {
+        Color::srgba(1.0, 1.0, 1.0, opacity)
+    }
+}
+
 /// Apply transparency to preview materials
 fn apply_preview_transparency(
     mut materials: ResMut<Assets<StandardMaterial>>,
+    opacity: Res<PreviewOpacity>,
     preview_query: Query<
         &MeshMaterial3d<StandardMaterial>,
         (With<PreviewMarker>, Added<MeshMaterial3d<StandardMaterial>>),
@@ -500,7 +945,7 @@ fn apply_preview_transparency(
 ) {
     for material_handle in preview_query.iter() {
         if let Some(material) = materials.get_mut(&material_handle.0) {
-            material.base_color = Color::srgba(1.0, 1.0, 1.0, 0.5);
+            material.base_color = preview_color(false, opacity.0);
             material.alpha_mode = AlphaMode::Blend;
         }
     }
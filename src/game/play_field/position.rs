@@ -2,11 +2,14 @@
 
 use super::{CELL_SIZE, CELL_SPACING, PlayField};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub(super) fn plugin(_app: &mut App) {}
 
 /// Position on the grid
-#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect, Default)]
+#[derive(
+    Component, Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect, Default, Serialize, Deserialize,
+)]
 #[reflect(Component)]
 pub struct GridPosition {
     pub x: i32,
@@ -70,8 +73,143 @@ impl GridPosition {
         )
     }
 
-    /// Convert grid position to world coordinates using PlayField reference
+    /// Convert grid position to world coordinates using PlayField reference,
+    /// laid out for the field's topology
     pub fn to_world_in(self, field: &PlayField) -> Vec3 {
-        self.to_world(field.width, field.height)
+        self.to_world(field.width, field.height) + field.topology.world_offset(self, field.height)
+    }
+
+    /// Project the centre of this cell (raised by `height`) to viewport coordinates
+    /// through the given camera, typically the `MainCamera`
+    /// Returns None if the point is behind the camera or outside the viewport
+    pub fn to_screen(
+        self,
+        field: &PlayField,
+        height: f32,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let screen = camera
+            .world_to_viewport(camera_transform, self.to_scene(field, height))
+            .ok()?;
+        let viewport_size = camera.logical_viewport_size()?;
+        (screen.cmpge(Vec2::ZERO).all() && screen.cmple(viewport_size).all()).then_some(screen)
+    }
+
+    /// Centre of this cell (raised by `height`) in scene coordinates
+    fn to_scene(self, field: &PlayField, height: f32) -> Vec3 {
+        let world_pos = self.to_world_in(field);
+        // Scene entities are placed with the grid's z axis flipped
+        Vec3::new(world_pos.x, height, -world_pos.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{
+        asset::AssetEvent,
+        ecs::system::RunSystemOnce,
+        render::camera::{ManualTextureViews, camera_system},
+        window::{
+            PrimaryWindow, WindowCreated, WindowResized, WindowResolution, WindowScaleFactorChanged,
+        },
+    };
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    /// Camera straight above the field, looking down with the scene's -z at the top,
+    /// rendering to a `VIEWPORT` sized primary window
+    fn top_down_camera() -> (Camera, GlobalTransform) {
+        let mut world = World::new();
+        world.init_resource::<Events<WindowResized>>();
+        world.init_resource::<Events<WindowCreated>>();
+        world.init_resource::<Events<WindowScaleFactorChanged>>();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<ManualTextureViews>();
+        world.spawn((
+            Window {
+                resolution: WindowResolution::new(VIEWPORT.x, VIEWPORT.y),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let camera = world
+            .spawn((
+                Camera::default(),
+                Projection::Perspective(PerspectiveProjection::default()),
+            ))
+            .id();
+        // Computes the projection and viewport size the way the render app does
+        world.run_system_once(camera_system).unwrap();
+
+        let transform = Transform::from_xyz(0.0, 20.0, 0.0).looking_at(Vec3::ZERO, Vec3::NEG_Z);
+        (
+            world.get::<Camera>(camera).unwrap().clone(),
+            GlobalTransform::from(transform),
+        )
+    }
+
+    #[test]
+    fn centre_cell_projects_to_the_middle_of_the_viewport() {
+        let field = PlayField::new(5, 5);
+        let (camera, transform) = top_down_camera();
+        assert_eq!(camera.logical_viewport_size(), Some(VIEWPORT));
+
+        let screen = GridPosition::new(2, 2)
+            .to_screen(&field, 0.0, &camera, &transform)
+            .unwrap();
+
+        assert!((screen - VIEWPORT / 2.0).length() < 0.01, "{screen}");
+    }
+
+    #[test]
+    fn cells_keep_their_layout_on_screen() {
+        let field = PlayField::new(5, 5);
+        let (camera, transform) = top_down_camera();
+        let project = |x, y| {
+            GridPosition::new(x, y)
+                .to_screen(&field, 0.0, &camera, &transform)
+                .unwrap()
+        };
+
+        let centre = project(2, 2);
+        assert!(project(3, 2).x > centre.x);
+        // Higher rows are further up the screen
+        assert!(project(2, 3).y < centre.y);
+    }
+
+    #[test]
+    fn cells_off_screen_or_behind_the_camera_have_no_position() {
+        let field = PlayField::new(500, 500);
+        let (camera, transform) = top_down_camera();
+
+        let far_away = GridPosition::new(0, 0).to_screen(&field, 0.0, &camera, &transform);
+        assert_eq!(far_away, None);
+
+        let above_camera = GridPosition::new(250, 250).to_screen(&field, 30.0, &camera, &transform);
+        assert_eq!(above_camera, None);
+    }
+
+    #[test]
+    fn scene_position_is_raised_and_flips_the_grid_z() {
+        let field = PlayField::new(5, 5);
+        let position = GridPosition::new(1, 3);
+
+        let world = position.to_world_in(&field);
+        let scene = position.to_scene(&field, 1.5);
+
+        assert_eq!(scene, Vec3::new(world.x, 1.5, -world.z));
+    }
+
+    #[test]
+    fn higher_rows_go_towards_negative_scene_z() {
+        let field = PlayField::new(5, 5);
+        let scene = |x, y| GridPosition::new(x, y).to_scene(&field, 0.0);
+
+        let centre = scene(2, 2);
+        assert!(scene(3, 2).x > centre.x);
+        assert!(scene(2, 3).z < centre.z);
     }
 }
@@ -0,0 +1,145 @@
+//! Moving placed mushrooms during the planting phase
+//!
+//! Dragging from a cell with a mushroom picks it up, and releasing over an empty cell
+//! that allows mushrooms moves it there. Anywhere else it snaps back to where it was.
+//! Updating the `GridPosition` triggers the usual connection rebuild.
+
+use bevy::{picking::pointer::PointerButton, prelude::*};
+
+use super::{GridPosition, events::GridCell, placement_preview::HoveredCell};
+use crate::game::{
+    game_flow::TurnPhase,
+    level::recording::{RecordActionEvent, RunActionKind},
+    mushrooms::{Mushroom, MushroomDefinitions, SelectedMushroomType},
+    resources::GameState,
+};
+
+/// Height the mushroom floats at while being carried
+const CARRY_HEIGHT: f32 = 0.8;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RelocatingMushroom>();
+    app.add_observer(pick_up_mushroom);
+    app.add_observer(drop_mushroom);
+    app.add_systems(Update, carry_mushroom.run_if(in_state(TurnPhase::Planting)));
+    app.add_systems(OnExit(TurnPhase::Planting), cancel_relocation);
+}
+
+/// Mushroom currently being moved, and the cell it came from
+#[derive(Resource, Default, Debug)]
+pub struct RelocatingMushroom {
+    pub carried: Option<(Entity, GridPosition)>,
+}
+
+fn pick_up_mushroom(
+    trigger: Trigger<Pointer<DragStart>>,
+    grid_cells: Query<&GridCell>,
+    current_phase: Option<Res<State<TurnPhase>>>,
+    selected_type: Res<SelectedMushroomType>,
+    game_state: Res<GameState>,
+    mut relocating: ResMut<RelocatingMushroom>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    let Ok(cell) = grid_cells.get(trigger.target()) else {
+        return;
+    };
+    if current_phase.as_deref().map(State::get) != Some(&TurnPhase::Planting) {
+        return;
+    }
+    // A mushroom waiting to be planted takes priority
+    if selected_type.mushroom_type.is_some() {
+        return;
+    }
+
+    if let Some(entity) = game_state.play_field.get(cell.position) {
+        info!("Picked up mushroom at {:?}", cell.position);
+        relocating.carried = Some((entity, cell.position));
+    }
+}
+
+/// Float the carried mushroom over the hovered cell
+fn carry_mushroom(
+    relocating: Res<RelocatingMushroom>,
+    hovered_cell: Res<HoveredCell>,
+    game_state: Res<GameState>,
+    mut transforms: Query<&mut Transform>,
+) {
+    let Some((entity, _)) = relocating.carried else {
+        return;
+    };
+    let Some(position) = hovered_cell.position else {
+        return;
+    };
+    let Ok(mut transform) = transforms.get_mut(entity) else {
+        return;
+    };
+
+    let world_pos = position.to_world_in(&game_state.play_field);
+    transform.translation = Vec3::new(world_pos.x, CARRY_HEIGHT, -world_pos.z);
+}
+
+fn drop_mushroom(
+    trigger: Trigger<Pointer<DragEnd>>,
+    mut commands: Commands,
+    hovered_cell: Res<HoveredCell>,
+    mut relocating: ResMut<RelocatingMushroom>,
+    mut game_state: ResMut<GameState>,
+    definitions: Res<MushroomDefinitions>,
+    mut mushrooms: Query<(&Mushroom, &mut GridPosition, &mut Transform, &mut Name)>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    let Some((entity, from)) = relocating.carried.take() else {
+        return;
+    };
+    let Ok((mushroom, mut position, mut transform, mut name)) = mushrooms.get_mut(entity) else {
+        return;
+    };
+
+    let destination = hovered_cell.position.filter(|to| {
+        *to != from
+            && game_state.play_field.get(*to).is_none()
+            && game_state
+                .play_field
+                .get_tile(*to)
+                .is_some_and(|tile| tile.allows_mushroom())
+    });
+
+    let target = match destination {
+        Some(to) => {
+            info!("Moved mushroom from {:?} to {:?}", from, to);
+            game_state.play_field.remove(from);
+            game_state.play_field.insert(to, entity);
+            *position = to;
+            let label = &definitions.get_or_default(mushroom.0).name;
+            *name = Name::new(format!("{} at ({}, {})", label, to.x, to.y));
+            commands.trigger(RecordActionEvent(RunActionKind::Move { from, to }));
+            to
+        }
+        None => {
+            info!("Invalid destination, returning mushroom to {:?}", from);
+            from
+        }
+    };
+
+    let world_pos = target.to_world_in(&game_state.play_field);
+    transform.translation = Vec3::new(world_pos.x, 0.5, -world_pos.z);
+}
+
+/// Put a carried mushroom back if the phase ends mid-drag
+fn cancel_relocation(
+    mut relocating: ResMut<RelocatingMushroom>,
+    game_state: Res<GameState>,
+    mut transforms: Query<&mut Transform>,
+) {
+    let Some((entity, from)) = relocating.carried.take() else {
+        return;
+    };
+    if let Ok(mut transform) = transforms.get_mut(entity) {
+        let world_pos = from.to_world_in(&game_state.play_field);
+        transform.translation = Vec3::new(world_pos.x, 0.5, -world_pos.z);
+    }
+}
@@ -0,0 +1,85 @@
+//! Board snapshots for putting the field back the way it was
+
+use bevy::prelude::*;
+
+use super::{GridPosition, TileType, field_renderer::TilesDirty};
+use crate::game::{
+    mushrooms::{Mushroom, MushroomDirection, MushroomType, SpawnMushroomEvent, SpawnSource},
+    resources::GameState,
+};
+
+/// A copy of the board: tiles, placed mushrooms and the spore counters
+#[derive(Debug, Clone)]
+pub struct BoardSnapshot {
+    pub tiles: Vec<TileType>,
+    pub mushrooms: Vec<(GridPosition, MushroomType, MushroomDirection)>,
+    pub spores: f64,
+    pub total_spores_earned: f64,
+    pub total_activations: u64,
+    pub chain_activations: u64,
+}
+
+impl BoardSnapshot {
+    /// Capture the current board
+    pub fn capture<'a>(
+        game_state: &GameState,
+        mushrooms: impl IntoIterator<Item = (&'a Mushroom, &'a GridPosition, &'a MushroomDirection)>,
+    ) -> Self {
+        let mut mushrooms: Vec<_> = mushrooms
+            .into_iter()
+            .map(|(mushroom, position, direction)| (*position, mushroom.0, *direction))
+            .collect();
+        mushrooms.sort_by_key(|(position, _, _)| (position.y, position.x));
+
+        Self {
+            tiles: game_state.play_field.tiles.clone(),
+            mushrooms,
+            spores: game_state.spores,
+            total_spores_earned: game_state.total_spores_earned,
+            total_activations: game_state.total_activations,
+            chain_activations: game_state.chain_activations,
+        }
+    }
+
+    /// Put the board back to this snapshot
+    /// Existing mushroom entities are despawned and the snapshot's mushrooms respawned,
+    /// without using up a card or playing the placement sound
+    pub fn restore(
+        &self,
+        commands: &mut Commands,
+        game_state: &mut GameState,
+        existing_mushrooms: impl IntoIterator<Item = Entity>,
+        field_grounds: impl IntoIterator<Item = Entity>,
+    ) {
+        for entity in existing_mushrooms {
+            commands.entity(entity).despawn();
+        }
+        game_state.play_field.entities.clear();
+
+        if game_state.play_field.tiles.len() == self.tiles.len() {
+            game_state.play_field.tiles.clone_from(&self.tiles);
+        } else {
+            warn!("Board snapshot doesn't match the field size, keeping current tiles");
+        }
+
+        game_state.spores = self.spores;
+        game_state.total_spores_earned = self.total_spores_earned;
+        game_state.total_activations = self.total_activations;
+        game_state.chain_activations = self.chain_activations;
+
+        for (position, mushroom_type, direction) in &self.mushrooms {
+            commands.trigger(SpawnMushroomEvent {
+                position: *position,
+                mushroom_type: *mushroom_type,
+                direction: Some(*direction),
+                entity: None,
+                source: SpawnSource::Restored,
+            });
+        }
+
+        // Rebuild the tile texture in case anything was converted
+        for entity in field_grounds {
+            commands.entity(entity).insert(TilesDirty);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Clearing the board quickly during the planting phase
+//!
+//! Hold Shift and the right mouse button, then move over the field to delete every
+//! mushroom the cursor passes over. The camera doesn't pan while sweeping. Ctrl+Z puts
+//! the last sweep back before planting ends, as long as the cells are still free.
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use super::{GridPosition, placement_preview::HoveredCell};
+use crate::{
+    MainCamera,
+    game::{
+        game_flow::TurnPhase,
+        level::{
+            playback::BestRunPlayback,
+            recording::{RecordActionEvent, RunActionKind},
+        },
+        mushrooms::{Mushroom, MushroomDirection, MushroomType, SpawnMushroomEvent, SpawnSource},
+        resources::GameState,
+    },
+};
+
+const SWEEP_BUTTON: MouseButton = MouseButton::Right;
+const SWEEP_MODIFIERS: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
+const UNDO_KEY: KeyCode = KeyCode::KeyZ;
+const UNDO_MODIFIERS: [KeyCode; 2] = [KeyCode::ControlLeft, KeyCode::ControlRight];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SweepDelete>();
+    app.add_systems(
+        Update,
+        (update_sweep, sweep_hovered_cell, undo_sweep)
+            .chain()
+            // The board belongs to the playback while a best run is being watched
+            .run_if(in_state(TurnPhase::Planting).and(not(resource_exists::<BestRunPlayback>))),
+    );
+    app.add_systems(OnExit(TurnPhase::Planting), end_sweep);
+}
+
+/// The sweep in progress, and the mushrooms the last one deleted
+#[derive(Resource, Default, Debug)]
+struct SweepDelete {
+    active: bool,
+    /// Mushrooms deleted by the current or last sweep, in the order they went
+    swept: Vec<(GridPosition, MushroomType, MushroomDirection)>,
+}
+
+/// Start a sweep on Shift + right press, end it on release
+fn update_sweep(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut sweep: ResMut<SweepDelete>,
+    mut cameras: Query<&mut PanOrbitCamera, With<MainCamera>>,
+) {
+    if !sweep.active
+        && mouse_buttons.just_pressed(SWEEP_BUTTON)
+        && keyboard.any_pressed(SWEEP_MODIFIERS)
+    {
+        sweep.active = true;
+        sweep.swept.clear();
+        // Right drag pans the camera otherwise
+        for mut camera in &mut cameras {
+            camera.enabled = false;
+        }
+    } else if sweep.active && !mouse_buttons.pressed(SWEEP_BUTTON) {
+        sweep.active = false;
+        info!("Sweep deleted {} mushrooms", sweep.swept.len());
+        for mut camera in &mut cameras {
+            camera.enabled = true;
+        }
+    }
+}
+
+/// Delete the mushroom under the cursor while sweeping
+fn sweep_hovered_cell(
+    mut commands: Commands,
+    mut sweep: ResMut<SweepDelete>,
+    hovered_cell: Res<HoveredCell>,
+    mut game_state: ResMut<GameState>,
+    mushrooms: Query<(&Mushroom, &MushroomDirection)>,
+) {
+    if !sweep.active {
+        return;
+    }
+    let Some(position) = hovered_cell.position else {
+        return;
+    };
+    let Some(entity) = game_state.play_field.get(position) else {
+        return;
+    };
+    let Ok((mushroom, direction)) = mushrooms.get(entity) else {
+        return;
+    };
+
+    info!("Sweep deleting mushroom at {:?}", position);
+    sweep.swept.push((position, mushroom.0, *direction));
+    commands.entity(entity).despawn();
+    game_state.play_field.remove(position);
+    commands.trigger(RecordActionEvent(RunActionKind::Remove { position }));
+}
+
+/// Put the mushrooms from the last sweep back
+fn undo_sweep(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut sweep: ResMut<SweepDelete>,
+    game_state: Res<GameState>,
+) {
+    if sweep.active || !keyboard.just_pressed(UNDO_KEY) || !keyboard.any_pressed(UNDO_MODIFIERS) {
+        return;
+    }
+    if sweep.swept.is_empty() {
+        info!("No sweep to undo");
+        return;
+    }
+
+    let mut restored = 0;
+    for (position, mushroom_type, direction) in sweep.swept.drain(..) {
+        if game_state.play_field.get(position).is_some() {
+            info!(
+                "Can't restore mushroom at {:?}, the cell is taken",
+                position
+            );
+            continue;
+        }
+        commands.trigger(SpawnMushroomEvent {
+            position,
+            mushroom_type,
+            direction: Some(direction),
+            entity: None,
+            source: SpawnSource::Restored,
+        });
+        commands.trigger(RecordActionEvent(RunActionKind::Place {
+            mushroom_type,
+            position,
+            direction: Some(direction),
+        }));
+        restored += 1;
+    }
+    info!("Undid sweep, restored {} mushrooms", restored);
+}
+
+/// End any sweep when planting ends, sweeps can only be undone in the phase they happened
+fn end_sweep(
+    mut sweep: ResMut<SweepDelete>,
+    mut cameras: Query<&mut PanOrbitCamera, With<MainCamera>>,
+) {
+    sweep.swept.clear();
+    if sweep.active {
+        sweep.active = false;
+        for mut camera in &mut cameras {
+            camera.enabled = true;
+        }
+    }
+}
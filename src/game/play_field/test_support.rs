@@ -0,0 +1,35 @@
+//! Play field fixtures shared by the play field tests
+
+use bevy::prelude::*;
+
+use super::{GridPosition, PlayField, mycelium::mycelium_links};
+use crate::game::mushrooms::{MushroomDefinitions, MushroomDirection, MushroomType};
+
+/// Place the mushrooms and connect them the way the mycelium system does
+pub(crate) fn connected_field(
+    mut field: PlayField,
+    mushrooms: &[(GridPosition, MushroomType, MushroomDirection)],
+) -> PlayField {
+    for (index, (position, ..)) in mushrooms.iter().enumerate() {
+        field.insert(*position, Entity::from_raw(index as u32));
+    }
+    let definitions = MushroomDefinitions::standard();
+    for link in mycelium_links(&definitions, &field, mushrooms) {
+        let (from, to) = (field.get(link.from).unwrap(), field.get(link.to).unwrap());
+        field.add_connection(link.from, link.to, from, to, link.strength, link.path);
+    }
+    field
+}
+
+/// A mushroom at `(x, y)` facing up
+pub(crate) fn at(
+    x: i32,
+    y: i32,
+    mushroom_type: MushroomType,
+) -> (GridPosition, MushroomType, MushroomDirection) {
+    (
+        GridPosition::new(x, y),
+        mushroom_type,
+        MushroomDirection::Up,
+    )
+}
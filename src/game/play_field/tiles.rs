@@ -1,18 +1,36 @@
 //! Tile types and terrain system for the play field
 
-use bevy::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
+
+use super::{
+    GridPosition,
+    field_renderer::{FieldGround, TilesDirty},
+};
+use crate::game::{
+    game_flow::{LevelState, TurnPhase, practice::PracticeMode},
+    resources::GameState,
+};
+
+/// Score phases a nutrient tile lasts, counting the one it appeared in
+pub const NUTRIENT_LIFETIME_TURNS: u32 = 3;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<TileType>();
+    app.init_resource::<TileDecay>();
+    app.add_systems(OnEnter(LevelState::StartDialogue), clear_tile_decay);
+    app.add_systems(OnExit(TurnPhase::Score), decay_nutrient_tiles);
 }
 
 /// Different types of tiles that affect gameplay
-#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Reflect)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Reflect, Deserialize)]
 #[reflect(Component)]
 pub enum TileType {
     #[default]
     Empty,
     Fertile,
+    /// Richer than fertile soil, but turns back to empty soil after a few turns
+    Nutrient,
     BlockedRock,
     BlockedWater,
     BlockedMoss,
@@ -24,6 +42,7 @@ impl TileType {
         match self {
             TileType::Empty => 1.0,
             TileType::Fertile => 1.25,
+            TileType::Nutrient => 1.75,
             TileType::BlockedRock | TileType::BlockedWater | TileType::BlockedMoss => 0.0,
         }
     }
@@ -36,10 +55,17 @@ impl TileType {
         )
     }
 
+    /// Can an unblocker clear this tile? Water stays put
+    pub fn can_be_cleared(&self) -> bool {
+        matches!(self, TileType::BlockedRock | TileType::BlockedMoss)
+    }
+
     /// Can mycelium grow through this tile?
     pub fn allows_mycelium(&self) -> bool {
         match self {
-            TileType::Empty | TileType::Fertile | TileType::BlockedMoss => true,
+            TileType::Empty | TileType::Fertile | TileType::Nutrient | TileType::BlockedMoss => {
+                true
+            }
             TileType::BlockedRock | TileType::BlockedWater => false,
         }
     }
@@ -48,8 +74,70 @@ impl TileType {
     pub fn mycelium_strength_modifier(&self) -> f32 {
         match self {
             TileType::Empty => 1.0,
-            TileType::Fertile => 1.0,
+            TileType::Fertile | TileType::Nutrient => 1.0,
             TileType::BlockedRock | TileType::BlockedWater | TileType::BlockedMoss => 0.0,
         }
     }
 }
+
+/// Turns left on each nutrient tile
+/// Tiles are picked up the first score phase they are seen in, however they became nutrient
+#[derive(Resource, Default, Debug)]
+pub struct TileDecay {
+    pub remaining: HashMap<GridPosition, u32>,
+}
+
+fn clear_tile_decay(mut decay: ResMut<TileDecay>) {
+    decay.remaining.clear();
+}
+
+/// Count down every nutrient tile and turn the expired ones back into empty soil
+fn decay_nutrient_tiles(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut decay: ResMut<TileDecay>,
+    practice: Res<PracticeMode>,
+    field_grounds: Query<Entity, With<FieldGround>>,
+) {
+    // Practice turns don't use up the real turn, so the tiles don't age either
+    if practice.active {
+        return;
+    }
+
+    let field = &game_state.play_field;
+    let nutrient_tiles: Vec<GridPosition> = (0..field.height)
+        .flat_map(|y| (0..field.width).map(move |x| GridPosition::new(x, y)))
+        .filter(|position| field.get_tile(*position) == Some(TileType::Nutrient))
+        .collect();
+
+    // Forget tiles that were converted to something else
+    decay
+        .remaining
+        .retain(|position, _| nutrient_tiles.contains(position));
+
+    let mut expired = Vec::new();
+    for position in nutrient_tiles {
+        let turns = decay
+            .remaining
+            .entry(position)
+            .or_insert(NUTRIENT_LIFETIME_TURNS);
+        *turns = turns.saturating_sub(1);
+        if *turns == 0 {
+            expired.push(position);
+        }
+    }
+
+    if expired.is_empty() {
+        return;
+    }
+
+    for position in expired {
+        decay.remaining.remove(&position);
+        game_state.play_field.set_tile(position, TileType::Empty);
+        info!("Nutrient tile at {:?} decayed to empty soil", position);
+    }
+
+    for entity in &field_grounds {
+        commands.entity(entity).insert(TilesDirty);
+    }
+}
@@ -0,0 +1,215 @@
+//! How grid cells neighbour each other
+//!
+//! Connection patterns are written as offsets on a square grid facing up. The topology
+//! turns those into actual cells, so a level can lay its cells out differently without
+//! every mushroom needing its own pattern per layout.
+//!
+//! - `Square`: offsets are used as-is, rotated in 90 degree steps
+//! - `Hex`: pointy-top hexes in "odd-r" rows, where odd rows sit half a cell to the right.
+//!   Offsets are read as axial coordinates and rotated in 60 degree steps: right and left
+//!   turn one step either way, down turns halfway around. Cells, mushrooms and click
+//!   targets are shifted into place; the ground texture underneath is still square.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::{CELL_SIZE, CELL_SPACING, GridPosition};
+use crate::game::mushrooms::{
+    MushroomDirection,
+    definitions::{GridOffset, MushroomDefinition},
+};
+
+/// Cell layout used by a level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Deserialize)]
+pub enum GridTopology {
+    #[default]
+    Square,
+    #[allow(dead_code)]
+    Hex,
+}
+
+/// The six axial neighbour offsets of a hex cell
+const HEX_NEIGHBOURS: [(i32, i32); 6] = [(1, -1), (1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1)];
+
+impl GridTopology {
+    /// The cell reached from `position` by a connection offset, with the mushroom facing `direction`
+    pub fn target(
+        self,
+        position: GridPosition,
+        offset: &GridOffset,
+        direction: MushroomDirection,
+    ) -> GridPosition {
+        match self {
+            GridTopology::Square => {
+                let rotated = offset.rotated(direction);
+                GridPosition::new(position.x + rotated.x, position.y + rotated.y)
+            }
+            GridTopology::Hex => {
+                let (q, r) = hex_rotate((offset.x, offset.y), hex_turns(direction));
+                let (origin_q, origin_r) = offset_to_axial(position);
+                axial_to_offset(origin_q + q, origin_r + r)
+            }
+        }
+    }
+
+    /// Every cell a mushroom at `position` facing `direction` connects to
+    /// The square grid reads the definition's cached rotations
+    pub fn targets(
+        self,
+        position: GridPosition,
+        definition: &MushroomDefinition,
+        direction: MushroomDirection,
+    ) -> Vec<GridPosition> {
+        match self {
+            GridTopology::Square => definition
+                .connection_points_facing(direction)
+                .iter()
+                .map(|offset| GridPosition::new(position.x + offset.x, position.y + offset.y))
+                .collect(),
+            GridTopology::Hex => definition
+                .connection_points
+                .iter()
+                .map(|offset| self.target(position, offset, direction))
+                .collect(),
+        }
+    }
+
+    /// Cells sharing an edge with `position`
+    pub fn neighbours(self, position: GridPosition) -> Vec<GridPosition> {
+        match self {
+            GridTopology::Square => position.adjacent_cardinal().to_vec(),
+            GridTopology::Hex => {
+                let (q, r) = offset_to_axial(position);
+                HEX_NEIGHBOURS
+                    .iter()
+                    .map(|(dq, dr)| axial_to_offset(q + dq, r + dr))
+                    .collect()
+            }
+        }
+    }
+
+    /// Invisible shape each cell is clicked through, matching the cell's outline
+    pub fn cell_mesh(self) -> Mesh {
+        match self {
+            GridTopology::Square => Rectangle::new(CELL_SIZE, CELL_SIZE).into(),
+            // Pointy-top, the flat sides are a cell apart like the square's
+            GridTopology::Hex => RegularPolygon::new(CELL_SIZE / 3f32.sqrt(), 6).into(),
+        }
+    }
+
+    /// Shift from the square layout to where the cell sits in this layout,
+    /// on a field `field_height` rows tall
+    pub fn world_offset(self, position: GridPosition, field_height: i32) -> Vec3 {
+        match self {
+            GridTopology::Square => Vec3::ZERO,
+            GridTopology::Hex => {
+                let total_cell_size = CELL_SIZE + CELL_SPACING;
+                let shift = if position.y & 1 == 1 { 0.5 } else { 0.0 };
+                // Rows pack closer together, squashed towards the middle row
+                let row_squash = (1.0 - 3f32.sqrt() / 2.0) * total_cell_size;
+                let from_middle = position.y as f32 - (field_height - 1) as f32 / 2.0;
+                Vec3::new(shift * total_cell_size, 0.0, -row_squash * from_middle)
+            }
+        }
+    }
+}
+
+/// Sixth-turns for a mushroom facing `direction`
+fn hex_turns(direction: MushroomDirection) -> u32 {
+    match direction {
+        MushroomDirection::Up => 0,
+        MushroomDirection::Right => 1,
+        MushroomDirection::Down => 3,
+        MushroomDirection::Left => 5,
+    }
+}
+
+/// Rotate an axial offset clockwise by `turns` sixth-turns, like the square grid's right turn
+fn hex_rotate((mut q, mut r): (i32, i32), turns: u32) -> (i32, i32) {
+    for _ in 0..turns % 6 {
+        (q, r) = (q + r, -q);
+    }
+    (q, r)
+}
+
+fn offset_to_axial(position: GridPosition) -> (i32, i32) {
+    let q = position.x - (position.y - (position.y & 1)) / 2;
+    (q, position.y)
+}
+
+fn axial_to_offset(q: i32, r: i32) -> GridPosition {
+    GridPosition::new(q + (r - (r & 1)) / 2, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::mushrooms::{MushroomDefinitions, MushroomType};
+
+    #[test]
+    fn six_hex_turns_come_back_around() {
+        assert_eq!(hex_rotate((1, 0), 1), (1, -1));
+        assert_eq!(hex_rotate((1, 0), 3), (-1, 0));
+        assert_eq!(hex_rotate((2, -1), 6), (2, -1));
+        // Down is halfway around, the same as three single turns
+        let down = hex_rotate((1, -1), hex_turns(MushroomDirection::Down));
+        assert_eq!(down, (-1, 1));
+    }
+
+    #[test]
+    fn facings_turn_an_offset_in_sixty_degree_steps() {
+        // Straight up on an even row reaches the up-right cell, odd rows sit to the right
+        let offset = GridOffset::new(0, 1);
+        let origin = GridPosition::new(2, 2);
+        let target = |direction| GridTopology::Hex.target(origin, &offset, direction);
+
+        assert_eq!(target(MushroomDirection::Up), GridPosition::new(2, 3));
+        assert_eq!(target(MushroomDirection::Right), GridPosition::new(3, 2));
+        assert_eq!(target(MushroomDirection::Left), GridPosition::new(1, 3));
+        assert_eq!(target(MushroomDirection::Down), GridPosition::new(1, 1));
+    }
+
+    #[test]
+    fn hex_targets_rotate_every_connection_point() {
+        let definitions = MushroomDefinitions::standard();
+        let definition = definitions.get(MushroomType::Pulse).unwrap();
+        let origin = GridPosition::new(3, 3);
+
+        for direction in MushroomDirection::ALL {
+            let targets = GridTopology::Hex.targets(origin, definition, direction);
+            let expected: Vec<_> = definition
+                .connection_points
+                .iter()
+                .map(|offset| GridTopology::Hex.target(origin, offset, direction))
+                .collect();
+            assert_eq!(targets, expected);
+        }
+    }
+
+    #[test]
+    fn hex_cells_have_six_neighbours_that_lead_back() {
+        for origin in [GridPosition::new(2, 2), GridPosition::new(2, 3)] {
+            let neighbours = GridTopology::Hex.neighbours(origin);
+            assert_eq!(neighbours.len(), 6);
+            for neighbour in neighbours {
+                assert!(GridTopology::Hex.neighbours(neighbour).contains(&origin));
+            }
+        }
+    }
+
+    #[test]
+    fn odd_hex_rows_sit_half_a_cell_right() {
+        let total_cell_size = CELL_SIZE + CELL_SPACING;
+        let even = GridTopology::Hex.world_offset(GridPosition::new(0, 2), 5);
+        let odd = GridTopology::Hex.world_offset(GridPosition::new(0, 3), 5);
+
+        assert_eq!(even.x, 0.0);
+        assert_eq!(odd.x, total_cell_size / 2.0);
+        // The middle row doesn't move, so the field stays centred
+        assert_eq!(even.z, 0.0);
+        assert_eq!(
+            GridTopology::Square.world_offset(GridPosition::new(0, 3), 5),
+            Vec3::ZERO
+        );
+    }
+}
@@ -7,6 +7,31 @@ pub(super) fn plugin(app: &mut App) {
     app.init_resource::<GameState>();
 }
 
+/// Largest spore value the game keeps track of, so stacked amplifiers can't overflow to `inf`
+pub const MAX_SPORES: f64 = 1.0e15;
+
+/// Keep a spore value finite and within `0..=MAX_SPORES`
+pub fn clamp_spores(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, MAX_SPORES)
+    }
+}
+
+/// Short display form of a spore value, like `950`, `12.5K` or `3.2M`
+pub fn format_spores(value: f64) -> String {
+    let value = clamp_spores(value);
+    const SUFFIXES: [(f64, &str); 4] = [(1.0e12, "T"), (1.0e9, "B"), (1.0e6, "M"), (1.0e3, "K")];
+
+    for (threshold, suffix) in SUFFIXES {
+        if value >= threshold {
+            return format!("{:.1}{suffix}", value / threshold);
+        }
+    }
+    format!("{value:.0}")
+}
+
 /// Core game state tracking the game field, spores and progression
 #[derive(Resource, Reflect, Debug)]
 #[reflect(Resource)]
@@ -22,6 +47,10 @@ pub struct GameState {
     /// The play field
     #[reflect(ignore)]
     pub play_field: PlayField,
+    /// Rolled once per run, each level's tile visuals are derived from it
+    pub render_seed: u32,
+    /// Cards earned from bonus goals, added to the next draw even if it's on the next level
+    pub bonus_cards: u32,
 }
 
 impl Default for GameState {
@@ -32,14 +61,16 @@ impl Default for GameState {
             total_activations: 0,
             chain_activations: 0,
             play_field: PlayField::new(6, 6),
+            render_seed: rand::random(),
+            bonus_cards: 0,
         }
     }
 }
 
 impl GameState {
     pub fn add_spores(&mut self, amount: f64) {
-        self.spores += amount;
-        self.total_spores_earned += amount;
+        self.spores = clamp_spores(self.spores + amount);
+        self.total_spores_earned = clamp_spores(self.total_spores_earned + amount);
     }
 
     #[allow(dead_code)]
@@ -0,0 +1,110 @@
+//! Game-wide random numbers
+//!
+//! Every level attempt draws its seed from [`GameRng`], and the per-level streams in
+//! [`CurrentLevel::rng`](crate::game::game_flow::CurrentLevel::rng) are derived from
+//! that seed. Seeding `GameRng` with a fixed value makes a whole run reproduce the
+//! same card draws, reshuffles, dialogue picks and tile conversions.
+//!
+//! Set the `GAME_SEED` environment variable to a number to fix the seed for a whole
+//! session. Dev builds can also toggle a fixed seed with Insert.
+
+use bevy::prelude::*;
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+
+/// Environment variable holding a fixed seed for the session
+const SEED_ENV_VAR: &str = "GAME_SEED";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GameRng>();
+    app.add_systems(Startup, seed_from_env);
+}
+
+/// Fix the seed from [`SEED_ENV_VAR`] when it's set, so QA can reproduce a run in any build
+fn seed_from_env(mut game_rng: ResMut<GameRng>) {
+    let Ok(value) = std::env::var(SEED_ENV_VAR) else {
+        return;
+    };
+    match parse_seed(&value) {
+        Some(seed) => {
+            game_rng.reseed(Some(seed));
+            info!("Game seed fixed to {:?} from {SEED_ENV_VAR}", game_rng.seed);
+        }
+        None => warn!("Ignoring {SEED_ENV_VAR}={value:?}, it isn't a number"),
+    }
+}
+
+fn parse_seed(value: &str) -> Option<u64> {
+    value.trim().parse().ok()
+}
+
+/// Source of randomness for gameplay, seeded from entropy unless a seed is given
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    /// Seed the generator was created from, `None` when seeded from entropy
+    pub seed: Option<u64>,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        Self { seed, rng }
+    }
+
+    /// Restart the generator from `seed`, or from entropy with `None`
+    pub fn reseed(&mut self, seed: Option<u64>) {
+        *self = Self::new(seed);
+    }
+
+    /// A fresh seed for one level attempt
+    pub fn next_seed(&mut self) -> u64 {
+        self.rng.random()
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_is_parsed_from_a_number_only() {
+        assert_eq!(parse_seed(" 1234\n"), Some(1234));
+        assert_eq!(parse_seed("abc"), None);
+        assert_eq!(parse_seed(""), None);
+    }
+
+    #[test]
+    fn reseeding_restarts_the_same_sequence() {
+        let mut rng = GameRng::new(Some(1234));
+        let first: Vec<u64> = (0..5).map(|_| rng.next_seed()).collect();
+
+        rng.reseed(Some(1234));
+        let second: Vec<u64> = (0..5).map(|_| rng.next_seed()).collect();
+
+        assert_eq!(rng.seed, Some(1234));
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,442 @@
+//! Saving and resuming progress between sessions
+//!
+//! Progress is auto-saved whenever the player moves on to the next level, leaves
+//! gameplay or quits the game, and can be saved by hand from the pause menu. It's read
+//! once at startup. Native builds write a RON file next to the game, through a temporary
+//! file so a crash mid-write keeps the old save. Web builds use the browser's local
+//! storage. The main menu's "Continue" button resumes from the save.
+//!
+//! Unlocks aren't restored directly, they follow from the restored spores, level and
+//! chain count just like during play. The saved list is only checked against them.
+//!
+//! Best runs and deck presets aren't tied to the level being resumed, they're filled in
+//! whenever the save is written and loaded as soon as it's read.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        carddeck::{
+            deck::{Deck, DiscardPile},
+            hand::Hand,
+            presets::{ActiveDeckPreset, DeckPreset, DeckPresets},
+        },
+        game_flow::{CurrentLevel, LevelState},
+        level::{
+            definitions::LevelDefinitions,
+            recording::{BestRuns, RunRecording},
+        },
+        mushrooms::{MushroomDefinitions, MushroomType, upgrades::MushroomUpgrades},
+        resources::GameState,
+    },
+    settings::AutoSave,
+};
+
+/// File next to the game on native builds, local storage key on the web
+#[cfg(not(target_family = "wasm"))]
+const SAVE_NAME: &str = "save.ron";
+#[cfg(target_family = "wasm")]
+const SAVE_NAME: &str = "psychocybin_save";
+/// Name of the preset the saved deck is restored into
+const SAVED_DECK_PRESET: &str = "Saved deck";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SavedGame>();
+    app.init_resource::<ResumeLevel>();
+    app.init_resource::<CompletedLevelDeck>();
+    app.add_systems(Startup, load_game);
+    app.add_systems(OnEnter(LevelState::Success), snapshot_completed_deck);
+    app.add_systems(Last, save_on_exit.run_if(on_event::<AppExit>));
+    app.add_observer(save_game);
+    app.add_observer(save_progress);
+    app.add_observer(continue_game);
+}
+
+/// Everything needed to pick a run back up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    /// Level to resume at
+    pub level_index: usize,
+    pub total_spores_earned: f64,
+    pub chain_activations: u64,
+    /// Mushroom types unlocked when the game was saved
+    pub unlocked: Vec<MushroomType>,
+    /// Cards in the deck and hand, by type
+    pub deck: Vec<(MushroomType, u32)>,
+    /// Times each mushroom type has been levelled up
+    #[serde(default)]
+    pub upgrades: Vec<(MushroomType, u32)>,
+    /// Highest scoring run for each level, saves from before runs were kept have none
+    #[serde(default, with = "stored_runs")]
+    pub best_runs: Vec<RunRecording>,
+    /// Deck presets, in the order they were saved
+    #[serde(default)]
+    pub presets: Vec<DeckPreset>,
+}
+
+impl SaveData {
+    /// Capture the run so it resumes at `level_index` with `deck`
+    /// Best runs and presets are added when the save is written
+    pub fn capture(
+        level_index: usize,
+        game_state: &GameState,
+        definitions: &MushroomDefinitions,
+        upgrades: &MushroomUpgrades,
+        deck: &[(MushroomType, u32)],
+    ) -> Self {
+        let unlocked = definitions
+            .all_types()
+            .into_iter()
+            .filter(|mushroom_type| *mushroom_type != MushroomType::Test)
+            .filter(|mushroom_type| {
+                definitions.is_unlocked(*mushroom_type, game_state, level_index)
+            })
+            .collect();
+
+        Self {
+            level_index,
+            total_spores_earned: game_state.total_spores_earned,
+            chain_activations: game_state.chain_activations,
+            unlocked,
+            deck: deck.to_vec(),
+            upgrades: upgrades.to_saved(),
+            best_runs: Vec::new(),
+            presets: Vec::new(),
+        }
+    }
+}
+
+/// Best runs are written as one RON string each, so a run that no longer parses is
+/// dropped on its own instead of making the whole save unreadable
+mod stored_runs {
+    use serde::{Deserialize, Deserializer, Serializer, ser};
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        runs: &[RunRecording],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let stored = runs
+            .iter()
+            .map(|run| ron::to_string(run).map_err(ser::Error::custom))
+            .collect::<Result<Vec<_>, _>>()?;
+        serializer.collect_seq(stored)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<RunRecording>, D::Error> {
+        let stored = Vec::<ron::Value>::deserialize(deserializer)?;
+        Ok(stored
+            .into_iter()
+            .filter_map(|value| {
+                let ron::Value::String(run) = value else {
+                    warn!("Dropping a best run saved in an older format");
+                    return None;
+                };
+                ron::from_str(&run)
+                    .inspect_err(|error| warn!("Dropping a best run that can't be read: {error}"))
+                    .ok()
+            })
+            .collect())
+    }
+}
+
+/// The save read at startup, or the last one written, `None` if there is no save
+#[derive(Resource, Default, Debug)]
+pub struct SavedGame(pub Option<SaveData>);
+
+/// Cards left in the deck and hand when the level was won
+///
+/// Taken on entering [`LevelState::Success`], as the hand is emptied by the same
+/// action that saves the game.
+#[derive(Resource, Default, Debug)]
+pub struct CompletedLevelDeck(pub Vec<(MushroomType, u32)>);
+
+/// Level the next gameplay session starts at, set when continuing from a save
+#[derive(Resource, Default, Debug)]
+pub struct ResumeLevel(pub Option<usize>);
+
+impl ResumeLevel {
+    /// Take the level to start at, clamped to the levels that exist
+    pub fn take_clamped(&mut self, level_definitions: &LevelDefinitions) -> usize {
+        let index = self.0.take().unwrap_or(0);
+        let last = level_definitions.levels.len().saturating_sub(1);
+        if index > last {
+            warn!(
+                "Save is at level {} but there are only {} levels, starting at the last one",
+                index + 1,
+                level_definitions.levels.len()
+            );
+        }
+        index.min(last)
+    }
+}
+
+/// Write the save to disk, or local storage on the web
+#[derive(Event, Debug)]
+pub struct SaveGame(pub SaveData);
+
+/// Save the level in progress, once the game has moved past it if it was won
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveProgress {
+    /// Leaving gameplay or quitting, skipped when [`AutoSave`] is off
+    Auto,
+    /// "Save Now" from the pause menu
+    Manual,
+}
+
+/// Restore progress from the save before gameplay starts
+#[derive(Event, Debug)]
+pub struct ContinueGame;
+
+fn load_game(
+    mut saved_game: ResMut<SavedGame>,
+    mut best_runs: ResMut<BestRuns>,
+    mut presets: ResMut<DeckPresets>,
+) {
+    let Some(contents) = read_stored(SAVE_NAME) else {
+        return;
+    };
+
+    match ron::from_str::<SaveData>(&contents) {
+        Ok(data) => {
+            info!("Found a save at level {}", data.level_index + 1);
+            best_runs.load(&data.best_runs);
+            presets.presets.clone_from(&data.presets);
+            saved_game.0 = Some(data);
+        }
+        Err(error) => warn!("Ignoring unreadable save: {error}"),
+    }
+}
+
+fn snapshot_completed_deck(
+    deck: Res<Deck>,
+    hand: Res<Hand>,
+    discard: Res<DiscardPile>,
+    mut completed_deck: ResMut<CompletedLevelDeck>,
+) {
+    completed_deck.0 = DeckPreset::from_cards(SAVED_DECK_PRESET, &deck, &hand, &discard).counts;
+}
+
+fn save_game(
+    trigger: Trigger<SaveGame>,
+    mut saved_game: ResMut<SavedGame>,
+    best_runs: Res<BestRuns>,
+    presets: Res<DeckPresets>,
+) {
+    let mut data = trigger.event().0.clone();
+    data.best_runs = best_runs.to_saved();
+    data.presets.clone_from(&presets.presets);
+    match ron::ser::to_string_pretty(&data, default()) {
+        Ok(contents) => {
+            write_stored(SAVE_NAME, &contents);
+            info!("Saved progress at level {}", data.level_index + 1);
+        }
+        Err(error) => warn!("Couldn't serialize the save: {error}"),
+    }
+    saved_game.0 = Some(data);
+}
+
+fn save_progress(
+    trigger: Trigger<SaveProgress>,
+    auto_save: Res<AutoSave>,
+    level_state: Res<State<LevelState>>,
+    current_level: Res<CurrentLevel>,
+    level_definitions: Res<LevelDefinitions>,
+    game_state: Res<GameState>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    completed_deck: Res<CompletedLevelDeck>,
+    presets: Res<DeckPresets>,
+    active_preset: Res<ActiveDeckPreset>,
+    mut commands: Commands,
+) {
+    if *trigger.event() == SaveProgress::Auto && !auto_save.0 {
+        return;
+    }
+    if matches!(
+        level_state.get(),
+        LevelState::NotPlaying | LevelState::GameComplete
+    ) {
+        return;
+    }
+
+    let data = if current_level.level_completed_successfully == Some(true) {
+        // Won, pick up at the next level with the cards that were left
+        let next_index = current_level.level_index + 1;
+        if next_index >= level_definitions.levels.len() {
+            info!("Last level won, nothing left to save");
+            return;
+        }
+        SaveData::capture(
+            next_index,
+            &game_state,
+            &definitions,
+            &upgrades,
+            &completed_deck.0,
+        )
+    } else {
+        // Restart the level with the deck it was built from
+        let deck = active_preset
+            .0
+            .as_deref()
+            .and_then(|name| presets.get(name))
+            .map(|preset| preset.counts.clone())
+            .unwrap_or_default();
+        SaveData::capture(
+            current_level.level_index,
+            &game_state,
+            &definitions,
+            &upgrades,
+            &deck,
+        )
+    };
+    commands.trigger(SaveGame(data));
+}
+
+/// Closing the window or quitting from the menu both send [`AppExit`], the save is
+/// written before the app stops at the end of the frame
+fn save_on_exit(mut commands: Commands) {
+    commands.trigger(SaveProgress::Auto);
+}
+
+fn continue_game(
+    _: Trigger<ContinueGame>,
+    saved_game: Res<SavedGame>,
+    mut resume_level: ResMut<ResumeLevel>,
+    mut game_state: ResMut<GameState>,
+    mut presets: ResMut<DeckPresets>,
+    mut active_preset: ResMut<ActiveDeckPreset>,
+    mut upgrades: ResMut<MushroomUpgrades>,
+    definitions: Res<MushroomDefinitions>,
+) {
+    let Some(data) = &saved_game.0 else {
+        warn!("Nothing to continue, there is no save");
+        return;
+    };
+
+    game_state.total_spores_earned = data.total_spores_earned;
+    game_state.chain_activations = data.chain_activations;
+    upgrades.load(&data.upgrades);
+    resume_level.0 = Some(data.level_index);
+
+    presets.save(DeckPreset {
+        name: SAVED_DECK_PRESET.to_string(),
+        counts: data.deck.clone(),
+    });
+    active_preset.0 = Some(SAVED_DECK_PRESET.to_string());
+
+    for mushroom_type in &data.unlocked {
+        if !definitions.is_unlocked(*mushroom_type, &game_state, data.level_index) {
+            warn!(
+                "{:?} was unlocked in the save but isn't after restoring it",
+                mushroom_type
+            );
+        }
+    }
+}
+
+/// Read a file written by [`write_stored`], `name` is a path natively and a local storage
+/// key on the web
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn read_stored(name: &str) -> Option<String> {
+    std::fs::read_to_string(name).ok()
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn write_stored(name: &str, contents: &str) {
+    // Written next to it first, renaming replaces the old file in one step so it's never
+    // left half written
+    let temp_path = format!("{name}.tmp");
+    let result =
+        std::fs::write(&temp_path, contents).and_then(|()| std::fs::rename(&temp_path, name));
+    if let Err(error) = result {
+        warn!("Couldn't write {name}: {error}");
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+#[cfg(target_family = "wasm")]
+pub(crate) fn read_stored(name: &str) -> Option<String> {
+    local_storage()?.get_item(name).ok().flatten()
+}
+
+#[cfg(target_family = "wasm")]
+pub(crate) fn write_stored(name: &str, contents: &str) {
+    let saved = local_storage().is_some_and(|storage| storage.set_item(name, contents).is_ok());
+    if !saved {
+        warn!("Couldn't write {name} to local storage");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        level::{
+            definitions::LevelDefinition,
+            recording::{RECORDING_VERSION, RunAction, RunActionKind},
+        },
+        play_field::GridPosition,
+    };
+
+    #[test]
+    fn unreadable_best_run_is_dropped_and_progress_kept() {
+        let run = |level_index, kind| RunRecording {
+            version: RECORDING_VERSION,
+            level_index,
+            score: 100.0,
+            actions: vec![RunAction {
+                turn: 1,
+                time: 0.5,
+                kind,
+            }],
+        };
+        let position = GridPosition::new(1, 1);
+        let data = SaveData {
+            level_index: 3,
+            total_spores_earned: 1200.0,
+            chain_activations: 40,
+            unlocked: vec![MushroomType::Basic],
+            deck: vec![(MushroomType::Basic, 10)],
+            upgrades: vec![],
+            best_runs: vec![
+                run(0, RunActionKind::StartChain { position }),
+                run(1, RunActionKind::Remove { position }),
+            ],
+            presets: vec![],
+        };
+        let contents = ron::ser::to_string_pretty(&data, default()).unwrap();
+        // As if a later build had dropped the `Remove` action
+        let contents = contents.replace("Remove", "Uproot");
+
+        let loaded = ron::from_str::<SaveData>(&contents).unwrap();
+
+        assert_eq!(loaded.level_index, 3);
+        assert_eq!(loaded.total_spores_earned, 1200.0);
+        assert_eq!(loaded.best_runs.len(), 1);
+        assert_eq!(loaded.best_runs[0].level_index, 0);
+    }
+
+    #[test]
+    fn resume_level_is_clamped_to_the_last_level() {
+        let level_definitions = LevelDefinitions {
+            levels: vec![LevelDefinition::default(); 3],
+        };
+
+        let mut resume = ResumeLevel(Some(7));
+        assert_eq!(resume.take_clamped(&level_definitions), 2);
+        // Taken, so the next session starts from the beginning
+        assert_eq!(resume.take_clamped(&level_definitions), 0);
+
+        let mut resume = ResumeLevel(Some(1));
+        assert_eq!(resume.take_clamped(&level_definitions), 1);
+    }
+}
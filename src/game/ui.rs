@@ -4,11 +4,21 @@ use bevy::{ecs::spawn::SpawnWith, prelude::*};
 
 use crate::{
     game::{
-        game_flow::{CurrentLevel, LevelState, TurnData, TurnPhase},
-        mushrooms::{ChainManager, MushroomDefinitions, MushroomType, SelectedMushroomType},
-        resources::GameState,
+        game_flow::{
+            CurrentLevel, GoalCelebration, LevelState, TurnData, TurnPhase,
+            practice::{PracticeMode, StartPracticeEvent},
+        },
+        mushrooms::{
+            ChainManager, MushroomDefinitions, MushroomType, SelectedMushroomType,
+            chain_activation::{ChainConfig, ChainSpeed},
+            definitions::{MushroomDefinition, NEEDS_DOWNSTREAM_HINT},
+            upgrades::MushroomUpgrades,
+        },
+        resources::{GameState, clamp_spores, format_spores},
     },
+    menus::Menu,
     screens::Screen,
+    settings::ReducedMotion,
     theme::{
         assets::ThemeAssets, interaction::InteractionPalette, palette as ui_palette,
         widget::slice_1_slicer,
@@ -29,13 +39,33 @@ pub(super) fn plugin(app: &mut App) {
             update_turn_phase_display,
             update_level_progress_display,
             update_chain_info,
+            update_next_unlock_display,
+            update_bonus_goals_display,
+            update_mushroom_info_panel,
         )
             .run_if(in_state(Screen::Gameplay)),
     );
 
+    // Dialogue runs outside of `Playing`, so Enter can't advance while choosing a reply.
+    // The goal celebration moves on by itself, so Enter waits for it too.
     app.add_systems(
         Update,
-        (control_ui_visibility, update_phase_button).chain().run_if(in_state(Screen::Gameplay)),
+        advance_phase_on_key.run_if(
+            in_state(LevelState::Playing)
+                .and(in_state(Menu::None))
+                .and(not(resource_exists::<GoalCelebration>)),
+        ),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            control_ui_visibility,
+            update_phase_button,
+            update_practice_button,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
     );
 }
 
@@ -47,6 +77,14 @@ struct SporeDisplay;
 #[derive(Component)]
 struct StatsDisplay;
 
+/// Marker for the next unlock display
+#[derive(Component)]
+struct NextUnlockDisplay;
+
+/// Marker for the bonus goals display
+#[derive(Component)]
+struct BonusGoalsDisplay;
+
 /// Marker for the turn phase display
 #[derive(Component)]
 struct TurnPhaseDisplay;
@@ -59,6 +97,10 @@ struct LevelProgressDisplay;
 #[derive(Component)]
 struct PhaseAdvanceButton;
 
+/// Component for the practice run button
+#[derive(Component)]
+struct PracticeButton;
+
 /// Component for mushroom purchase buttons
 #[derive(Component)]
 struct MushroomButton {
@@ -69,6 +111,10 @@ struct MushroomButton {
 #[derive(Component)]
 struct ChainInfoDisplay;
 
+/// Marker for the selected mushroom's info panel
+#[derive(Component)]
+struct MushroomInfoPanel;
+
 fn spawn_game_ui(
     mut commands: Commands,
     _definitions: Res<MushroomDefinitions>,
@@ -132,6 +178,30 @@ fn spawn_game_ui(
                 StatsDisplay,
             ));
 
+            parent.spawn((
+                Name::new("Next Unlock"),
+                Text::new(""),
+                TextFont {
+                    font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(ui_palette::LABEL_TEXT),
+                NextUnlockDisplay,
+            ));
+
+            parent.spawn((
+                Name::new("Bonus Goals"),
+                Text::new(""),
+                TextFont {
+                    font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(ui_palette::LABEL_TEXT),
+                BonusGoalsDisplay,
+            ));
+
             // Add turn phase display
             // parent.spawn((
             //     Name::new("Turn Phase"),
@@ -200,55 +270,106 @@ fn spawn_game_ui(
         })
         .observe(advance_phase_on_click);
 
-    // Side panel for mushroom selection
-    //     commands
-    //         .spawn((
-    //             Name::new("Game UI - Side Panel"),
-    //             Node {
-    //                 position_type: PositionType::Absolute,
-    //                 top: Val::Px(100.0),
-    //                 right: Val::Px(10.0),
-    //                 width: Val::Px(300.0),
-    //                 padding: UiRect::all(Val::Px(20.0)),
-    //                 flex_direction: FlexDirection::Column,
-    //                 row_gap: Val::Px(15.0),
-    //                 ..default()
-    //             },
-    //             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
-    //             StateScoped(Screen::Gameplay),
-    //             GameplayUI,
-    //             children![
-    //                 (
-    //                     Name::new("Mushroom Selection Header"),
-    //                     Text::new("Mushrooms"),
-    //                     TextFont::from_font_size(28.0),
-    //                     TextColor(ui_palette::HEADER_TEXT),
-    //                 ),
-    //                 (
-    //                     Name::new("Instructions"),
-    //                     Text::new("Hover to preview placement\nPress R to rotate preview\nClick to place mushroom\nRight-click to delete"),
-    //                     TextFont::from_font_size(16.0),
-    //                     TextColor(ui_palette::LABEL_TEXT),
-    //                 ),
-    //                 spawn_mushroom_button(MushroomType::Basic, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Pulse, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Fork, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Sideways, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Threeway, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Diagonal, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Surround, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Skipper, &definitions),
-    // //                spawn_mushroom_button(MushroomType::Deleter, &definitions),
-    // //                spawn_mushroom_button(MushroomType::Bomb, &definitions),
-    // //                spawn_mushroom_button(MushroomType::Burst, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Amplifier, &definitions),
-    //                 spawn_mushroom_button(MushroomType::Fourway_amplifier, &definitions),
-    // //                spawn_mushroom_button(MushroomType::Splitter, &definitions),
-    // //                spawn_mushroom_button(MushroomType::Chain, &definitions),
-    // //                spawn_mushroom_button(MushroomType::Converter, &definitions),
-    // //                spawn_mushroom_button(MushroomType::Knight, &definitions),
-    //             ],
-    //         ));
+    // Add practice run button
+    commands
+        .spawn((
+            Name::new("Practice Control"),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(350.0),
+                right: Val::Percent(5.0),
+                width: Val::Px(200.0),
+                height: Val::Px(50.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Button,
+            StateScoped(Screen::Gameplay),
+            PracticeButton,
+            GameplayUI,
+            ImageNode {
+                image: theme_assets.slice_1.clone(),
+                image_mode: NodeImageMode::Sliced(slice_1_slicer()),
+                color: Color::WHITE,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Button Text"),
+                Text::new("Practice Chain"),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextFont {
+                    font: asset_server.load("fonts/PixelOperatorMonoHB.ttf"),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Pickable::IGNORE,
+            ));
+        })
+        .observe(|_: Trigger<Pointer<Click>>, mut commands: Commands| {
+            commands.trigger(StartPracticeEvent);
+        });
+
+    // Side panel with details of the selected mushroom
+    let font = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
+    let info_font = |size: f32| TextFont {
+        font: font.clone(),
+        font_size: size,
+        ..default()
+    };
+    commands.spawn((
+        Name::new("Game UI - Mushroom Info Panel"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(100.0),
+            right: Val::Px(10.0),
+            width: Val::Px(300.0),
+            padding: UiRect::all(Val::Px(20.0)),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(10.0),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        StateScoped(Screen::Gameplay),
+        GameplayUI,
+        MushroomInfoPanel,
+        children![
+            (
+                Name::new("Mushroom Info Name"),
+                Text::new(""),
+                info_font(24.0),
+                TextColor(ui_palette::HEADER_TEXT),
+            ),
+            (
+                Name::new("Mushroom Info Description"),
+                Text::new(""),
+                info_font(16.0),
+                TextColor(ui_palette::LABEL_TEXT),
+            ),
+            (
+                Name::new("Mushroom Info Stats"),
+                Text::new(""),
+                info_font(16.0),
+                TextColor(ui_palette::LABEL_TEXT),
+            ),
+            (
+                Name::new("Mushroom Info Pattern"),
+                Text::new(""),
+                info_font(18.0),
+                TextColor(Color::WHITE),
+            ),
+            (
+                Name::new("Mushroom Info Unlock"),
+                Text::new(""),
+                info_font(14.0),
+                TextColor(ui_palette::LABEL_TEXT),
+            ),
+        ],
+    ));
 }
 
 #[allow(dead_code)]
@@ -326,6 +447,8 @@ fn spawn_mushroom_button(
 fn update_turn_phase_display(
     current_phase: Option<Res<State<TurnPhase>>>,
     current_level_state: Res<State<LevelState>>,
+    chain_manager: Res<ChainManager>,
+    chain_speed: Res<ChainSpeed>,
     mut phase_display: Query<
         (&mut Text, &mut TextColor),
         (With<TurnPhaseDisplay>, Without<LevelProgressDisplay>),
@@ -338,7 +461,10 @@ fn update_turn_phase_display(
                     match phase.get() {
                         TurnPhase::Draw => "",
                         TurnPhase::Planting => "PLANTING PHASE - Place mushrooms on the grid",
-                        TurnPhase::Chain => "CHAIN PHASE - Click a mushroom to start the reaction",
+                        TurnPhase::Chain if chain_manager.has_active_chains() => {
+                            "CHAIN PHASE - Chain reaction in progress"
+                        }
+                        TurnPhase::Chain => "CHAIN PHASE - Start a chain or end the turn",
                         TurnPhase::Score => "",
                     }
                 } else {
@@ -353,7 +479,19 @@ fn update_turn_phase_display(
             LevelState::GameComplete => "",
         };
 
-        text.0 = format!("{phase_text}");
+        // Show the chain speed while a chain is running
+        let chain_running = current_phase
+            .as_ref()
+            .is_some_and(|phase| *phase.get() == TurnPhase::Chain)
+            && chain_manager.has_active_chains();
+        let speed_hint = match *chain_speed {
+            _ if !chain_running => "",
+            ChainSpeed::Normal => " (Tab to speed up)",
+            ChainSpeed::Double => " (2x)",
+            ChainSpeed::Quadruple => " (4x)",
+        };
+
+        text.0 = format!("{phase_text}{speed_hint}");
 
         // Change color based on phase
         if let Some(ref phase) = current_phase {
@@ -375,12 +513,10 @@ fn update_phase_button(
 ) {
     if let Ok((children, mut visibility)) = button.single_mut() {
         // Update button visibility
+        // Stays visible during the chain phase so the turn can always be ended
         *visibility = if *current_level_state.get() == LevelState::Playing {
-            if let Some(ref phase) = current_phase {
-                match phase.get() {
-                    TurnPhase::Chain => Visibility::Hidden,
-                    _ => Visibility::Inherited,
-                }
+            if current_phase.is_some() {
+                Visibility::Inherited
             } else {
                 Visibility::Hidden
             }
@@ -405,12 +541,83 @@ fn update_phase_button(
     }
 }
 
+fn update_practice_button(
+    current_phase: Option<Res<State<TurnPhase>>>,
+    practice: Res<PracticeMode>,
+    mut button: Query<(&Children, &mut Visibility), With<PracticeButton>>,
+    mut texts: Query<&mut Text>,
+) {
+    let Ok((children, mut visibility)) = button.single_mut() else {
+        return;
+    };
+
+    let phase = current_phase.map(|p| p.get().clone());
+
+    // Offer a practice run while planting, and label the run while it plays out
+    if *visibility != Visibility::Hidden {
+        *visibility = if practice.active || phase == Some(TurnPhase::Planting) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    if let Some(&text_entity) = children.first() {
+        if let Ok(mut text) = texts.get_mut(text_entity) {
+            text.0 = if practice.active {
+                "Practicing..."
+            } else {
+                "Practice Chain"
+            }
+            .to_string();
+        }
+    }
+}
+
 fn advance_phase_on_click(
     _: Trigger<Pointer<Click>>,
+    current_phase: Option<Res<State<TurnPhase>>>,
+    next_phase: ResMut<NextState<TurnPhase>>,
+    turn_data: ResMut<TurnData>,
+    game_state: Res<GameState>,
+    chain_manager: ResMut<ChainManager>,
+) {
+    advance_phase(
+        current_phase,
+        next_phase,
+        turn_data,
+        game_state,
+        chain_manager,
+    );
+}
+
+/// Enter does the same as clicking the phase button
+fn advance_phase_on_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_phase: Option<Res<State<TurnPhase>>>,
+    next_phase: ResMut<NextState<TurnPhase>>,
+    turn_data: ResMut<TurnData>,
+    game_state: Res<GameState>,
+    chain_manager: ResMut<ChainManager>,
+) {
+    if !keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        return;
+    }
+    advance_phase(
+        current_phase,
+        next_phase,
+        turn_data,
+        game_state,
+        chain_manager,
+    );
+}
+
+fn advance_phase(
     current_phase: Option<Res<State<TurnPhase>>>,
     mut next_phase: ResMut<NextState<TurnPhase>>,
     mut turn_data: ResMut<TurnData>,
     game_state: Res<GameState>,
+    mut chain_manager: ResMut<ChainManager>,
 ) {
     if let Some(ref phase) = current_phase {
         let next = match phase.get() {
@@ -423,7 +630,11 @@ fn advance_phase_on_click(
                 }
                 TurnPhase::Chain
             },
-            TurnPhase::Chain => TurnPhase::Score,
+            TurnPhase::Chain => {
+                // End the turn even if a chain is still running
+                chain_manager.flush();
+                TurnPhase::Score
+            },
             TurnPhase::Score => {
                 turn_data.current_turn += 1;
                 TurnPhase::Draw
@@ -433,6 +644,58 @@ fn advance_phase_on_click(
     }
 }
 
+fn update_next_unlock_display(
+    game_state: Res<GameState>,
+    current_level: Res<CurrentLevel>,
+    definitions: Res<MushroomDefinitions>,
+    mut display: Query<&mut Text, With<NextUnlockDisplay>>,
+) {
+    let Ok(mut text) = display.single_mut() else {
+        return;
+    };
+
+    let next = definitions.next_unlock(&game_state, current_level.level_index);
+    text.0 = match next.and_then(|(mushroom_type, progress)| {
+        definitions.get(mushroom_type).map(|def| (def, progress))
+    }) {
+        Some((definition, progress)) => format!(
+            "Next unlock: {} at {} - {}/{}",
+            definition.name,
+            progress.goal,
+            format_spores(progress.current),
+            format_spores(progress.required),
+        ),
+        None => "All mushrooms unlocked!".to_string(),
+    };
+}
+
+fn update_bonus_goals_display(
+    current_level: Res<CurrentLevel>,
+    mut display: Query<&mut Text, With<BonusGoalsDisplay>>,
+) {
+    if !current_level.is_changed() {
+        return;
+    }
+    let Ok(mut text) = display.single_mut() else {
+        return;
+    };
+
+    text.0 = current_level
+        .bonus_goals
+        .iter()
+        .zip(&current_level.bonus_completed)
+        .map(|(goal, completed)| {
+            format!(
+                "Bonus {} {} ({})",
+                if *completed { "[x]" } else { "[ ]" },
+                goal.objective.description(),
+                goal.reward.description()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
 fn update_level_progress_display(
     current_level: Res<CurrentLevel>,
     turn_data: Res<TurnData>,
@@ -440,26 +703,74 @@ fn update_level_progress_display(
 ) {
     if let Ok(mut text) = progress_display.single_mut() {
         text.0 = format!(
-            "Level {} - Turn {}/{}",
+            "Level {} - Turn {}/{}{}",
             current_level.level_index + 1,
             turn_data.current_turn,
             current_level.max_turns,
+            if current_level.overtime {
+                " - OVERTIME"
+            } else {
+                ""
+            },
         );
     }
 }
 
+/// How quickly the spore counter closes a small gap to the real total, per second
+const SPORE_COUNTER_RATE: f64 = 12.0;
+
+/// Move the shown spore count towards `target` over `delta_secs`
+///
+/// Each frame closes a fraction of the gap, and the fraction grows with every digit in
+/// the gap, so a huge payout lands about as quickly as a small one.
+fn tick_spore_counter(displayed: f64, target: f64, delta_secs: f64) -> f64 {
+    let gap = target - displayed;
+    if gap < 1.0 {
+        return target;
+    }
+    let rate = SPORE_COUNTER_RATE * gap.max(10.0).log10();
+    let step = 1.0 - (-rate * delta_secs).exp();
+    displayed + gap * step
+}
+
 fn update_spore_display(
     game_state: Res<GameState>,
     mut spore_display: Query<&mut Text, (With<SporeDisplay>, Without<StatsDisplay>)>,
     mut stats_display: Query<&mut Text, (With<StatsDisplay>, Without<SporeDisplay>)>,
     current_level: Res<CurrentLevel>,
+    chain_manager: Res<ChainManager>,
+    turn_phase: Option<Res<State<TurnPhase>>>,
+    practice: Res<PracticeMode>,
+    reduced_motion: Res<ReducedMotion>,
+    time: Res<Time>,
+    mut displayed_spores: Local<f64>,
 ) {
+    // Count up towards the real total so payouts tick in. While chains run, that's the
+    // level total plus what this turn's chains have made so far, which is what the
+    // score phase adds on. Practice chains don't count towards the level.
+    let in_chain = turn_phase.is_some_and(|phase| *phase.get() == TurnPhase::Chain);
+    let running: f64 = if in_chain && !practice.active {
+        chain_manager
+            .chains
+            .iter()
+            .map(|chain| chain.total_spores)
+            .sum()
+    } else {
+        0.0
+    };
+    let target = clamp_spores(current_level.total_spores_earned + running);
+    *displayed_spores = if reduced_motion.0 {
+        target
+    } else {
+        tick_spore_counter(*displayed_spores, target, time.delta_secs_f64())
+    };
+
     // Update spore count
     if let Ok(mut text) = spore_display.single_mut() {
         text.0 = format!(
             "Spores: {}/{}",
-            current_level.total_spores_earned.trunc() as i32,
-            current_level.target_score.trunc() as i32,
+            format_spores(displayed_spores.floor()),
+            format_spores(current_level.target_score),
         );
     }
 
@@ -472,6 +783,7 @@ fn update_spore_display(
 fn update_mushroom_buttons(
     game_state: Res<GameState>,
     definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
     current_level: Res<CurrentLevel>,
     selected: Res<SelectedMushroomType>,
     mut buttons: Query<(
@@ -516,7 +828,11 @@ fn update_mushroom_buttons(
             // Update description (second child)
             if let Some(desc_entity) = children.iter().nth(1) {
                 if let Ok(mut text) = texts.get_mut(desc_entity) {
-                    text.0 = def.description.clone();
+                    text.0 = if def.needs_downstream() {
+                        format!("{}\n{}", def.description, NEEDS_DOWNSTREAM_HINT)
+                    } else {
+                        def.description.clone()
+                    };
                 }
             }
 
@@ -525,7 +841,8 @@ fn update_mushroom_buttons(
                 if let Ok(mut text) = texts.get_mut(stats_entity) {
                     text.0 = format!(
                         "Production: {} | Uses: {}/turn",
-                        def.base_production, def.max_uses_per_turn
+                        def.base_production,
+                        upgrades.max_uses(button.mushroom_type, def)
                     );
                 }
             }
@@ -535,21 +852,68 @@ fn update_mushroom_buttons(
 
 fn update_chain_info(
     chain_manager: Res<ChainManager>,
+    chain_config: Res<ChainConfig>,
     current_phase: Option<Res<State<TurnPhase>>>,
     mut chain_display: Query<&mut Text, With<ChainInfoDisplay>>,
 ) {
     if let Ok(mut text) = chain_display.single_mut() {
         if let Some(phase) = current_phase {
             if *phase.get() == TurnPhase::Chain {
-                if chain_manager.chain_started_this_turn {
+                if chain_manager.any_chain_started() {
                     let _active_chains = chain_manager.chains.iter().filter(|c| c.active).count();
                     let total_spores: f64 =
                         chain_manager.chains.iter().map(|c| c.total_spores).sum();
 
+                    text.0 = if chain_manager.has_active_chains() {
+                        let mut summary = format!(
+                            "Chain Active! {} activations queued | {} spores generated",
+                            chain_manager.activation_queue.len(),
+                            format_spores(total_spores)
+                        );
+                        // Energy reaching the latest mushroom, after splits, boosts and decay
+                        if let Some(energy) = chain_manager
+                            .chains
+                            .iter()
+                            .filter(|chain| chain.active)
+                            .filter_map(|chain| chain.activations.last())
+                            .map(|activation| activation.energy)
+                            .next_back()
+                        {
+                            summary.push_str(&format!("\nEnergy: {energy:.2}"));
+                            if chain_config.decay_per_hop != 1.0 {
+                                summary.push_str(&format!(
+                                    " (x{:.2} per hop)",
+                                    chain_config.decay_per_hop
+                                ));
+                            }
+                        }
+                        summary
+                    } else {
+                        // Explain where the chain died out
+                        let endings = chain_manager
+                            .chains
+                            .iter()
+                            .map(|chain| chain.ending_summary())
+                            .filter(|summary| !summary.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let mut summary = format!(
+                            "Chain complete | {} spores generated\nEnded: {}",
+                            format_spores(total_spores),
+                            endings
+                        );
+                        if chain_manager.chains_remaining > 0 {
+                            summary.push_str(&format!(
+                                "\nClick a mushroom to start another chain ({} left)",
+                                chain_manager.chains_remaining
+                            ));
+                        }
+                        summary
+                    };
+                } else if chain_manager.chains_remaining > 1 {
                     text.0 = format!(
-                        "Chain Active! {} activations queued | {:.0} spores generated",
-                        chain_manager.activation_queue.len(),
-                        total_spores
+                        "Click a mushroom to start a chain reaction! ({} chains this turn)",
+                        chain_manager.chains_remaining
                     );
                 } else {
                     text.0 = "Click a mushroom to start a chain reaction!".to_string();
@@ -561,6 +925,112 @@ fn update_chain_info(
     }
 }
 
+fn update_mushroom_info_panel(
+    selected: Res<SelectedMushroomType>,
+    definitions: Res<MushroomDefinitions>,
+    upgrades: Res<MushroomUpgrades>,
+    game_state: Res<GameState>,
+    current_level: Res<CurrentLevel>,
+    mut panels: Query<(&mut Node, &Children), With<MushroomInfoPanel>>,
+    mut texts: Query<&mut Text>,
+) {
+    if !selected.is_changed() && !game_state.is_changed() && !upgrades.is_changed() {
+        return;
+    }
+
+    let Ok((mut node, children)) = panels.single_mut() else {
+        return;
+    };
+
+    let Some((mushroom_type, definition)) = selected.mushroom_type.and_then(|mushroom_type| {
+        definitions
+            .get(mushroom_type)
+            .map(|def| (mushroom_type, def))
+    }) else {
+        node.display = Display::None;
+        return;
+    };
+    node.display = Display::Flex;
+
+    let description = if definition.needs_downstream() {
+        format!("{}\n{}", definition.description, NEEDS_DOWNSTREAM_HINT)
+    } else {
+        definition.description.clone()
+    };
+    let tags: Vec<&str> = definition.tags.iter().map(|tag| tag.label()).collect();
+    let stats = format!(
+        "Production: {}\nUses: {}/turn\nCooldown: {:.1}s\nTags: {}",
+        definition.base_production,
+        upgrades.max_uses(mushroom_type, definition),
+        definition.cooldown_time,
+        tags.join(", ")
+    );
+    let unlock = if definitions.is_unlocked(mushroom_type, &game_state, current_level.level_index) {
+        "Unlocked".to_string()
+    } else {
+        match definition
+            .unlock_requirement
+            .progress(&game_state, current_level.level_index)
+        {
+            Some(progress) => format!(
+                "Locked - needs {} ({}/{})",
+                progress.goal,
+                format_spores(progress.current),
+                format_spores(progress.required)
+            ),
+            None => "Locked".to_string(),
+        }
+    };
+
+    let contents = [
+        definition.name.clone(),
+        description,
+        stats,
+        connection_pattern_diagram(definition),
+        unlock,
+    ];
+    for (child, content) in children.iter().zip(contents) {
+        if let Ok(mut text) = texts.get_mut(child) {
+            text.0 = content;
+        }
+    }
+}
+
+/// Small text grid of where a mushroom connects when facing up, with `M` at its centre
+fn connection_pattern_diagram(definition: &MushroomDefinition) -> String {
+    let reach = definition
+        .connection_points
+        .iter()
+        .map(|offset| offset.x.abs().max(offset.y.abs()))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    // Rows from the top so the default facing points up
+    (-reach..=reach)
+        .rev()
+        .map(|y| {
+            (-reach..=reach)
+                .map(|x| {
+                    if x == 0 && y == 0 {
+                        "M"
+                    } else if definition
+                        .connection_points
+                        .iter()
+                        .any(|offset| offset.x == x && offset.y == y)
+                    {
+                        "o"
+                    } else {
+                        "."
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn control_ui_visibility(
     level_state: Res<State<LevelState>>,
     mut ui_query: Query<&mut Visibility, With<GameplayUI>>,
@@ -578,3 +1048,30 @@ fn control_ui_visibility(
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frames at 60 fps until the counter shows `target`
+    fn frames_to_settle(target: f64) -> usize {
+        let mut displayed = 0.0;
+        let mut frames = 0;
+        while displayed < target {
+            let next = tick_spore_counter(displayed, target, 1.0 / 60.0);
+            assert!(next > displayed && next <= target);
+            displayed = next;
+            frames += 1;
+        }
+        frames
+    }
+
+    #[test]
+    fn huge_payouts_land_about_as_fast_as_small_ones() {
+        let small = frames_to_settle(500.0);
+        let huge = frames_to_settle(1e15);
+
+        assert!(small <= 60, "took {small} frames");
+        assert!(huge <= 60, "took {huge} frames");
+    }
+}
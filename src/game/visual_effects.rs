@@ -2,9 +2,14 @@
 
 use crate::{
     MainCamera,
-    game::mushrooms::{Mushroom, MushroomDefinitions, MushroomSprite},
+    game::{
+        mushrooms::{
+            Mushroom, MushroomDefinitions, MushroomDirection, MushroomSprite, SelectedMushroomType,
+        },
+        play_field::placement_preview::PlacementPreview,
+    },
 };
-use bevy::prelude::*;
+use bevy::{pbr::NotShadowCaster, prelude::*};
 use bevy_sprite3d::Sprite3d;
 
 pub(super) fn plugin(app: &mut App) {
@@ -14,10 +19,19 @@ pub(super) fn plugin(app: &mut App) {
             face_camera,
             update_mushroom_sprite_direction,
             update_activation_animations,
+            spawn_facing_indicators,
+            point_facing_indicators.after(face_camera),
         ),
     );
 }
 
+/// Height of the facing arrow below the mushroom's origin, just above the ground
+const FACING_INDICATOR_DROP: f32 = 0.45;
+
+/// Ground arrow showing which way a directional mushroom, or the preview, is facing
+#[derive(Component)]
+struct FacingIndicator;
+
 /// Component for entities that should face the camera
 #[derive(Component)]
 pub struct FaceCamera;
@@ -129,6 +143,79 @@ fn update_mushroom_sprite_direction(
     }
 }
 
+/// Give directional mushrooms and previews an arrow on the ground
+///
+/// Mushrooms whose connections look the same every way round don't get one.
+fn spawn_facing_indicators(
+    mut commands: Commands,
+    added: Query<(Entity, Option<&Mushroom>, Has<PlacementPreview>), Added<MushroomDirection>>,
+    definitions: Res<MushroomDefinitions>,
+    selected_type: Res<SelectedMushroomType>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut handles: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+) {
+    for (entity, mushroom, is_preview) in &added {
+        let mushroom_type = match mushroom {
+            Some(mushroom) => Some(mushroom.0),
+            None if is_preview => selected_type.mushroom_type,
+            None => None,
+        };
+        let Some(definition) = mushroom_type.and_then(|ty| definitions.get(ty)) else {
+            continue;
+        };
+        if !definition.is_directional() {
+            continue;
+        }
+
+        let (mesh, material) = handles
+            .get_or_insert_with(|| {
+                // Flat triangle pointing forward (-Z), wound to face up
+                let mesh = meshes.add(Triangle3d::new(
+                    Vec3::new(0.0, 0.0, -0.45),
+                    Vec3::new(-0.15, 0.0, -0.25),
+                    Vec3::new(0.15, 0.0, -0.25),
+                ));
+                let material = materials.add(StandardMaterial {
+                    base_color: Color::srgba(1.0, 1.0, 1.0, 0.8),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    cull_mode: None,
+                    ..default()
+                });
+                (mesh, material)
+            })
+            .clone();
+
+        commands.spawn((
+            Name::new("Facing Indicator"),
+            FacingIndicator,
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_xyz(0.0, -FACING_INDICATOR_DROP, 0.0),
+            NotShadowCaster,
+            Pickable::IGNORE,
+            ChildOf(entity),
+        ));
+    }
+}
+
+/// Turn the arrows to their parent's facing
+///
+/// Runs every frame since the preview's own transform turns with the camera, so the
+/// arrow has to cancel that out to stay fixed to the grid.
+fn point_facing_indicators(
+    mut indicators: Query<(&mut Transform, &ChildOf), With<FacingIndicator>>,
+    parents: Query<(&Transform, &MushroomDirection), Without<FacingIndicator>>,
+) {
+    for (mut transform, child_of) in &mut indicators {
+        let Ok((parent_transform, direction)) = parents.get(child_of.parent()) else {
+            continue;
+        };
+        transform.rotation = parent_transform.rotation.inverse() * direction.to_quat();
+    }
+}
+
 /// Update activation animations for mushrooms
 fn update_activation_animations(
     mut commands: Commands,
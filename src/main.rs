@@ -5,11 +5,12 @@
 
 mod asset_tracking;
 mod audio;
-// #[cfg(feature = "dev")]
-// mod dev_tools;
+#[cfg(feature = "dev")]
+mod dev_tools;
 mod game;
 mod menus;
 mod screens;
+mod settings;
 mod theme;
 
 use bevy::{
@@ -67,7 +68,7 @@ impl Plugin for AppPlugin {
         });
 
         app.insert_resource(GlobalVolume {
-            volume: Volume::Linear(0.3),
+            volume: Volume::Linear(settings::DEFAULT_GLOBAL_VOLUME),
         });
 
         app.add_plugins(Text3dPlugin {
@@ -80,10 +81,11 @@ impl Plugin for AppPlugin {
             asset_tracking::plugin,
             audio::plugin,
             game::plugin,
-            // #[cfg(feature = "dev")]
-            // dev_tools::plugin,
+            #[cfg(feature = "dev")]
+            dev_tools::plugin,
             menus::plugin,
             screens::plugin,
+            settings::plugin,
             theme::plugin,
         ));
 
@@ -165,6 +167,8 @@ fn spawn_camera(mut commands: Commands) {
             ..default()
         },
         MainCamera,
+        // Spatial sounds, like mushroom activations, are heard from the camera
+        SpatialListener::new(4.0),
         Camera::default(),
         PanOrbitCamera {
             button_orbit: MouseButton::Middle,
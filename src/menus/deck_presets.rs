@@ -0,0 +1,304 @@
+//! The deck presets menu, reached from the pause menu.
+//!
+//! Lists the saved presets and lets the player save the current deck as a new one,
+//! pick one for the next level, or delete it. The name for the next save is typed
+//! straight into the menu, it starts as the next free numbered name. Saving under a
+//! name that's taken replaces that preset.
+
+use bevy::{
+    input::{
+        ButtonState,
+        common_conditions::input_just_pressed,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+
+use crate::{
+    game::carddeck::{
+        deck::{Deck, DiscardPile},
+        hand::Hand,
+        presets::{ActiveDeckPreset, DeckPreset, DeckPresets},
+    },
+    menus::Menu,
+    theme::{assets::ThemeAssets, prelude::*, widget::slice_2_slicer},
+};
+
+/// Longest preset name that can be typed
+const MAX_NAME_LENGTH: usize = 24;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PresetName>();
+    app.add_systems(
+        OnEnter(Menu::DeckPresets),
+        (reset_preset_name, spawn_deck_presets_menu).chain(),
+    );
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+            type_preset_name,
+            refresh_deck_presets_menu
+                .run_if(resource_changed::<DeckPresets>.or(resource_changed::<ActiveDeckPreset>)),
+            update_preset_name_label.run_if(resource_changed::<PresetName>),
+        )
+            .chain()
+            .run_if(in_state(Menu::DeckPresets)),
+    );
+}
+
+/// Root of the menu, rebuilt whenever the presets change
+#[derive(Component)]
+struct DeckPresetsMenu;
+
+/// Name the current deck is saved under
+#[derive(Resource, Default, Debug)]
+struct PresetName(String);
+
+/// Label showing the name being typed
+#[derive(Component)]
+struct PresetNameLabel;
+
+fn spawn_deck_presets_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme_assets: Res<ThemeAssets>,
+    presets: Res<DeckPresets>,
+    active_preset: Res<ActiveDeckPreset>,
+    preset_name: Res<PresetName>,
+) {
+    let font_handle = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
+    let active_text = match &active_preset.0 {
+        Some(name) => format!("Next level uses: {name}"),
+        None => "Next level uses: random deck".to_string(),
+    };
+    let rows: Vec<(String, String)> = presets
+        .presets
+        .iter()
+        .map(|preset| {
+            (
+                preset.name.clone(),
+                format!("{} ({} cards)", preset.name, preset.card_count()),
+            )
+        })
+        .collect();
+
+    commands
+        .spawn((
+            widget::ui_root("Deck Presets Menu", Some(font_handle.clone())),
+            DeckPresetsMenu,
+            GlobalZIndex(2),
+            StateScoped(Menu::DeckPresets),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("Deck Presets", Some(font_handle.clone())));
+            parent.spawn(widget::label(active_text, Some(font_handle.clone())));
+
+            parent
+                .spawn((
+                    Name::new("Preset Grid"),
+                    Node {
+                        display: Display::Grid,
+                        row_gap: Px(10.0),
+                        column_gap: Px(30.0),
+                        grid_template_columns: RepeatedGridTrack::auto(3),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                ))
+                .with_children(|grid| {
+                    for (name, label) in rows {
+                        let use_name = name.clone();
+                        grid.spawn(widget::label(label, Some(font_handle.clone())));
+                        grid.spawn(widget::button_sliced(
+                            "Use",
+                            move |_: Trigger<Pointer<Click>>,
+                                  mut active_preset: ResMut<ActiveDeckPreset>| {
+                                active_preset.0 = Some(use_name.clone());
+                            },
+                            theme_assets.slice_2.clone(),
+                            slice_2_slicer(),
+                            font_handle.clone(),
+                        ));
+                        grid.spawn(widget::button_sliced(
+                            "Delete",
+                            move |_: Trigger<Pointer<Click>>,
+                                  mut presets: ResMut<DeckPresets>,
+                                  mut active_preset: ResMut<ActiveDeckPreset>| {
+                                presets.remove(&name);
+                                if active_preset.0.as_deref() == Some(name.as_str()) {
+                                    active_preset.0 = None;
+                                }
+                            },
+                            theme_assets.slice_2.clone(),
+                            slice_2_slicer(),
+                            font_handle.clone(),
+                        ));
+                    }
+                });
+
+            parent.spawn((
+                widget::label(preset_name_text(&preset_name.0), Some(font_handle.clone())),
+                PresetNameLabel,
+            ));
+            parent.spawn(widget::button_sliced(
+                "Save current deck",
+                save_current_deck,
+                theme_assets.slice_2.clone(),
+                slice_2_slicer(),
+                font_handle.clone(),
+            ));
+            parent.spawn(widget::button_sliced(
+                "Use random deck",
+                use_random_deck,
+                theme_assets.slice_2.clone(),
+                slice_2_slicer(),
+                font_handle.clone(),
+            ));
+            parent.spawn(widget::button_sliced(
+                "Back",
+                go_back_on_click,
+                theme_assets.slice_2.clone(),
+                slice_2_slicer(),
+                font_handle.clone(),
+            ));
+        });
+}
+
+fn refresh_deck_presets_menu(
+    mut commands: Commands,
+    menus: Query<Entity, With<DeckPresetsMenu>>,
+    asset_server: Res<AssetServer>,
+    theme_assets: Res<ThemeAssets>,
+    presets: Res<DeckPresets>,
+    active_preset: Res<ActiveDeckPreset>,
+    preset_name: Res<PresetName>,
+) {
+    for entity in &menus {
+        commands.entity(entity).despawn();
+    }
+    spawn_deck_presets_menu(
+        commands,
+        asset_server,
+        theme_assets,
+        presets,
+        active_preset,
+        preset_name,
+    );
+}
+
+fn preset_name_text(name: &str) -> String {
+    format!("Name: {name}_")
+}
+
+fn reset_preset_name(presets: Res<DeckPresets>, mut preset_name: ResMut<PresetName>) {
+    preset_name.0 = presets.next_name();
+}
+
+/// Apply a key press to the name being typed
+fn edit_preset_name(name: &mut String, key: &Key) {
+    match key {
+        Key::Backspace => {
+            name.pop();
+        }
+        Key::Space => push_name_chars(name, " "),
+        Key::Character(text) => push_name_chars(name, text),
+        _ => {}
+    }
+}
+
+fn push_name_chars(name: &mut String, text: &str) {
+    for c in text.chars().filter(|c| !c.is_control()) {
+        if name.chars().count() >= MAX_NAME_LENGTH {
+            return;
+        }
+        // Names can't start with a space
+        if c == ' ' && name.is_empty() {
+            continue;
+        }
+        name.push(c);
+    }
+}
+
+fn type_preset_name(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut preset_name: ResMut<PresetName>,
+) {
+    for input in keyboard_input.read() {
+        if input.state == ButtonState::Pressed {
+            edit_preset_name(&mut preset_name.0, &input.logical_key);
+        }
+    }
+}
+
+fn update_preset_name_label(
+    preset_name: Res<PresetName>,
+    mut labels: Query<&mut Text, With<PresetNameLabel>>,
+) {
+    for mut text in &mut labels {
+        text.0 = preset_name_text(&preset_name.0);
+    }
+}
+
+fn save_current_deck(
+    _: Trigger<Pointer<Click>>,
+    deck: Res<Deck>,
+    hand: Res<Hand>,
+    discard: Res<DiscardPile>,
+    mut presets: ResMut<DeckPresets>,
+    mut preset_name: ResMut<PresetName>,
+) {
+    let name = match preset_name.0.trim() {
+        "" => presets.next_name(),
+        typed => typed.to_string(),
+    };
+    let preset = DeckPreset::from_cards(name, &deck, &hand, &discard);
+    info!(
+        "Saved deck preset {} ({} cards)",
+        preset.name,
+        preset.card_count()
+    );
+    presets.save(preset);
+    preset_name.0 = presets.next_name();
+}
+
+fn use_random_deck(_: Trigger<Pointer<Click>>, mut active_preset: ResMut<ActiveDeckPreset>) {
+    active_preset.0 = None;
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typing_a_preset_name() {
+        let mut name = String::new();
+        for key in [
+            Key::Space,
+            Key::Character("Pu".into()),
+            Key::Character("lse".into()),
+            Key::Space,
+            Key::Character("x".into()),
+            Key::Backspace,
+            Key::Character("rush".into()),
+            Key::Enter,
+        ] {
+            edit_preset_name(&mut name, &key);
+        }
+        assert_eq!(name, "Pulse rush");
+
+        for _ in 0..40 {
+            edit_preset_name(&mut name, &Key::Character("a".into()));
+        }
+        assert_eq!(name.chars().count(), MAX_NAME_LENGTH);
+    }
+}
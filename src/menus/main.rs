@@ -2,13 +2,15 @@
 
 use std::time::Duration;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_tweening::{Animator, RepeatCount, RepeatStrategy, Tween, lens::UiPositionLens};
 
 use crate::{
     asset_tracking::ResourceHandles,
+    game::save::{ContinueGame, SavedGame},
     menus::Menu,
-    screens::{Screen, assets::ScreenAssets},
+    screens::{Screen, assets::ScreenAssets, transition::FadeToScreen},
+    settings::{AnimationStyle, ReducedMotion},
     theme::{
         assets::ThemeAssets,
         widget::{self, slice_2_slicer},
@@ -20,12 +22,26 @@ pub(super) fn plugin(app: &mut App) {
         OnEnter(Menu::Main),
         (spawn_main_menu, spawn_main_menu_art_assets),
     );
+    app.add_systems(Update, shift_parallax_layers.run_if(in_state(Menu::Main)));
 }
 
 //component to store floating parameters for spores
 #[derive(Component)]
 struct Spore;
 
+/// Most a layer of depth 1.0 moves away from its resting place, in pixels
+const PARALLAX_STRENGTH: f32 = 24.0;
+/// How quickly layers catch up with the cursor
+const PARALLAX_FOLLOW_RATE: f32 = 4.0;
+/// Speed of the slow drift used while the cursor is outside the window
+const PARALLAX_DRIFT_SPEED: f32 = 0.3;
+
+/// Menu art that shifts with the cursor, nearer layers have a larger `depth`
+#[derive(Component)]
+struct ParallaxLayer {
+    depth: f32,
+}
+
 // spawn all menu widgets
 
 fn spawn_main_menu(
@@ -33,74 +49,66 @@ fn spawn_main_menu(
     _screen_assets: Res<ScreenAssets>,
     theme_assets: Res<ThemeAssets>,
     asset_server: Res<AssetServer>,
+    saved_game: Res<SavedGame>,
 ) {
     let font_handle = asset_server.load("fonts/PixelOperatorMonoHB.ttf");
 
-    commands.spawn((
-        widget::ui_root("Main Menu", Some(font_handle.clone())),
-        GlobalZIndex(2),
-        StateScoped(Menu::Main),
-        #[cfg(not(target_family = "wasm"))]
-        children![
-            widget::button_sliced(
+    commands
+        .spawn((
+            widget::ui_root("Main Menu", Some(font_handle.clone())),
+            GlobalZIndex(2),
+            StateScoped(Menu::Main),
+        ))
+        .with_children(|parent| {
+            // Only offered once there's a run to pick back up
+            if saved_game.0.is_some() {
+                parent.spawn(widget::button_sliced(
+                    "Continue",
+                    continue_saved_game,
+                    theme_assets.slice_2.clone(),
+                    slice_2_slicer(),
+                    font_handle.clone(),
+                ));
+            }
+            parent.spawn(widget::button_sliced(
                 "Play",
                 enter_loading_or_gameplay_screen,
                 theme_assets.slice_2.clone(),
                 slice_2_slicer(),
-                font_handle.clone()
-            ),
-            widget::button_sliced(
+                font_handle.clone(),
+            ));
+            parent.spawn(widget::button_sliced(
                 "Settings",
                 open_settings_menu,
                 theme_assets.slice_2.clone(),
                 slice_2_slicer(),
-                font_handle.clone()
-            ),
-            widget::button_sliced(
+                font_handle.clone(),
+            ));
+            parent.spawn(widget::button_sliced(
                 "Credits",
                 open_credits_menu,
                 theme_assets.slice_2.clone(),
                 slice_2_slicer(),
-                font_handle.clone()
-            ),
-            widget::button_sliced(
+                font_handle.clone(),
+            ));
+            #[cfg(not(target_family = "wasm"))]
+            parent.spawn(widget::button_sliced(
                 "Exit",
                 exit_app,
                 theme_assets.slice_2.clone(),
                 slice_2_slicer(),
-                font_handle.clone()
-            ),
-        ],
-        #[cfg(target_family = "wasm")]
-        children![
-            widget::button_sliced(
-                "Play",
-                enter_loading_or_gameplay_screen,
-                theme_assets.slice_2.clone(),
-                slice_2_slicer(),
-                font_handle.clone()
-            ),
-            widget::button_sliced(
-                "Settings",
-                open_settings_menu,
-                theme_assets.slice_2.clone(),
-                slice_2_slicer(),
-                font_handle.clone()
-            ),
-            widget::button_sliced(
-                "Credits",
-                open_credits_menu,
-                theme_assets.slice_2.clone(),
-                slice_2_slicer(),
-                font_handle.clone()
-            ),
-        ],
-    ));
+                font_handle.clone(),
+            ));
+        });
 }
 
 // spawn all main menu art assets
 
-fn spawn_main_menu_art_assets(mut commands: Commands, screen_assets: Res<ScreenAssets>) {
+fn spawn_main_menu_art_assets(
+    mut commands: Commands,
+    screen_assets: Res<ScreenAssets>,
+    animation_style: Res<AnimationStyle>,
+) {
     commands.spawn((
         Name::new("Main Menu - Art"),
         Node {
@@ -130,6 +138,7 @@ fn spawn_main_menu_art_assets(mut commands: Commands, screen_assets: Res<ScreenA
                 children![(
                     Name::new("Splash Art"),
                     Pickable::IGNORE,
+                    ParallaxLayer { depth: 0.4 },
                     ImageNode::new(screen_assets.titlescreen.clone()),
                     Node {
                         left: Val::Percent(22.5),
@@ -153,6 +162,7 @@ fn spawn_main_menu_art_assets(mut commands: Commands, screen_assets: Res<ScreenA
                 children![(
                     Name::new("Game Title"),
                     Pickable::IGNORE,
+                    ParallaxLayer { depth: 0.7 },
                     ImageNode::new(screen_assets.gametitle.clone()),
                     Node {
                         left: Val::Percent(-70.0),
@@ -177,6 +187,7 @@ fn spawn_main_menu_art_assets(mut commands: Commands, screen_assets: Res<ScreenA
             ..default()
         },
         Pickable::IGNORE,
+        ParallaxLayer { depth: 1.0 },
         GlobalZIndex(0),
         StateScoped(Menu::Main),
         children![(
@@ -190,7 +201,7 @@ fn spawn_main_menu_art_assets(mut commands: Commands, screen_assets: Res<ScreenA
             Pickable::IGNORE,
             Animator::new(
                 Tween::new(
-                    EaseFunction::QuadraticInOut,
+                    animation_style.ease(),
                     Duration::from_secs(2),
                     UiPositionLens {
                         start: UiRect::new(
@@ -219,12 +230,26 @@ fn spawn_main_menu_art_assets(mut commands: Commands, screen_assets: Res<ScreenA
 fn enter_loading_or_gameplay_screen(
     _: Trigger<Pointer<Click>>,
     resource_handles: Res<ResourceHandles>,
-    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
 ) {
     if resource_handles.is_all_done() {
-        next_screen.set(Screen::Gameplay);
+        commands.trigger(FadeToScreen(Screen::Gameplay));
     } else {
-        next_screen.set(Screen::Loading);
+        commands.trigger(FadeToScreen(Screen::Loading));
+    }
+}
+
+/// Restore the saved run, then enter gameplay the same way "Play" does
+fn continue_saved_game(
+    _: Trigger<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut commands: Commands,
+) {
+    commands.trigger(ContinueGame);
+    if resource_handles.is_all_done() {
+        commands.trigger(FadeToScreen(Screen::Gameplay));
+    } else {
+        commands.trigger(FadeToScreen(Screen::Loading));
     }
 }
 
@@ -242,3 +267,41 @@ fn exit_app(_: Trigger<Pointer<Click>>, mut app_exit: EventWriter<AppExit>) {
 }
 
 //system for making spores move in the main menu
+
+/// Shift the art layers against the cursor, or drift slowly without one
+///
+/// Layers are moved through their margins, so the layout and the spore tween's
+/// positions are left alone.
+fn shift_parallax_layers(
+    time: Res<Time>,
+    reduced_motion: Res<ReducedMotion>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut layers: Query<(&ParallaxLayer, &mut Node)>,
+    mut offset: Local<Vec2>,
+) {
+    let target = if reduced_motion.0 {
+        Vec2::ZERO
+    } else if let Some(cursor) = window.cursor_position() {
+        // -1..1 from the window centre, moving away from the cursor
+        let size = window.size().max(Vec2::ONE);
+        -(cursor / size * 2.0 - Vec2::ONE)
+    } else {
+        let t = time.elapsed_secs() * PARALLAX_DRIFT_SPEED;
+        Vec2::new(t.sin(), (t * 0.7).cos() * 0.5)
+    };
+
+    *offset = if reduced_motion.0 {
+        Vec2::ZERO
+    } else {
+        offset.lerp(target, (PARALLAX_FOLLOW_RATE * time.delta_secs()).min(1.0))
+    };
+
+    for (layer, mut node) in &mut layers {
+        let shift = *offset * PARALLAX_STRENGTH * layer.depth;
+        // Skip the write when nothing moved, it would redo the layout
+        if node.margin.left != Val::Px(shift.x) || node.margin.top != Val::Px(shift.y) {
+            node.margin.left = Val::Px(shift.x);
+            node.margin.top = Val::Px(shift.y);
+        }
+    }
+}
@@ -3,8 +3,9 @@
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
 use crate::{
+    game::save::SaveProgress,
     menus::Menu,
-    screens::Screen,
+    screens::{Screen, transition::FadeToScreen},
     theme::{
         assets::ThemeAssets,
         widget::{self, slice_2_slicer},
@@ -45,6 +46,20 @@ fn spawn_pause_menu(
                 slice_2_slicer(),
                 font_handle.clone()
             ),
+            widget::button_sliced(
+                "Deck Presets",
+                open_deck_presets_menu,
+                theme_assets.slice_2.clone(),
+                slice_2_slicer(),
+                font_handle.clone()
+            ),
+            widget::button_sliced(
+                "Save Now",
+                save_now,
+                theme_assets.slice_2.clone(),
+                slice_2_slicer(),
+                font_handle.clone()
+            ),
             widget::button_sliced(
                 "Quit",
                 quit_to_title,
@@ -60,12 +75,21 @@ fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextStat
     next_menu.set(Menu::Settings);
 }
 
+fn open_deck_presets_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::DeckPresets);
+}
+
 fn close_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
 
-fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
+fn save_now(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(SaveProgress::Manual);
+}
+
+fn quit_to_title(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(SaveProgress::Auto);
+    commands.trigger(FadeToScreen(Screen::Title));
 }
 
 fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
@@ -2,27 +2,78 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{
+    audio::Volume, ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*,
+    ui::Val::*,
+};
 
 use crate::{
     game::fixed_timestep::FixedTimestepConfig,
     menus::Menu,
     screens::Screen,
+    settings::{
+        AlwaysShowConnections, AnimationStyle, AutoSave, ColorblindMode, ConnectionWidthMode,
+        DEFAULT_GLOBAL_VOLUME, KeyBindings, ParticleQuality, PreviewOpacity, ReducedMotion,
+        ShowEnergyTags, SmartRotate, TurnSummary, UiScaleFactor, key_label,
+    },
     theme::{assets::ThemeAssets, prelude::*, widget::slice_2_slicer},
 };
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PendingRebind>();
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
+    app.add_systems(OnExit(Menu::Settings), cancel_rebind);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
+        (
+            // Escape cancels a rebind instead of leaving the menu
+            go_back.run_if(
+                in_state(Menu::Settings)
+                    .and(input_just_pressed(KeyCode::Escape))
+                    .and(|pending: Res<PendingRebind>| pending.0.is_none()),
+            ),
+            capture_rebind.run_if(in_state(Menu::Settings)),
+        )
+            .chain(),
     );
 
     app.register_type::<GlobalVolumeLabel>();
     app.register_type::<TimestepLabel>();
+    app.register_type::<AnimationStyleLabel>();
+    app.register_type::<ReducedMotionLabel>();
+    app.register_type::<UiScaleLabel>();
+    app.register_type::<ConnectionWidthLabel>();
+    app.register_type::<TurnSummaryLabel>();
+    app.register_type::<SmartRotateLabel>();
+    app.register_type::<AlwaysShowConnectionsLabel>();
+    app.register_type::<EnergyTagsLabel>();
+    app.register_type::<PreviewOpacityLabel>();
+    app.register_type::<ParticleQualityLabel>();
+    app.register_type::<AutoSaveLabel>();
+    app.register_type::<ColorblindModeLabel>();
+    app.register_type::<KeyBindingLabel>();
+    app.register_type::<ChoiceKeysLabel>();
     app.add_systems(
         Update,
-        (update_global_volume_label, update_timestep_label).run_if(in_state(Menu::Settings)),
+        (
+            update_global_volume_label,
+            update_timestep_label,
+            update_animation_style_label,
+            update_reduced_motion_label,
+            update_ui_scale_label,
+            update_connection_width_label,
+            update_turn_summary_label,
+            update_smart_rotate_label,
+            update_always_show_connections_label,
+            update_energy_tags_label,
+            update_preview_opacity_label,
+            update_particle_quality_label,
+            update_auto_save_label,
+            update_colorblind_mode_label,
+            update_key_binding_labels,
+            update_choice_keys_label,
+        )
+            .run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -39,6 +90,13 @@ fn spawn_settings_menu(
         children![
             widget::header("Settings", Some(font_handle.clone())),
             settings_grid(font_handle.clone()),
+            widget::button_sliced(
+                "Reset to Defaults",
+                reset_to_defaults,
+                theme_assets.slice_2.clone(),
+                slice_2_slicer(),
+                font_handle.clone()
+            ),
             widget::button_sliced(
                 "Back",
                 go_back_on_click,
@@ -50,6 +108,43 @@ fn spawn_settings_menu(
     ));
 }
 
+/// Put every setting back to its default, the labels pick it up on their next update
+fn reset_to_defaults(
+    _: Trigger<Pointer<Click>>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut timestep: ResMut<FixedTimestepConfig>,
+    mut animation_style: ResMut<AnimationStyle>,
+    mut reduced_motion: ResMut<ReducedMotion>,
+    mut ui_scale: ResMut<UiScaleFactor>,
+    mut connection_width: ResMut<ConnectionWidthMode>,
+    mut turn_summary: ResMut<TurnSummary>,
+    mut smart_rotate: ResMut<SmartRotate>,
+    mut always_show: ResMut<AlwaysShowConnections>,
+    mut energy_tags: ResMut<ShowEnergyTags>,
+    mut preview_opacity: ResMut<PreviewOpacity>,
+    mut particle_quality: ResMut<ParticleQuality>,
+    mut auto_save: ResMut<AutoSave>,
+    mut colorblind_mode: ResMut<ColorblindMode>,
+    mut key_bindings: ResMut<KeyBindings>,
+) {
+    global_volume.volume = Volume::Linear(DEFAULT_GLOBAL_VOLUME);
+    *timestep = default();
+    *animation_style = default();
+    *reduced_motion = default();
+    *ui_scale = default();
+    *connection_width = default();
+    *turn_summary = default();
+    *smart_rotate = default();
+    *always_show = default();
+    *energy_tags = default();
+    *preview_opacity = default();
+    *particle_quality = default();
+    *auto_save = default();
+    *colorblind_mode = default();
+    *key_bindings = default();
+    info!("Settings reset to defaults");
+}
+
 fn settings_grid(font: Handle<Font>) -> impl Bundle {
     (
         Name::new("Settings Grid"),
@@ -57,27 +152,61 @@ fn settings_grid(font: Handle<Font>) -> impl Bundle {
             display: Display::Grid,
             row_gap: Px(10.0),
             column_gap: Px(30.0),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            // Flexible columns so the grid still fits at larger UI scales
+            grid_template_columns: RepeatedGridTrack::minmax(
+                2,
+                MinTrackSizingFunction::Auto,
+                MaxTrackSizingFunction::Px(400.0),
+            ),
+            ..default()
+        },
+        // Spawned one by one, there are more rows than `children!` takes
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(setting_label("Master Volume", font.clone()));
+            parent.spawn(global_volume_widget(font.clone()));
+            parent.spawn(setting_label("Animation Style", font.clone()));
+            parent.spawn(animation_style_widget(font.clone()));
+            parent.spawn(setting_label("Reduced Motion", font.clone()));
+            parent.spawn(reduced_motion_widget(font.clone()));
+            parent.spawn(setting_label("Colorblind Mode", font.clone()));
+            parent.spawn(colorblind_mode_widget(font.clone()));
+            parent.spawn(setting_label("Particles", font.clone()));
+            parent.spawn(particle_quality_widget(font.clone()));
+            parent.spawn(setting_label("UI Scale", font.clone()));
+            parent.spawn(ui_scale_widget(font.clone()));
+            parent.spawn(setting_label("Mycelium Width", font.clone()));
+            parent.spawn(connection_width_widget(font.clone()));
+            parent.spawn(setting_label("Turn Summary", font.clone()));
+            parent.spawn(turn_summary_widget(font.clone()));
+            parent.spawn(setting_label("Smart Rotate", font.clone()));
+            parent.spawn(smart_rotate_widget(font.clone()));
+            parent.spawn(setting_label("Always Show Mycelium", font.clone()));
+            parent.spawn(always_show_connections_widget(font.clone()));
+            parent.spawn(setting_label("Energy Tags", font.clone()));
+            parent.spawn(energy_tags_widget(font.clone()));
+            parent.spawn(setting_label("Preview Opacity", font.clone()));
+            parent.spawn(preview_opacity_widget(font.clone()));
+            parent.spawn(setting_label("Advance Dialogue", font.clone()));
+            parent.spawn(key_binding_widget(RebindAction::Advance, font.clone()));
+            parent.spawn(setting_label("Dialogue Choices", font.clone()));
+            parent.spawn(choice_keys_widget(font.clone()));
+            parent.spawn(setting_label("Rotate", font.clone()));
+            parent.spawn(key_binding_widget(RebindAction::Rotate, font.clone()));
+            parent.spawn(setting_label("Auto-Save", font.clone()));
+            parent.spawn(auto_save_widget(font.clone()));
+            // parent.spawn(setting_label("Game Speed (Hz)", font.clone()));
+            // parent.spawn(timestep_widget(font.clone()));
+        })),
+    )
+}
+
+fn setting_label(text: &'static str, font: Handle<Font>) -> impl Bundle {
+    (
+        widget::label(text, Some(font)),
+        Node {
+            justify_self: JustifySelf::End,
             ..default()
         },
-        children![
-            (
-                widget::label("Master Volume", Some(font.clone())),
-                Node {
-                    justify_self: JustifySelf::End,
-                    ..default()
-                }
-            ),
-            global_volume_widget(font.clone()),
-            // (
-            //     widget::label("Game Speed (Hz)", Some(font.clone())),
-            //     Node {
-            //         justify_self: JustifySelf::End,
-            //         ..default()
-            //     }
-            // ),
-            // timestep_widget(font.clone()),
-        ],
     )
 }
 
@@ -129,6 +258,643 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn animation_style_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Animation Style Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_animation_style),
+            (
+                Name::new("Current Animation Style"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), AnimationStyleLabel)],
+            ),
+            widget::button_small(">", next_animation_style),
+        ],
+    )
+}
+
+fn previous_animation_style(_: Trigger<Pointer<Click>>, mut style: ResMut<AnimationStyle>) {
+    *style = style.previous();
+}
+
+fn next_animation_style(_: Trigger<Pointer<Click>>, mut style: ResMut<AnimationStyle>) {
+    *style = style.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AnimationStyleLabel;
+
+fn update_animation_style_label(
+    style: Res<AnimationStyle>,
+    mut label: Single<&mut Text, With<AnimationStyleLabel>>,
+) {
+    label.0 = style.label().to_string();
+}
+
+fn colorblind_mode_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Colorblind Mode Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_colorblind_mode),
+            (
+                Name::new("Current Colorblind Mode"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), ColorblindModeLabel)],
+            ),
+            widget::button_small(">", next_colorblind_mode),
+        ],
+    )
+}
+
+fn previous_colorblind_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<ColorblindMode>) {
+    *mode = mode.previous();
+}
+
+fn next_colorblind_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<ColorblindMode>) {
+    *mode = mode.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ColorblindModeLabel;
+
+fn update_colorblind_mode_label(
+    mode: Res<ColorblindMode>,
+    mut label: Single<&mut Text, With<ColorblindModeLabel>>,
+) {
+    label.0 = mode.label().to_string();
+}
+
+fn particle_quality_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Particle Quality Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_particle_quality),
+            (
+                Name::new("Current Particle Quality"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), ParticleQualityLabel)],
+            ),
+            widget::button_small(">", next_particle_quality),
+        ],
+    )
+}
+
+fn previous_particle_quality(_: Trigger<Pointer<Click>>, mut quality: ResMut<ParticleQuality>) {
+    *quality = quality.previous();
+}
+
+fn next_particle_quality(_: Trigger<Pointer<Click>>, mut quality: ResMut<ParticleQuality>) {
+    *quality = quality.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ParticleQualityLabel;
+
+fn update_particle_quality_label(
+    quality: Res<ParticleQuality>,
+    mut label: Single<&mut Text, With<ParticleQualityLabel>>,
+) {
+    label.0 = quality.label().to_string();
+}
+
+fn reduced_motion_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Reduced Motion Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_reduced_motion),
+            (
+                Name::new("Current Reduced Motion"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), ReducedMotionLabel)],
+            ),
+            widget::button_small(">", toggle_reduced_motion),
+        ],
+    )
+}
+
+fn toggle_reduced_motion(_: Trigger<Pointer<Click>>, mut reduced_motion: ResMut<ReducedMotion>) {
+    reduced_motion.0 = !reduced_motion.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ReducedMotionLabel;
+
+fn update_reduced_motion_label(
+    reduced_motion: Res<ReducedMotion>,
+    mut label: Single<&mut Text, With<ReducedMotionLabel>>,
+) {
+    label.0 = if reduced_motion.0 { "On" } else { "Off" }.to_string();
+}
+
+fn ui_scale_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("UI Scale Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower_ui_scale),
+            (
+                Name::new("Current UI Scale"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), UiScaleLabel)],
+            ),
+            widget::button_small("+", raise_ui_scale),
+        ],
+    )
+}
+
+fn lower_ui_scale(_: Trigger<Pointer<Click>>, mut factor: ResMut<UiScaleFactor>) {
+    factor.0 = (factor.0 - UiScaleFactor::STEP).max(UiScaleFactor::MIN);
+}
+
+fn raise_ui_scale(_: Trigger<Pointer<Click>>, mut factor: ResMut<UiScaleFactor>) {
+    factor.0 = (factor.0 + UiScaleFactor::STEP).min(UiScaleFactor::MAX);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct UiScaleLabel;
+
+fn update_ui_scale_label(
+    factor: Res<UiScaleFactor>,
+    mut label: Single<&mut Text, With<UiScaleLabel>>,
+) {
+    let percent = 100.0 * factor.0;
+    label.0 = format!("{percent:3.0}%");
+}
+
+fn preview_opacity_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Preview Opacity Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower_preview_opacity),
+            (
+                Name::new("Current Preview Opacity"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), PreviewOpacityLabel)],
+            ),
+            widget::button_small("+", raise_preview_opacity),
+        ],
+    )
+}
+
+fn lower_preview_opacity(_: Trigger<Pointer<Click>>, mut opacity: ResMut<PreviewOpacity>) {
+    opacity.0 = (opacity.0 - PreviewOpacity::STEP).max(PreviewOpacity::MIN);
+}
+
+fn raise_preview_opacity(_: Trigger<Pointer<Click>>, mut opacity: ResMut<PreviewOpacity>) {
+    opacity.0 = (opacity.0 + PreviewOpacity::STEP).min(PreviewOpacity::MAX);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PreviewOpacityLabel;
+
+fn update_preview_opacity_label(
+    opacity: Res<PreviewOpacity>,
+    mut label: Single<&mut Text, With<PreviewOpacityLabel>>,
+) {
+    let percent = 100.0 * opacity.0;
+    label.0 = format!("{percent:3.0}%");
+}
+
+fn connection_width_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Connection Width Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_connection_width),
+            (
+                Name::new("Current Connection Width"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), ConnectionWidthLabel)],
+            ),
+            widget::button_small(">", toggle_connection_width),
+        ],
+    )
+}
+
+fn toggle_connection_width(_: Trigger<Pointer<Click>>, mut mode: ResMut<ConnectionWidthMode>) {
+    *mode = mode.toggled();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ConnectionWidthLabel;
+
+fn update_connection_width_label(
+    mode: Res<ConnectionWidthMode>,
+    mut label: Single<&mut Text, With<ConnectionWidthLabel>>,
+) {
+    label.0 = mode.label().to_string();
+}
+
+fn turn_summary_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Turn Summary Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_turn_summary),
+            (
+                Name::new("Current Turn Summary"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), TurnSummaryLabel)],
+            ),
+            widget::button_small(">", toggle_turn_summary),
+        ],
+    )
+}
+
+fn toggle_turn_summary(_: Trigger<Pointer<Click>>, mut turn_summary: ResMut<TurnSummary>) {
+    turn_summary.0 = !turn_summary.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TurnSummaryLabel;
+
+fn update_turn_summary_label(
+    turn_summary: Res<TurnSummary>,
+    mut label: Single<&mut Text, With<TurnSummaryLabel>>,
+) {
+    label.0 = if turn_summary.0 { "On" } else { "Off" }.to_string();
+}
+
+fn smart_rotate_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Smart Rotate Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_smart_rotate),
+            (
+                Name::new("Current Smart Rotate"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), SmartRotateLabel)],
+            ),
+            widget::button_small(">", toggle_smart_rotate),
+        ],
+    )
+}
+
+fn toggle_smart_rotate(_: Trigger<Pointer<Click>>, mut smart_rotate: ResMut<SmartRotate>) {
+    smart_rotate.0 = !smart_rotate.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SmartRotateLabel;
+
+fn update_smart_rotate_label(
+    smart_rotate: Res<SmartRotate>,
+    mut label: Single<&mut Text, With<SmartRotateLabel>>,
+) {
+    label.0 = if smart_rotate.0 { "On" } else { "Off" }.to_string();
+}
+
+fn always_show_connections_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Always Show Mycelium Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_always_show_connections),
+            (
+                Name::new("Current Always Show Mycelium"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(
+                    widget::label("", Some(font.clone())),
+                    AlwaysShowConnectionsLabel
+                )],
+            ),
+            widget::button_small(">", toggle_always_show_connections),
+        ],
+    )
+}
+
+fn toggle_always_show_connections(
+    _: Trigger<Pointer<Click>>,
+    mut always_show: ResMut<AlwaysShowConnections>,
+) {
+    always_show.0 = !always_show.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AlwaysShowConnectionsLabel;
+
+fn update_always_show_connections_label(
+    always_show: Res<AlwaysShowConnections>,
+    mut label: Single<&mut Text, With<AlwaysShowConnectionsLabel>>,
+) {
+    label.0 = if always_show.0 { "On" } else { "Off" }.to_string();
+}
+
+fn energy_tags_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Energy Tags Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_energy_tags),
+            (
+                Name::new("Current Energy Tags"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), EnergyTagsLabel)],
+            ),
+            widget::button_small(">", toggle_energy_tags),
+        ],
+    )
+}
+
+fn toggle_energy_tags(_: Trigger<Pointer<Click>>, mut energy_tags: ResMut<ShowEnergyTags>) {
+    energy_tags.0 = !energy_tags.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct EnergyTagsLabel;
+
+fn update_energy_tags_label(
+    energy_tags: Res<ShowEnergyTags>,
+    mut label: Single<&mut Text, With<EnergyTagsLabel>>,
+) {
+    label.0 = if energy_tags.0 { "On" } else { "Off" }.to_string();
+}
+
+fn auto_save_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Auto-Save Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_auto_save),
+            (
+                Name::new("Current Auto-Save"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), AutoSaveLabel)],
+            ),
+            widget::button_small(">", toggle_auto_save),
+        ],
+    )
+}
+
+fn toggle_auto_save(_: Trigger<Pointer<Click>>, mut auto_save: ResMut<AutoSave>) {
+    auto_save.0 = !auto_save.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AutoSaveLabel;
+
+fn update_auto_save_label(
+    auto_save: Res<AutoSave>,
+    mut label: Single<&mut Text, With<AutoSaveLabel>>,
+) {
+    label.0 = if auto_save.0 { "On" } else { "Off" }.to_string();
+}
+
+/// A key binding that's waiting for the player to press its new key
+#[derive(Resource, Default, Debug)]
+struct PendingRebind(Option<RebindAction>);
+
+/// Actions bound to a single key
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+enum RebindAction {
+    Advance,
+    Rotate,
+}
+
+impl RebindAction {
+    fn key(&self, bindings: &KeyBindings) -> KeyCode {
+        match self {
+            RebindAction::Advance => bindings.advance,
+            RebindAction::Rotate => bindings.rotate,
+        }
+    }
+
+    fn key_mut<'a>(&self, bindings: &'a mut KeyBindings) -> &'a mut KeyCode {
+        match self {
+            RebindAction::Advance => &mut bindings.advance,
+            RebindAction::Rotate => &mut bindings.rotate,
+        }
+    }
+}
+
+fn key_binding_widget(action: RebindAction, font: Handle<Font>) -> impl Bundle {
+    let start_rebind = move |_: Trigger<Pointer<Click>>, mut pending: ResMut<PendingRebind>| {
+        pending.0 = Some(action);
+    };
+    (
+        Name::new("Key Binding Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", start_rebind),
+            (
+                Name::new("Current Key Binding"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(
+                    widget::label("", Some(font.clone())),
+                    KeyBindingLabel(action)
+                )],
+            ),
+            widget::button_small(">", start_rebind),
+        ],
+    )
+}
+
+/// Bind the next key pressed to the pending action, Escape keeps the old key
+fn capture_rebind(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut pending: ResMut<PendingRebind>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    let Some(action) = pending.0 else {
+        return;
+    };
+    let Some(key) = keyboard.get_just_pressed().next().copied() else {
+        return;
+    };
+
+    pending.0 = None;
+    if key == KeyCode::Escape {
+        return;
+    }
+    *action.key_mut(&mut bindings) = key;
+    info!("Bound {:?} to {:?}", action, key);
+}
+
+fn cancel_rebind(mut pending: ResMut<PendingRebind>) {
+    pending.0 = None;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct KeyBindingLabel(RebindAction);
+
+fn update_key_binding_labels(
+    bindings: Res<KeyBindings>,
+    pending: Res<PendingRebind>,
+    mut labels: Query<(&mut Text, &KeyBindingLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        text.0 = if pending.0 == Some(label.0) {
+            "Press a key".to_string()
+        } else {
+            key_label(label.0.key(&bindings))
+        };
+    }
+}
+
+fn choice_keys_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Choice Keys Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_choice_keys),
+            (
+                Name::new("Current Choice Keys"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    min_width: Px(120.0),
+                    ..default()
+                },
+                children![(widget::label("", Some(font.clone())), ChoiceKeysLabel)],
+            ),
+            widget::button_small(">", next_choice_keys),
+        ],
+    )
+}
+
+fn previous_choice_keys(_: Trigger<Pointer<Click>>, mut bindings: ResMut<KeyBindings>) {
+    bindings.choice_keys = bindings.choice_keys.previous();
+}
+
+fn next_choice_keys(_: Trigger<Pointer<Click>>, mut bindings: ResMut<KeyBindings>) {
+    bindings.choice_keys = bindings.choice_keys.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ChoiceKeysLabel;
+
+fn update_choice_keys_label(
+    bindings: Res<KeyBindings>,
+    mut label: Single<&mut Text, With<ChoiceKeysLabel>>,
+) {
+    label.0 = bindings.choice_keys.label().to_string();
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -3,7 +3,11 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*};
+use crate::{
+    asset_tracking::ResourceHandles,
+    screens::{Screen, transition::FadeToScreen},
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
@@ -23,8 +27,8 @@ fn spawn_loading_screen(mut commands: Commands, asset_server: Res<AssetServer>)
     ));
 }
 
-fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Gameplay);
+fn enter_gameplay_screen(mut commands: Commands) {
+    commands.trigger(FadeToScreen(Screen::Gameplay));
 }
 
 fn all_assets_loaded(resource_handles: Res<ResourceHandles>) -> bool {
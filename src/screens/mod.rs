@@ -5,6 +5,7 @@ mod gameplay;
 mod loading;
 mod splash;
 mod title;
+pub mod transition;
 
 use bevy::prelude::*;
 
@@ -17,6 +18,7 @@ pub(super) fn plugin(app: &mut App) {
         loading::plugin,
         splash::plugin,
         title::plugin,
+        transition::plugin,
     ));
 }
 
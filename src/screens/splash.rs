@@ -6,7 +6,11 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{AppSystems, screens::Screen, theme::prelude::*};
+use crate::{
+    AppSystems,
+    screens::{Screen, transition::FadeToScreen},
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Spawn splash screen.
@@ -136,12 +140,12 @@ fn tick_splash_timer(time: Res<Time>, mut timer: ResMut<SplashTimer>) {
     timer.0.tick(time.delta());
 }
 
-fn check_splash_timer(timer: ResMut<SplashTimer>, mut next_screen: ResMut<NextState<Screen>>) {
+fn check_splash_timer(timer: ResMut<SplashTimer>, mut commands: Commands) {
     if timer.0.just_finished() {
-        next_screen.set(Screen::Title);
+        commands.trigger(FadeToScreen(Screen::Title));
     }
 }
 
-fn enter_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
+fn enter_title_screen(mut commands: Commands) {
+    commands.trigger(FadeToScreen(Screen::Title));
 }
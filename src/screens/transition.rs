@@ -0,0 +1,128 @@
+//! Fade-to-black transitions between screens.
+//!
+//! Trigger [`FadeToScreen`] instead of setting `NextState<Screen>` directly: the old
+//! screen fades out, the state changes (so state-scoped entities are cleaned up as usual),
+//! then the new screen fades in.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_tweening::{Animator, Tween, TweenCompleted, lens::UiBackgroundColorLens};
+
+use crate::screens::Screen;
+
+/// How long each half of the fade takes
+const FADE_DURATION: f32 = 0.35;
+
+/// Tween user data marking the end of a fade out
+const FADE_OUT_COMPLETE: u64 = 100;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PendingScreen>();
+    app.add_systems(Startup, spawn_fade_overlay);
+
+    app.add_observer(start_fade_out);
+    app.add_observer(finish_fade_out);
+
+    for screen in [
+        Screen::Splash,
+        Screen::Title,
+        Screen::Loading,
+        Screen::Gameplay,
+    ] {
+        app.add_systems(OnEnter(screen), start_fade_in);
+    }
+}
+
+/// Event to fade out of the current screen and into another
+#[derive(Event, Debug)]
+pub struct FadeToScreen(pub Screen);
+
+/// Screen waiting for the fade out to finish
+#[derive(Resource, Default, Debug)]
+struct PendingScreen(Option<Screen>);
+
+/// Marker for the full-screen fade overlay
+#[derive(Component)]
+struct FadeOverlay;
+
+fn spawn_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Fade Overlay"),
+        FadeOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.0)),
+        GlobalZIndex(100),
+        Pickable::IGNORE,
+    ));
+}
+
+fn fade_tween(start: Color, end: Color) -> Tween<BackgroundColor> {
+    Tween::new(
+        EaseFunction::QuadraticInOut,
+        Duration::from_secs_f32(FADE_DURATION),
+        UiBackgroundColorLens { start, end },
+    )
+}
+
+fn start_fade_out(
+    trigger: Trigger<FadeToScreen>,
+    mut commands: Commands,
+    mut pending: ResMut<PendingScreen>,
+    overlay: Query<(Entity, &BackgroundColor), With<FadeOverlay>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    // Ignore repeated requests while a fade is already running
+    if pending.0.is_some() {
+        return;
+    }
+
+    let target = trigger.0;
+    let Ok((entity, color)) = overlay.single() else {
+        next_screen.set(target);
+        return;
+    };
+
+    info!("Fading out to {:?}", target);
+    pending.0 = Some(target);
+    commands.entity(entity).insert(Animator::new(
+        fade_tween(color.0, Color::BLACK).with_completed_event(FADE_OUT_COMPLETE),
+    ));
+}
+
+fn finish_fade_out(
+    trigger: Trigger<TweenCompleted>,
+    mut pending: ResMut<PendingScreen>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if trigger.user_data != FADE_OUT_COMPLETE {
+        return;
+    }
+
+    if let Some(target) = pending.0.take() {
+        next_screen.set(target);
+    }
+}
+
+/// Fade back in from however dark the overlay currently is
+fn start_fade_in(
+    mut commands: Commands,
+    overlay: Query<(Entity, &BackgroundColor), With<FadeOverlay>>,
+) {
+    let Ok((entity, color)) = overlay.single() else {
+        return;
+    };
+    if color.0.alpha() <= 0.0 {
+        return;
+    }
+
+    commands.entity(entity).insert(Animator::new(fade_tween(
+        color.0,
+        Color::BLACK.with_alpha(0.0),
+    )));
+}
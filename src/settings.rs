@@ -0,0 +1,611 @@
+//! Player-facing settings shared between the menus and the game.
+//!
+//! Key bindings, the colorblind mode, the preview opacity and auto-save are kept between
+//! sessions, in a RON file next to the game on native builds and in the browser's local storage on the web. They're read at startup and written
+//! whenever they change.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::save::{read_stored, write_stored};
+
+/// File next to the game on native builds, local storage key on the web
+#[cfg(not(target_family = "wasm"))]
+const SETTINGS_NAME: &str = "settings.ron";
+#[cfg(target_family = "wasm")]
+const SETTINGS_NAME: &str = "psychocybin_settings";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<AnimationStyle>();
+    app.init_resource::<AnimationStyle>();
+    app.register_type::<ReducedMotion>();
+    app.init_resource::<ReducedMotion>();
+    app.register_type::<ConnectionWidthMode>();
+    app.init_resource::<ConnectionWidthMode>();
+    app.register_type::<TurnSummary>();
+    app.init_resource::<TurnSummary>();
+    app.register_type::<SmartRotate>();
+    app.init_resource::<SmartRotate>();
+    app.register_type::<AlwaysShowConnections>();
+    app.init_resource::<AlwaysShowConnections>();
+    app.register_type::<ShowEnergyTags>();
+    app.init_resource::<ShowEnergyTags>();
+    app.register_type::<PreviewOpacity>();
+    app.init_resource::<PreviewOpacity>();
+    app.register_type::<ParticleQuality>();
+    app.init_resource::<ParticleQuality>();
+    app.register_type::<UiScaleFactor>();
+    app.init_resource::<UiScaleFactor>();
+    app.register_type::<AutoSave>();
+    app.init_resource::<AutoSave>();
+    app.register_type::<ColorblindMode>();
+    app.init_resource::<ColorblindMode>();
+    app.register_type::<KeyBindings>();
+    app.init_resource::<KeyBindings>();
+    app.add_systems(Startup, load_settings);
+    app.add_systems(
+        Update,
+        (
+            apply_ui_scale.run_if(resource_changed::<UiScaleFactor>),
+            warn_key_binding_collisions.run_if(resource_changed::<KeyBindings>),
+            save_settings.run_if(stored_settings_changed),
+        ),
+    );
+}
+
+/// Settings kept between sessions, missing fields keep their defaults
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+struct StoredSettings {
+    #[serde(default)]
+    key_bindings: KeyBindings,
+    #[serde(default)]
+    colorblind_mode: ColorblindMode,
+    #[serde(default)]
+    preview_opacity: PreviewOpacity,
+    #[serde(default)]
+    auto_save: AutoSave,
+}
+
+fn load_settings(
+    mut key_bindings: ResMut<KeyBindings>,
+    mut colorblind_mode: ResMut<ColorblindMode>,
+    mut preview_opacity: ResMut<PreviewOpacity>,
+    mut auto_save: ResMut<AutoSave>,
+) {
+    let Some(contents) = read_stored(SETTINGS_NAME) else {
+        return;
+    };
+
+    match ron::from_str::<StoredSettings>(&contents) {
+        Ok(settings) => {
+            *key_bindings = settings.key_bindings;
+            *colorblind_mode = settings.colorblind_mode;
+            *preview_opacity = settings.preview_opacity.clamped();
+            *auto_save = settings.auto_save;
+        }
+        Err(error) => warn!("Ignoring unreadable settings: {error}"),
+    }
+}
+
+/// Changed since they were loaded, so starting the game doesn't rewrite them
+fn stored_settings_changed(
+    key_bindings: Res<KeyBindings>,
+    colorblind_mode: Res<ColorblindMode>,
+    preview_opacity: Res<PreviewOpacity>,
+    auto_save: Res<AutoSave>,
+) -> bool {
+    (key_bindings.is_changed() && !key_bindings.is_added())
+        || (colorblind_mode.is_changed() && !colorblind_mode.is_added())
+        || (preview_opacity.is_changed() && !preview_opacity.is_added())
+        || (auto_save.is_changed() && !auto_save.is_added())
+}
+
+fn save_settings(
+    key_bindings: Res<KeyBindings>,
+    colorblind_mode: Res<ColorblindMode>,
+    preview_opacity: Res<PreviewOpacity>,
+    auto_save: Res<AutoSave>,
+) {
+    let settings = StoredSettings {
+        key_bindings: *key_bindings,
+        colorblind_mode: *colorblind_mode,
+        preview_opacity: *preview_opacity,
+        auto_save: *auto_save,
+    };
+    match ron::ser::to_string_pretty(&settings, default()) {
+        Ok(contents) => write_stored(SETTINGS_NAME, &contents),
+        Err(error) => warn!("Couldn't serialize the settings: {error}"),
+    }
+}
+
+/// Opacity of the placement preview mushroom
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct PreviewOpacity(pub f32);
+
+impl Default for PreviewOpacity {
+    fn default() -> Self {
+        Self(0.5)
+    }
+}
+
+impl PreviewOpacity {
+    /// Kept short of both ends, so the preview never vanishes or looks already planted
+    pub const MIN: f32 = 0.2;
+    pub const MAX: f32 = 0.9;
+    pub const STEP: f32 = 0.1;
+
+    /// Pulled back into range, for values read from a hand-edited settings file
+    pub fn clamped(self) -> Self {
+        Self(self.0.clamp(Self::MIN, Self::MAX))
+    }
+}
+
+/// Linear volume the game starts at, set on [`GlobalVolume`] at startup
+pub const DEFAULT_GLOBAL_VOLUME: f32 = 0.3;
+
+/// Multiplier for all UI text and node sizes, for high-DPI and small screens
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource)]
+pub struct UiScaleFactor(pub f32);
+
+impl Default for UiScaleFactor {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl UiScaleFactor {
+    pub const MIN: f32 = 0.75;
+    pub const MAX: f32 = 2.0;
+    pub const STEP: f32 = 0.25;
+}
+
+/// Bevy's [`UiScale`] scales every `Val::Px` and font size in the UI, so the factor is applied there
+fn apply_ui_scale(factor: Res<UiScaleFactor>, mut ui_scale: ResMut<UiScale>) {
+    ui_scale.0 = factor.0.clamp(UiScaleFactor::MIN, UiScaleFactor::MAX);
+}
+
+/// Turns off purely decorative motion, like looping previews and ambient effects
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct ReducedMotion(pub bool);
+
+/// Show a summary card between turns, off for players who want to move quickly
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct TurnSummary(pub bool);
+
+impl Default for TurnSummary {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Save progress on finishing a level, leaving gameplay and quitting the game
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct AutoSave(pub bool);
+
+impl Default for AutoSave {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Automatically face the placement preview towards the most connections on hover
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct SmartRotate(pub bool);
+
+/// Keep highlighting where placed mushrooms connect outside the planting phase
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct AlwaysShowConnections(pub bool);
+
+/// Tag each chain activation with the energy it arrived with, to show how splits and
+/// amplifiers change it
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct ShowEnergyTags(pub bool);
+
+/// What sets the thickness of mycelium connection lines
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum ConnectionWidthMode {
+    /// Width follows the connection's strength
+    #[default]
+    Static,
+    /// Width follows the energy that last travelled along the connection
+    Dynamic,
+}
+
+impl ConnectionWidthMode {
+    /// Display name for the settings menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConnectionWidthMode::Static => "Static",
+            ConnectionWidthMode::Dynamic => "Dynamic",
+        }
+    }
+
+    pub fn toggled(&self) -> ConnectionWidthMode {
+        match self {
+            ConnectionWidthMode::Static => ConnectionWidthMode::Dynamic,
+            ConnectionWidthMode::Dynamic => ConnectionWidthMode::Static,
+        }
+    }
+}
+
+/// How many particles effects spawn, lowered for the web and weaker hardware
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum ParticleQuality {
+    Off,
+    Low,
+    #[default]
+    High,
+}
+
+impl ParticleQuality {
+    /// Multiplier on each effect's spawn rate and particle capacity
+    pub fn particle_scale(&self) -> f32 {
+        match self {
+            ParticleQuality::Off => 0.0,
+            ParticleQuality::Low => 0.3,
+            ParticleQuality::High => 1.0,
+        }
+    }
+
+    /// Display name for the settings menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            ParticleQuality::Off => "Off",
+            ParticleQuality::Low => "Low",
+            ParticleQuality::High => "High",
+        }
+    }
+
+    pub fn next(&self) -> ParticleQuality {
+        match self {
+            ParticleQuality::Off => ParticleQuality::Low,
+            ParticleQuality::Low => ParticleQuality::High,
+            ParticleQuality::High => ParticleQuality::Off,
+        }
+    }
+
+    pub fn previous(&self) -> ParticleQuality {
+        match self {
+            ParticleQuality::Off => ParticleQuality::High,
+            ParticleQuality::Low => ParticleQuality::Off,
+            ParticleQuality::High => ParticleQuality::Low,
+        }
+    }
+}
+
+/// Colours for the placement preview highlights on the field, so the highlight types stay
+/// apart for colour vision deficiencies
+///
+/// Any mode other than `Off` also adds patterns to the highlights, so they don't rely on
+/// colour alone.
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorblindMode {
+    /// Display name for the settings menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorblindMode::Off => "Off",
+            ColorblindMode::Deuteranopia => "Deuteranopia",
+            ColorblindMode::Protanopia => "Protanopia",
+            ColorblindMode::Tritanopia => "Tritanopia",
+        }
+    }
+
+    pub fn next(&self) -> ColorblindMode {
+        match self {
+            ColorblindMode::Off => ColorblindMode::Deuteranopia,
+            ColorblindMode::Deuteranopia => ColorblindMode::Protanopia,
+            ColorblindMode::Protanopia => ColorblindMode::Tritanopia,
+            ColorblindMode::Tritanopia => ColorblindMode::Off,
+        }
+    }
+
+    pub fn previous(&self) -> ColorblindMode {
+        match self {
+            ColorblindMode::Off => ColorblindMode::Tritanopia,
+            ColorblindMode::Deuteranopia => ColorblindMode::Off,
+            ColorblindMode::Protanopia => ColorblindMode::Deuteranopia,
+            ColorblindMode::Tritanopia => ColorblindMode::Protanopia,
+        }
+    }
+}
+
+/// Keys for the actions that can be rebound from the settings menu
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct KeyBindings {
+    /// Advance dialogue
+    pub advance: KeyCode,
+    /// Pick dialogue choices and cards in the hand, the first key picks the first one
+    pub choice_keys: ChoiceKeys,
+    /// Rotate the placement preview
+    pub rotate: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            advance: KeyCode::Space,
+            choice_keys: ChoiceKeys::Digits,
+            rotate: KeyCode::KeyR,
+        }
+    }
+}
+
+/// Keys with fixed gameplay actions, rebinding an action onto one of them collides
+const FIXED_KEYS: [KeyCode; 11] = [
+    // Pause
+    KeyCode::Escape,
+    KeyCode::KeyP,
+    // End the phase
+    KeyCode::Enter,
+    KeyCode::NumpadEnter,
+    // Sort the hand
+    KeyCode::KeyS,
+    // Chain speed
+    KeyCode::Tab,
+    // Frame the board
+    KeyCode::KeyF,
+    // Compare placements
+    KeyCode::KeyC,
+    // Cinematic camera
+    KeyCode::KeyV,
+    // Watch the best run
+    KeyCode::KeyB,
+    // Undo a sweep delete, with Ctrl
+    KeyCode::KeyZ,
+];
+
+impl KeyBindings {
+    /// Keys bound to more than one action, including the fixed gameplay keys
+    pub fn collisions(&self) -> Vec<KeyCode> {
+        let mut keys = vec![self.advance, self.rotate];
+        keys.extend(self.choice_keys.keys());
+        keys.extend(FIXED_KEYS);
+
+        let mut collisions = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            if keys[i + 1..].contains(key) && !collisions.contains(key) {
+                collisions.push(*key);
+            }
+        }
+        collisions
+    }
+}
+
+/// Short name for a key, for the settings menu
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    ["Key", "Digit"]
+        .into_iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .map(str::to_string)
+        .unwrap_or(name)
+}
+
+/// Bindings are still applied when they collide, the first action checked for a key wins
+fn warn_key_binding_collisions(bindings: Res<KeyBindings>) {
+    for key in bindings.collisions() {
+        warn!("{:?} is bound to more than one action", key);
+    }
+}
+
+/// A run of keys for picking dialogue choices
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChoiceKeys {
+    /// 1 to 9 on the number row
+    #[default]
+    Digits,
+    /// 1 to 9 on the numpad
+    Numpad,
+    /// F1 to F9
+    FunctionKeys,
+}
+
+impl ChoiceKeys {
+    /// Keys for the first nine choices, in order
+    pub fn keys(&self) -> [KeyCode; 9] {
+        match self {
+            ChoiceKeys::Digits => [
+                KeyCode::Digit1,
+                KeyCode::Digit2,
+                KeyCode::Digit3,
+                KeyCode::Digit4,
+                KeyCode::Digit5,
+                KeyCode::Digit6,
+                KeyCode::Digit7,
+                KeyCode::Digit8,
+                KeyCode::Digit9,
+            ],
+            ChoiceKeys::Numpad => [
+                KeyCode::Numpad1,
+                KeyCode::Numpad2,
+                KeyCode::Numpad3,
+                KeyCode::Numpad4,
+                KeyCode::Numpad5,
+                KeyCode::Numpad6,
+                KeyCode::Numpad7,
+                KeyCode::Numpad8,
+                KeyCode::Numpad9,
+            ],
+            ChoiceKeys::FunctionKeys => [
+                KeyCode::F1,
+                KeyCode::F2,
+                KeyCode::F3,
+                KeyCode::F4,
+                KeyCode::F5,
+                KeyCode::F6,
+                KeyCode::F7,
+                KeyCode::F8,
+                KeyCode::F9,
+            ],
+        }
+    }
+
+    /// Display name for the settings menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChoiceKeys::Digits => "1-9",
+            ChoiceKeys::Numpad => "Numpad 1-9",
+            ChoiceKeys::FunctionKeys => "F1-F9",
+        }
+    }
+
+    pub fn next(&self) -> ChoiceKeys {
+        match self {
+            ChoiceKeys::Digits => ChoiceKeys::Numpad,
+            ChoiceKeys::Numpad => ChoiceKeys::FunctionKeys,
+            ChoiceKeys::FunctionKeys => ChoiceKeys::Digits,
+        }
+    }
+
+    pub fn previous(&self) -> ChoiceKeys {
+        match self {
+            ChoiceKeys::Digits => ChoiceKeys::FunctionKeys,
+            ChoiceKeys::Numpad => ChoiceKeys::Digits,
+            ChoiceKeys::FunctionKeys => ChoiceKeys::Numpad,
+        }
+    }
+}
+
+/// How UI tweens feel, picked from the settings menu
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum AnimationStyle {
+    Snappy,
+    #[default]
+    Smooth,
+    Bouncy,
+}
+
+impl AnimationStyle {
+    /// Easing function used when building tweens
+    pub fn ease(&self) -> EaseFunction {
+        match self {
+            AnimationStyle::Snappy => EaseFunction::CubicOut,
+            AnimationStyle::Smooth => EaseFunction::QuadraticInOut,
+            AnimationStyle::Bouncy => EaseFunction::BackOut,
+        }
+    }
+
+    /// Display name for the settings menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnimationStyle::Snappy => "Snappy",
+            AnimationStyle::Smooth => "Smooth",
+            AnimationStyle::Bouncy => "Bouncy",
+        }
+    }
+
+    pub fn next(&self) -> AnimationStyle {
+        match self {
+            AnimationStyle::Snappy => AnimationStyle::Smooth,
+            AnimationStyle::Smooth => AnimationStyle::Bouncy,
+            AnimationStyle::Bouncy => AnimationStyle::Snappy,
+        }
+    }
+
+    pub fn previous(&self) -> AnimationStyle {
+        match self {
+            AnimationStyle::Snappy => AnimationStyle::Bouncy,
+            AnimationStyle::Smooth => AnimationStyle::Snappy,
+            AnimationStyle::Bouncy => AnimationStyle::Smooth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings_do_not_collide() {
+        assert!(KeyBindings::default().collisions().is_empty());
+    }
+
+    #[test]
+    fn keys_bound_twice_are_reported_once() {
+        let bindings = KeyBindings {
+            advance: KeyCode::KeyR,
+            choice_keys: ChoiceKeys::Digits,
+            rotate: KeyCode::KeyR,
+        };
+        assert_eq!(bindings.collisions(), vec![KeyCode::KeyR]);
+
+        // Colliding with a choice key counts too
+        let bindings = KeyBindings {
+            rotate: KeyCode::Digit3,
+            ..bindings
+        };
+        assert_eq!(bindings.collisions(), vec![KeyCode::Digit3]);
+    }
+
+    #[test]
+    fn binding_a_fixed_gameplay_key_collides() {
+        for key in [KeyCode::KeyS, KeyCode::Tab, KeyCode::Enter] {
+            let bindings = KeyBindings {
+                rotate: key,
+                ..default()
+            };
+            assert_eq!(bindings.collisions(), vec![key]);
+        }
+    }
+
+    #[test]
+    fn stored_settings_survive_a_round_trip() {
+        let settings = StoredSettings {
+            key_bindings: KeyBindings {
+                advance: KeyCode::Enter,
+                choice_keys: ChoiceKeys::FunctionKeys,
+                rotate: KeyCode::KeyQ,
+            },
+            colorblind_mode: ColorblindMode::Tritanopia,
+            preview_opacity: PreviewOpacity(0.7),
+            auto_save: AutoSave(false),
+        };
+
+        let contents = ron::ser::to_string_pretty(&settings, default()).unwrap();
+
+        assert_eq!(
+            ron::from_str::<StoredSettings>(&contents).unwrap(),
+            settings
+        );
+    }
+
+    #[test]
+    fn missing_settings_keep_their_defaults() {
+        let settings = ron::from_str::<StoredSettings>("()").unwrap();
+        assert_eq!(settings, StoredSettings::default());
+    }
+
+    #[test]
+    fn stored_preview_opacity_is_kept_in_range() {
+        assert_eq!(
+            PreviewOpacity(0.0).clamped(),
+            PreviewOpacity(PreviewOpacity::MIN)
+        );
+        assert_eq!(
+            PreviewOpacity(1.0).clamped(),
+            PreviewOpacity(PreviewOpacity::MAX)
+        );
+        assert_eq!(PreviewOpacity(0.6).clamped(), PreviewOpacity(0.6));
+    }
+}